@@ -0,0 +1,250 @@
+//! Compact board deltas for undo/redo support
+//!
+//! Instead of storing a full board snapshot per move, front-ends can keep
+//! a stack of [`BoardDelta`] values and apply or revert them in order.
+
+use serde::{Deserialize, Serialize};
+
+/// A single-cell change to a board
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardDelta {
+    /// The cell index that changed (0-80)
+    pub index: usize,
+    /// The cell value before the change (`None` for empty)
+    pub before: Option<u8>,
+    /// The cell value after the change (`None` for empty)
+    pub after: Option<u8>,
+}
+
+/// Reasons a delta cannot be applied to a board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeltaError {
+    /// The delta's index is outside the board
+    IndexOutOfRange(usize),
+    /// Two boards that should line up cell for cell have different lengths
+    LengthMismatch { expected: usize, actual: usize },
+    /// The board's current value doesn't match what the delta expects
+    Mismatch {
+        index: usize,
+        expected: Option<u8>,
+        actual: Option<u8>,
+    },
+}
+
+impl std::fmt::Display for DeltaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeltaError::IndexOutOfRange(index) => write!(f, "Cell index {} is out of range", index),
+            DeltaError::LengthMismatch { expected, actual } => {
+                write!(f, "Board has {} cells, expected {}", actual, expected)
+            }
+            DeltaError::Mismatch {
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Cell {} holds {:?} but the delta expects {:?}",
+                index, actual, expected
+            ),
+        }
+    }
+}
+
+impl BoardDelta {
+    /// Creates a delta that sets `index` to `after`, capturing the current value
+    ///
+    /// # Arguments
+    /// * `board` - The board before the change
+    /// * `index` - The cell to change (0-80)
+    /// * `after` - The new value (`None` to clear)
+    pub fn new(board: &[Option<u8>], index: usize, after: Option<u8>) -> Self {
+        Self {
+            index,
+            before: board[index],
+            after,
+        }
+    }
+
+    /// Returns the delta that undoes this one
+    pub fn inverse(&self) -> Self {
+        Self {
+            index: self.index,
+            before: self.after,
+            after: self.before,
+        }
+    }
+}
+
+/// Applies a delta to a board in-place
+///
+/// The cell must currently hold the delta's `before` value, which guards
+/// against replaying a stale undo stack onto a different board.
+///
+/// # Arguments
+/// * `board` - The board to modify
+/// * `delta` - The change to apply
+///
+/// # Returns
+/// `Ok(())` if applied, or the reason the delta doesn't fit the board
+pub fn apply_delta(board: &mut [Option<u8>], delta: &BoardDelta) -> Result<(), DeltaError> {
+    if delta.index >= board.len() {
+        return Err(DeltaError::IndexOutOfRange(delta.index));
+    }
+
+    if board[delta.index] != delta.before {
+        return Err(DeltaError::Mismatch {
+            index: delta.index,
+            expected: delta.before,
+            actual: board[delta.index],
+        });
+    }
+
+    board[delta.index] = delta.after;
+    Ok(())
+}
+
+/// Reverts a previously applied delta in-place
+///
+/// # Arguments
+/// * `board` - The board to modify
+/// * `delta` - The change to undo
+///
+/// # Returns
+/// `Ok(())` if reverted, or the reason the delta doesn't fit the board
+pub fn revert_delta(board: &mut [Option<u8>], delta: &BoardDelta) -> Result<(), DeltaError> {
+    apply_delta(board, &delta.inverse())
+}
+
+/// Clears every player entry, leaving only the puzzle's givens
+///
+/// # Arguments
+/// * `puzzle` - The original puzzle; its filled cells are the givens
+/// * `current` - The board being played
+///
+/// # Returns
+/// The board reset to its givens, or an error if `current` has a different
+/// length or has changed a given
+pub fn reset_to_givens(
+    puzzle: &[Option<u8>],
+    current: &[Option<u8>],
+) -> Result<Vec<Option<u8>>, DeltaError> {
+    if current.len() != puzzle.len() {
+        return Err(DeltaError::LengthMismatch {
+            expected: puzzle.len(),
+            actual: current.len(),
+        });
+    }
+
+    for (index, (&given, &actual)) in puzzle.iter().zip(current).enumerate() {
+        if given.is_some() && given != actual {
+            return Err(DeltaError::Mismatch {
+                index,
+                expected: given,
+                actual,
+            });
+        }
+    }
+
+    Ok(puzzle.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::BOARD_SIZE;
+
+    #[test]
+    fn test_apply_and_revert_sequence() {
+        let original = vec![None; BOARD_SIZE];
+        let mut board = original.clone();
+        let mut history = Vec::new();
+
+        for (index, value) in [(0, 5), (10, 3), (0, 7), (80, 9)] {
+            let delta = BoardDelta::new(&board, index, Some(value));
+            apply_delta(&mut board, &delta).unwrap();
+            history.push(delta);
+        }
+        assert_eq!(board[0], Some(7));
+        assert_eq!(board[10], Some(3));
+        assert_eq!(board[80], Some(9));
+
+        // Undo everything in reverse order
+        let snapshot = board.clone();
+        for delta in history.iter().rev() {
+            revert_delta(&mut board, delta).unwrap();
+        }
+        assert_eq!(board, original);
+
+        // Redo restores the final state
+        for delta in &history {
+            apply_delta(&mut board, delta).unwrap();
+        }
+        assert_eq!(board, snapshot);
+    }
+
+    #[test]
+    fn test_mismatched_delta_is_rejected() {
+        let mut board = vec![None; BOARD_SIZE];
+        board[4] = Some(2);
+
+        let stale = BoardDelta {
+            index: 4,
+            before: Some(6),
+            after: None,
+        };
+        assert_eq!(
+            apply_delta(&mut board, &stale),
+            Err(DeltaError::Mismatch {
+                index: 4,
+                expected: Some(6),
+                actual: Some(2)
+            })
+        );
+        assert_eq!(board[4], Some(2), "Board must be untouched on error");
+
+        let out_of_range = BoardDelta {
+            index: BOARD_SIZE,
+            before: None,
+            after: Some(1),
+        };
+        assert_eq!(
+            apply_delta(&mut board, &out_of_range),
+            Err(DeltaError::IndexOutOfRange(BOARD_SIZE))
+        );
+    }
+
+    #[test]
+    fn test_reset_to_givens() {
+        let mut puzzle = vec![None; BOARD_SIZE];
+        puzzle[0] = Some(5);
+        puzzle[40] = Some(9);
+
+        let mut current = puzzle.clone();
+        current[1] = Some(3);
+        current[80] = Some(7);
+
+        let reset = reset_to_givens(&puzzle, &current).unwrap();
+        assert_eq!(reset, puzzle);
+        assert_eq!(reset.iter().filter(|c| c.is_some()).count(), 2);
+
+        // A board whose givens were altered belongs to another puzzle
+        current[40] = Some(1);
+        assert_eq!(
+            reset_to_givens(&puzzle, &current),
+            Err(DeltaError::Mismatch {
+                index: 40,
+                expected: Some(9),
+                actual: Some(1)
+            })
+        );
+
+        assert_eq!(
+            reset_to_givens(&puzzle, &current[..80]),
+            Err(DeltaError::LengthMismatch {
+                expected: BOARD_SIZE,
+                actual: 80
+            })
+        );
+    }
+}
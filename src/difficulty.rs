@@ -3,8 +3,101 @@
 //! This module analyzes Sudoku puzzles to determine their difficulty level
 //! based on the solving techniques required and other complexity metrics.
 
-use crate::solver::HumanStyleSolver;
-use crate::types::{DifficultyAnalysis, DifficultyLevel, SolvingTechnique};
+use crate::grid::units;
+use crate::solver::{candidate_masks, HumanStyleSolver, SolveStep};
+use crate::types::{
+    Confidence, DifficultyAnalysis, DifficultyLevel, InputError, SolvingTechnique, GRID_SIZE,
+};
+use crate::validator::{
+    check_board_values, count_solutions, has_unique_solution, is_solvable_at_all,
+};
+use serde::{Deserialize, Serialize};
+
+/// Branching factor bands used to classify difficulty
+///
+/// A puzzle whose branching factor is at most `very_easy` can rate Very
+/// Easy, at most `easy` Easy, at most `medium` Medium and at most `hard`
+/// Hard; above that it rates Expert. The technique a puzzle needs still
+/// sets the level first, so the bands only move a rating within what the
+/// technique allows.
+///
+/// Serializes to a flat JSON object; fields missing when deserializing take
+/// their [`Default`] values.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct DifficultyThresholds {
+    pub very_easy: f64,
+    pub easy: f64,
+    pub medium: f64,
+    pub hard: f64,
+}
+
+impl Default for DifficultyThresholds {
+    fn default() -> Self {
+        Self {
+            very_easy: 1.7,
+            easy: 2.2,
+            medium: 3.8,
+            hard: 6.0,
+        }
+    }
+}
+
+impl DifficultyThresholds {
+    /// Checks that the bands are finite and rise from Very Easy to Hard
+    ///
+    /// # Returns
+    /// `Ok(())`, or the first band that is out of place
+    pub fn validate(&self) -> Result<(), ThresholdError> {
+        let bands = [
+            ("very_easy", self.very_easy),
+            ("easy", self.easy),
+            ("medium", self.medium),
+            ("hard", self.hard),
+        ];
+        if let Some(&(field, value)) = bands.iter().find(|(_, value)| !value.is_finite()) {
+            return Err(ThresholdError::NotFinite { field, value });
+        }
+        for pair in bands.windows(2) {
+            let ((lower, lower_value), (higher, higher_value)) = (pair[0], pair[1]);
+            if higher_value < lower_value {
+                return Err(ThresholdError::OutOfOrder { lower, higher });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A set of difficulty bands that can't classify puzzles consistently
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ThresholdError {
+    /// A band is NaN or infinite
+    NotFinite { field: &'static str, value: f64 },
+    /// A band is below the one for the next easier level
+    OutOfOrder {
+        lower: &'static str,
+        higher: &'static str,
+    },
+}
+
+impl std::fmt::Display for ThresholdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThresholdError::NotFinite { field, value } => {
+                write!(
+                    f,
+                    "Threshold {} must be a finite number, got {}",
+                    field, value
+                )
+            }
+            ThresholdError::OutOfOrder { lower, higher } => write!(
+                f,
+                "Threshold {} must not be below threshold {}",
+                higher, lower
+            ),
+        }
+    }
+}
 
 /// Analyzes the difficulty of a Sudoku puzzle
 ///
@@ -15,33 +108,291 @@ use crate::types::{DifficultyAnalysis, DifficultyLevel, SolvingTechnique};
 /// * `board` - The puzzle board to analyze
 ///
 /// # Returns
-/// A `DifficultyAnalysis` containing difficulty metrics
+/// A `DifficultyAnalysis` containing difficulty metrics. A board without
+/// empty cells has nothing to rate: check for [`Confidence::NoPuzzle`]
+/// before reading its placeholder VeryEasy level
 pub fn analyze_difficulty(board: &[Option<u8>]) -> DifficultyAnalysis {
-    let mut solver = HumanStyleSolver::new(board);
-    let _solved = solver.solve_with_techniques();
+    analyze_difficulty_weighted(board, None)
+}
+
+/// Analyzes difficulty with custom branching factor bands
+///
+/// # Arguments
+/// * `board` - The puzzle board to analyze
+/// * `thresholds` - The bands to classify by, e.g. tuned for an audience
+///
+/// # Returns
+/// A `DifficultyAnalysis` like [`analyze_difficulty`]'s, with the level
+/// classified by `thresholds`, or the [`ThresholdError`] that makes the
+/// bands unusable
+pub fn analyze_difficulty_with_thresholds(
+    board: &[Option<u8>],
+    thresholds: &DifficultyThresholds,
+) -> Result<DifficultyAnalysis, ThresholdError> {
+    thresholds.validate()?;
+    Ok(analyze_solve(board, None, thresholds, true))
+}
+
+/// Analyzes difficulty, estimating solve time from custom time weights
+///
+/// Each technique application on the solve path costs its weight in
+/// seconds; techniques without a weight use
+/// [`SolvingTechnique::default_seconds`]. A puzzle that logic can't finish
+/// adds one trial-and-error application.
+///
+/// # Arguments
+/// * `board` - The puzzle board to analyze
+/// * `time_weights` - Seconds per application for some techniques, e.g.
+///   from a publisher's timing data
+///
+/// # Returns
+/// A `DifficultyAnalysis` whose `estimated_seconds` uses the weights. A
+/// board without empty cells is reported with [`Confidence::NoPuzzle`],
+/// zero steps and zero seconds; one that has no solution at all with
+/// [`Confidence::Unsolvable`], rated Expert without solving.
+pub fn analyze_difficulty_weighted(
+    board: &[Option<u8>],
+    time_weights: Option<&[(SolvingTechnique, f64)]>,
+) -> DifficultyAnalysis {
+    analyze_solve(board, time_weights, &DifficultyThresholds::default(), true)
+}
+
+/// Analyzes difficulty along the easiest solve path
+///
+/// The solver already restarts from the easiest technique after every
+/// step, so its path is the easiest one it can find; no reordering would
+/// lower the hardest technique on it. What this changes is the grading:
+/// the hardest technique on that path is reported as is, without the
+/// clue-count estimate [`analyze_difficulty`] substitutes when the solve
+/// uses only singles.
+///
+/// # Arguments
+/// * `board` - The puzzle board to analyze
+///
+/// # Returns
+/// A `DifficultyAnalysis` graded by the easiest route to the solution
+pub fn analyze_difficulty_easiest(board: &[Option<u8>]) -> DifficultyAnalysis {
+    analyze_solve(board, None, &DifficultyThresholds::default(), false)
+}
+
+/// Analyzes difficulty from a human-style solve of the puzzle
+///
+/// With `estimate_singles` set, a solve that uses only singles is rated by
+/// the clue-count heuristic instead of its solve path.
+fn analyze_solve(
+    board: &[Option<u8>],
+    time_weights: Option<&[(SolvingTechnique, f64)]>,
+    thresholds: &DifficultyThresholds,
+    estimate_singles: bool,
+) -> DifficultyAnalysis {
+    if board.iter().all(|cell| cell.is_some()) {
+        return DifficultyAnalysis {
+            level: DifficultyLevel::VeryEasy,
+            hardest_technique: SolvingTechnique::NakedSingle,
+            technique_diversity: 0,
+            branching_factor: 1.0,
+            average_branching_during_solve: 1.0,
+            step_count: 0,
+            estimated_seconds: 0.0,
+            confidence: Confidence::NoPuzzle,
+        };
+    }
+
+    // Logic would only stall on a board without a completion, and the
+    // stall would be rated as if guessing could finish it
+    if !is_solvable_at_all(board) {
+        let branching_factor = HumanStyleSolver::new(board).calculate_branching_factor();
+        return DifficultyAnalysis {
+            level: DifficultyLevel::Expert,
+            hardest_technique: SolvingTechnique::TrialAndError,
+            technique_diversity: 0,
+            branching_factor,
+            average_branching_during_solve: branching_factor,
+            step_count: 0,
+            estimated_seconds: 0.0,
+            confidence: Confidence::Unsolvable,
+        };
+    }
+
+    let mut solver = HumanStyleSolver::new(board).with_trace();
+    let solved = solver.solve_with_techniques();
+    let step_count = solver.get_trace().len() as u32 + solver.get_elimination_count();
 
     let basic_technique = solver.get_hardest_technique_used();
     let techniques_used = solver.get_techniques_used();
     let branching_factor = solver.calculate_branching_factor();
 
     // If only basic techniques were found, use heuristic analysis for advanced puzzles
-    let hardest_technique = if basic_technique <= SolvingTechnique::HiddenSingle {
+    let estimate = if basic_technique <= SolvingTechnique::HiddenSingle && estimate_singles {
         analyze_difficulty_heuristic(board)
     } else {
-        basic_technique
+        basic_technique.clone()
+    };
+    // The floor applies to every stall, not just singles-only ones: a
+    // solver that got as far as pointing pairs before stalling still left
+    // the puzzle to something harder
+    let hardest_technique = if solved {
+        estimate
+    } else {
+        estimate.max(stalled_technique_floor(board))
     };
 
-    let level =
-        classify_difficulty_level(&hardest_technique, techniques_used.len(), branching_factor);
+    let mut level = classify_difficulty_level(
+        &hardest_technique,
+        techniques_used.len(),
+        branching_factor,
+        thresholds,
+    );
+    if solved
+        && basic_technique == SolvingTechnique::HiddenSingle
+        && is_hidden_single_heavy(solver.get_trace())
+    {
+        level = level.max(DifficultyLevel::Medium);
+    }
+
+    let mut applications = solver.get_technique_counts();
+    if !solved {
+        applications.push((SolvingTechnique::TrialAndError, 1));
+    }
+    let weight = |technique: &SolvingTechnique| {
+        time_weights
+            .and_then(|weights| weights.iter().find(|(t, _)| t == technique))
+            .map_or(technique.default_seconds(), |&(_, seconds)| seconds)
+    };
+    let estimated_seconds = applications
+        .iter()
+        .map(|(technique, count)| weight(technique) * *count as f64)
+        .sum();
 
     DifficultyAnalysis {
         level,
         hardest_technique,
         technique_diversity: techniques_used.len(),
         branching_factor,
+        average_branching_during_solve: solver.get_average_branching_factor(),
+        step_count,
+        estimated_seconds,
+        confidence: if solved {
+            Confidence::Solved
+        } else {
+            Confidence::Heuristic
+        },
+    }
+}
+
+/// Bounds a puzzle's difficulty level from below without solving it
+///
+/// Only the clue count and the starting candidates are looked at, so this
+/// is cheap enough to call on every candidate removal during generation.
+/// A puzzle is at least Medium when [`analyze_difficulty`] would rate its
+/// hardest technique above hidden singles either way: the clue count alone
+/// puts a singles-only solve past them, or no cell has a single candidate
+/// and no unit has a digit with a single place, so the solver can't start
+/// with singles.
+///
+/// # Arguments
+/// * `board` - The puzzle board to examine
+///
+/// # Returns
+/// A level never above the one `analyze_difficulty` reports
+pub fn cheap_difficulty_lower_bound(board: &[Option<u8>]) -> DifficultyLevel {
+    if board.iter().all(|cell| cell.is_some()) {
+        return DifficultyLevel::VeryEasy;
+    }
+    if analyze_difficulty_heuristic(board) > SolvingTechnique::HiddenSingle {
+        return DifficultyLevel::Medium;
+    }
+
+    let masks = candidate_masks(board);
+    let naked_single = masks.iter().any(|mask| mask.count_ones() == 1);
+    let hidden_single = units().iter().any(|unit| {
+        (0..GRID_SIZE).any(|bit| unit.iter().filter(|&&i| masks[i] >> bit & 1 == 1).count() == 1)
+    });
+    if naked_single || hidden_single {
+        DifficultyLevel::VeryEasy
+    } else {
+        DifficultyLevel::Medium
     }
 }
 
+/// One row of a bulk difficulty analysis
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalysisRecord {
+    /// Overall difficulty level
+    pub level: DifficultyLevel,
+    /// The most advanced technique needed to solve the puzzle
+    pub hardest_technique: SolvingTechnique,
+    /// Numeric weight of the hardest technique (its hint cost)
+    pub score: u32,
+    /// Average number of candidates per empty cell
+    pub branching_factor: f64,
+    /// Number of givens
+    pub clue_count: usize,
+    /// Number of solutions, capped at 2 (2 means "more than one")
+    pub solution_count: usize,
+}
+
+/// Analyzes many puzzles at once
+///
+/// # Arguments
+/// * `boards` - The puzzles to analyze
+///
+/// # Returns
+/// One entry per input, in order: a record, or the reason the board was rejected
+pub fn analyze_batch(boards: &[Vec<Option<u8>>]) -> Vec<Result<AnalysisRecord, InputError>> {
+    boards
+        .iter()
+        .map(|board| {
+            check_board_values(board)?;
+            let analysis = analyze_difficulty(board);
+            Ok(AnalysisRecord {
+                score: analysis.hardest_technique.hint_cost(),
+                level: analysis.level,
+                hardest_technique: analysis.hardest_technique,
+                branching_factor: analysis.branching_factor,
+                clue_count: board.iter().filter(|c| c.is_some()).count(),
+                solution_count: count_solutions(board, 2),
+            })
+        })
+        .collect()
+}
+
+/// What happens to a puzzle when one of its givens is removed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClueImpact {
+    /// The given that was removed (0-80)
+    pub index: usize,
+    /// Whether the puzzle still has exactly one solution without it
+    pub still_unique: bool,
+    /// Difficulty level of the puzzle without it
+    pub resulting_level: DifficultyLevel,
+}
+
+/// Analyzes the effect of removing each given in turn
+///
+/// Runs a uniqueness check and a full difficulty analysis per given, so it
+/// is meant for puzzle editors rather than real-time use.
+///
+/// # Arguments
+/// * `board` - The puzzle to examine
+///
+/// # Returns
+/// One entry per given, in index order
+pub fn clue_removal_impact(board: &[Option<u8>]) -> Vec<ClueImpact> {
+    (0..board.len())
+        .filter(|&index| board[index].is_some())
+        .map(|index| {
+            let mut reduced = board.to_vec();
+            reduced[index] = None;
+            ClueImpact {
+                index,
+                still_unique: has_unique_solution(&reduced),
+                resulting_level: analyze_difficulty(&reduced).level,
+            }
+        })
+        .collect()
+}
+
 /// Heuristic-based difficulty analysis for when advanced solver techniques are not implemented
 ///
 /// Uses puzzle characteristics like clue count, constraint density, and solving complexity
@@ -112,6 +463,42 @@ fn analyze_difficulty_heuristic(board: &[Option<u8>]) -> SolvingTechnique {
     }
 }
 
+/// Lowest technique credited to a puzzle that the implemented techniques can't finish
+///
+/// Stalling proves something beyond singles is needed. Sparse grids that
+/// stall are graded as expert, since they rarely yield to a single fish.
+fn stalled_technique_floor(board: &[Option<u8>]) -> SolvingTechnique {
+    let clue_count = board.iter().filter(|c| c.is_some()).count();
+    if clue_count <= 24 {
+        SolvingTechnique::XYWing
+    } else {
+        SolvingTechnique::XWing
+    }
+}
+
+/// Hidden singles needed before a singles-only puzzle feels like Medium
+const HEAVY_HIDDEN_SINGLE_COUNT: usize = 20;
+
+/// Average branching factor at those hidden singles for the same bump
+const HEAVY_HIDDEN_SINGLE_DEPTH: f64 = 2.0;
+
+/// Checks whether a solve path leans on many well-hidden singles
+///
+/// A hidden single found while the board still has many candidates per
+/// cell is much harder to spot than one near the end of a solve.
+fn is_hidden_single_heavy(trace: &[SolveStep]) -> bool {
+    let depths: Vec<f64> = trace
+        .iter()
+        .filter(|step| step.technique == SolvingTechnique::HiddenSingle)
+        .map(|step| step.branching_factor)
+        .collect();
+    if depths.len() < HEAVY_HIDDEN_SINGLE_COUNT {
+        return false;
+    }
+
+    depths.iter().sum::<f64>() / depths.len() as f64 >= HEAVY_HIDDEN_SINGLE_DEPTH
+}
+
 /// Calculates a complexity metric for the puzzle based on constraint density
 fn calculate_puzzle_complexity(board: &[Option<u8>]) -> f64 {
     let mut complexity = 0.0;
@@ -147,7 +534,7 @@ fn calculate_puzzle_complexity(board: &[Option<u8>]) -> f64 {
             + (35.0 - clue_count as f64) / 10.0;
     }
 
-    complexity.max(1.0).min(5.0)
+    complexity.clamp(1.0, 5.0)
 }
 
 /// Classifies the difficulty level based on solving requirements
@@ -155,20 +542,21 @@ fn classify_difficulty_level(
     hardest_technique: &SolvingTechnique,
     technique_count: usize,
     branching_factor: f64,
+    thresholds: &DifficultyThresholds,
 ) -> DifficultyLevel {
     // Use branching factor as a secondary classifier
     let bf_difficulty = match branching_factor {
-        bf if bf <= 1.7 => DifficultyLevel::VeryEasy,
-        bf if bf <= 2.2 => DifficultyLevel::Easy,
-        bf if bf <= 3.8 => DifficultyLevel::Medium,
-        bf if bf <= 6.0 => DifficultyLevel::Hard,
+        bf if bf <= thresholds.very_easy => DifficultyLevel::VeryEasy,
+        bf if bf <= thresholds.easy => DifficultyLevel::Easy,
+        bf if bf <= thresholds.medium => DifficultyLevel::Medium,
+        bf if bf <= thresholds.hard => DifficultyLevel::Hard,
         _ => DifficultyLevel::Expert,
     };
 
     // Primary classification by technique
     let technique_difficulty = match hardest_technique {
         SolvingTechnique::NakedSingle => {
-            if branching_factor <= 1.7 {
+            if branching_factor <= thresholds.very_easy {
                 DifficultyLevel::VeryEasy
             } else {
                 DifficultyLevel::Easy
@@ -192,7 +580,11 @@ fn classify_difficulty_level(
             }
         }
 
-        SolvingTechnique::XWing | SolvingTechnique::PointingTriples => {
+        SolvingTechnique::XWing
+        | SolvingTechnique::PointingTriples
+        | SolvingTechnique::EmptyRectangle
+        | SolvingTechnique::FinnedXWing
+        | SolvingTechnique::RemotePairs => {
             if technique_count <= 7 && branching_factor <= 5.5 {
                 DifficultyLevel::Hard
             } else {
@@ -204,6 +596,7 @@ fn classify_difficulty_level(
         | SolvingTechnique::Coloring
         | SolvingTechnique::XYWing
         | SolvingTechnique::XYChain
+        | SolvingTechnique::AlmostLockedSet
         | SolvingTechnique::ForcingChain
         | SolvingTechnique::TrialAndError => {
             if branching_factor <= 7.0 {
@@ -236,25 +629,300 @@ fn classify_difficulty_level(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_fixtures::{parse, SINGLES_PUZZLE, SOLVED_GRID, STALLED_PUZZLE};
 
     #[test]
     fn test_difficulty_classification() {
+        let defaults = DifficultyThresholds::default();
+
         // Test basic technique classification
-        let very_easy_level = classify_difficulty_level(&SolvingTechnique::NakedSingle, 1, 1.5);
+        let very_easy_level =
+            classify_difficulty_level(&SolvingTechnique::NakedSingle, 1, 1.5, &defaults);
         assert_eq!(very_easy_level, DifficultyLevel::VeryEasy);
 
-        let easy_level = classify_difficulty_level(&SolvingTechnique::NakedSingle, 1, 2.1);
+        let easy_level =
+            classify_difficulty_level(&SolvingTechnique::NakedSingle, 1, 2.1, &defaults);
         assert_eq!(easy_level, DifficultyLevel::Easy);
 
-        let hard_level = classify_difficulty_level(&SolvingTechnique::XWing, 4, 3.0);
+        let hard_level = classify_difficulty_level(&SolvingTechnique::XWing, 4, 3.0, &defaults);
         assert_eq!(hard_level, DifficultyLevel::Hard);
 
         // Expert level with higher branching factor
-        let expert_level = classify_difficulty_level(&SolvingTechnique::Swordfish, 6, 7.5);
+        let expert_level =
+            classify_difficulty_level(&SolvingTechnique::Swordfish, 6, 7.5, &defaults);
         assert_eq!(expert_level, DifficultyLevel::Expert);
     }
 
     #[test]
+    fn test_thresholds_reclassify_borderline_puzzle() {
+        // Logic stalls with a branching factor of about 4.2, inside the Hard band
+        let board = parse(STALLED_PUZZLE);
+        let defaults = DifficultyThresholds::default();
+        let default_level = analyze_difficulty_with_thresholds(&board, &defaults)
+            .unwrap()
+            .level;
+        assert_eq!(default_level, analyze_difficulty(&board).level);
+        assert_eq!(default_level, DifficultyLevel::Hard);
+
+        let stricter = DifficultyThresholds {
+            hard: 4.0,
+            ..defaults
+        };
+        let level = analyze_difficulty_with_thresholds(&board, &stricter)
+            .unwrap()
+            .level;
+        assert_eq!(level, DifficultyLevel::Expert);
+    }
+
+    #[test]
+    fn test_thresholds_must_be_finite_and_ordered() {
+        let defaults = DifficultyThresholds::default();
+        assert_eq!(defaults.validate(), Ok(()));
+
+        // Equal bands leave a level empty but still classify consistently
+        let flat = DifficultyThresholds {
+            very_easy: 2.0,
+            easy: 2.0,
+            medium: 2.0,
+            hard: 2.0,
+        };
+        assert_eq!(flat.validate(), Ok(()));
+
+        let below_very_easy = DifficultyThresholds {
+            easy: 1.0,
+            ..defaults
+        };
+        assert_eq!(
+            below_very_easy.validate(),
+            Err(ThresholdError::OutOfOrder {
+                lower: "very_easy",
+                higher: "easy"
+            })
+        );
+
+        let not_a_number = DifficultyThresholds {
+            medium: f64::NAN,
+            ..defaults
+        };
+        assert!(matches!(
+            not_a_number.validate(),
+            Err(ThresholdError::NotFinite {
+                field: "medium",
+                ..
+            })
+        ));
+
+        let infinite = DifficultyThresholds {
+            hard: f64::INFINITY,
+            ..defaults
+        };
+        assert!(infinite.validate().is_err());
+
+        let board = vec![None; GRID_SIZE];
+        assert!(analyze_difficulty_with_thresholds(&board, &not_a_number).is_err());
+    }
+
+    #[test]
+    fn test_stalled_puzzle_graded_above_singles() {
+        // 21 clues that singles can't finish
+        let board = parse(STALLED_PUZZLE);
+
+        let analysis = analyze_difficulty(&board);
+        assert!(analysis.hardest_technique >= SolvingTechnique::XYWing);
+    }
+
+    #[test]
+    fn test_stalled_denser_puzzle_graded_at_least_x_wing() {
+        // 25 clues; the solver gets as far as box/line reductions, then stalls
+        let board = parse(
+            "8....3.....36......7..9.2.3.5...78......457....71...3...1....68..85...1..9....4..",
+        );
+        let mut solver = HumanStyleSolver::new(&board);
+        assert!(!solver.solve_with_techniques());
+        assert_eq!(
+            solver.get_hardest_technique_used(),
+            SolvingTechnique::BoxLineReduction
+        );
+
+        let analysis = analyze_difficulty(&board);
+        assert_eq!(analysis.hardest_technique, SolvingTechnique::XWing);
+    }
+
+    #[test]
+    fn test_hidden_single_heavy_puzzle_at_least_medium() {
+        // 37 clues solved by singles alone, but 29 of them are hidden
+        let board = parse(SINGLES_PUZZLE);
+
+        let mut solver = HumanStyleSolver::new(&board).with_trace();
+        assert!(solver.solve_with_techniques());
+        assert_eq!(
+            solver.get_hardest_technique_used(),
+            SolvingTechnique::HiddenSingle
+        );
+        assert!(is_hidden_single_heavy(solver.get_trace()));
+
+        assert!(analyze_difficulty(&board).level >= DifficultyLevel::Medium);
+    }
+
+    #[test]
+    fn test_confidence_reflects_full_solve() {
+        let singles = parse(SINGLES_PUZZLE);
+        assert_eq!(analyze_difficulty(&singles).confidence, Confidence::Solved);
+
+        // Needs techniques the solver doesn't implement yet
+        let hard = parse(STALLED_PUZZLE);
+        assert_eq!(analyze_difficulty(&hard).confidence, Confidence::Heuristic);
+    }
+
+    #[test]
+    fn test_complete_board_is_no_puzzle() {
+        let solution = parse(SOLVED_GRID);
+        let analysis = analyze_difficulty(&solution);
+        assert_eq!(analysis.confidence, Confidence::NoPuzzle);
+        // The confidence carries the state; the level is only a placeholder
+        assert_eq!(analysis.level, DifficultyLevel::VeryEasy);
+        assert_eq!(analysis.step_count, 0);
+        assert_eq!(analysis.estimated_seconds, 0.0);
+
+        let mut puzzle = solution;
+        puzzle[0] = None;
+        assert_eq!(analyze_difficulty(&puzzle).confidence, Confidence::Solved);
+    }
+
+    #[test]
+    fn test_branching_during_solve_falls_for_easy_puzzle() {
+        let easy = parse(SINGLES_PUZZLE);
+        let start = HumanStyleSolver::new(&easy).calculate_branching_factor();
+        let analysis = analyze_difficulty(&easy);
+        assert!(start > 2.5);
+        assert!(
+            (1.0..2.0).contains(&analysis.average_branching_during_solve),
+            "{}",
+            analysis.average_branching_during_solve
+        );
+
+        // A puzzle that stalls at once never narrows from its start
+        let stalled = parse(STALLED_PUZZLE);
+        let analysis = analyze_difficulty(&stalled);
+        assert_eq!(
+            analysis.average_branching_during_solve,
+            HumanStyleSolver::new(&stalled).calculate_branching_factor()
+        );
+    }
+
+    #[test]
+    fn test_cheap_lower_bound_never_exceeds_analysis() {
+        let mut boards = vec![parse(STALLED_PUZZLE), parse(SINGLES_PUZZLE)];
+
+        // Every stage of carving a grid down, from complete to nearly empty
+        let mut board = parse(SOLVED_GRID);
+        for index in (0..81).map(|i| i * 7 % 81) {
+            boards.push(board.clone());
+            board[index] = None;
+        }
+
+        let mut raised = 0;
+        for board in &boards {
+            let bound = cheap_difficulty_lower_bound(board);
+            assert!(bound <= analyze_difficulty(board).level, "{:?}", board);
+            raised += (bound > DifficultyLevel::VeryEasy) as usize;
+        }
+        assert!(raised > 0);
+    }
+
+    #[test]
+    fn test_easiest_path_skips_clue_count_estimate() {
+        // Hidden singles solve it; the X-Wing comes from the clue count alone
+        let board = parse(
+            "9...1..........251.83.7....4..8....98..69......7.......1...2..5...5..17.3....9..8",
+        );
+        assert_eq!(
+            analyze_difficulty(&board).hardest_technique,
+            SolvingTechnique::XWing
+        );
+
+        let easiest = analyze_difficulty_easiest(&board);
+        assert_eq!(easiest.hardest_technique, SolvingTechnique::HiddenSingle);
+        assert_eq!(easiest.confidence, Confidence::Solved);
+    }
+
+    #[test]
+    fn test_estimated_seconds_scale_with_weights() {
+        let board = parse(SINGLES_PUZZLE);
+        let baseline = analyze_difficulty(&board).estimated_seconds;
+        assert!(baseline > 0.0);
+
+        let doubled: Vec<(SolvingTechnique, f64)> = SolvingTechnique::ALL
+            .into_iter()
+            .map(|technique| {
+                let seconds = technique.default_seconds() * 2.0;
+                (technique, seconds)
+            })
+            .collect();
+        let weighted = analyze_difficulty_weighted(&board, Some(&doubled)).estimated_seconds;
+        assert!((weighted - 2.0 * baseline).abs() < 1e-9);
+
+        // At one second per application the estimate is the step count
+        let flat: Vec<(SolvingTechnique, f64)> = SolvingTechnique::ALL
+            .into_iter()
+            .map(|technique| (technique, 1.0))
+            .collect();
+        let analysis = analyze_difficulty_weighted(&board, Some(&flat));
+        assert_eq!(analysis.estimated_seconds, analysis.step_count as f64);
+    }
+
+    #[test]
+    fn test_clue_removal_impact() {
+        let board = parse(SINGLES_PUZZLE);
+        let level = analyze_difficulty(&board).level;
+
+        let impacts = clue_removal_impact(&board);
+        assert_eq!(impacts.len(), 37);
+        assert!(impacts.iter().all(|impact| board[impact.index].is_some()));
+        assert!(impacts.iter().any(|impact| !impact.still_unique));
+        assert!(impacts
+            .iter()
+            .any(|impact| impact.still_unique && impact.resulting_level != level));
+    }
+
+    #[test]
+    fn test_analyze_batch() {
+        let solution = parse(SOLVED_GRID);
+        let mut puzzle = solution.clone();
+        for cell in puzzle.iter_mut().step_by(4) {
+            *cell = None;
+        }
+        let boards = vec![puzzle, vec![None; 81], vec![None; 80]];
+
+        let records = analyze_batch(&boards);
+        assert_eq!(records.len(), boards.len());
+
+        let record = records[0].as_ref().unwrap();
+        assert_eq!(record.clue_count, 60);
+        assert_eq!(record.solution_count, 1);
+        assert_eq!(record.score, record.hardest_technique.hint_cost());
+
+        assert_eq!(records[1].as_ref().unwrap().solution_count, 2);
+        assert!(records[2].is_err());
+    }
+
+    #[test]
+    fn test_contradictory_board_not_very_easy() {
+        // Row 0 holds 1-8 and column 8 already has a 9, so (0, 8) is dead
+        let mut board = vec![None; 81];
+        for (col, cell) in board.iter_mut().take(8).enumerate() {
+            *cell = Some(col as u8 + 1);
+        }
+        board[5 * 9 + 8] = Some(9);
+
+        let analysis = analyze_difficulty(&board);
+        assert!(analysis.branching_factor.is_infinite());
+        assert_ne!(analysis.level, DifficultyLevel::VeryEasy);
+        assert_eq!(analysis.confidence, Confidence::Unsolvable);
+    }
+
+    #[test]
+    #[allow(clippy::needless_range_loop)]
     fn test_heuristic_analysis() {
         // Create a test puzzle with specific clue count
         let mut board = vec![None; 81];
@@ -1,23 +1,94 @@
 //! Sudoku puzzle generator with difficulty control and branching factor tuning
 
-use crate::difficulty::analyze_difficulty;
-use crate::solver::HumanStyleSolver;
-use crate::types::{DifficultyAnalysis, DifficultyLevel, SolvingTechnique, BOARD_SIZE};
-use crate::validator::has_unique_solution;
+use crate::difficulty::{analyze_difficulty, cheap_difficulty_lower_bound};
+use crate::grid::{coords_to_index, index_to_coords, unit_indices};
+use crate::rng::make_rng;
+use crate::solver::{
+    count_required_guesses, count_single_placements, solve_path_length, HumanStyleSolver,
+    IMPLEMENTED_TECHNIQUES,
+};
+use crate::transform::{
+    apply_transform, has_nontrivial_automorphism, relabel_digits, Deduplicator, Transform,
+};
+use crate::types::{
+    DifficultyAnalysis, DifficultyLevel, SolvingTechnique, SymmetryMode, TechniqueSet, BOARD_SIZE,
+    GRID_SIZE,
+};
+use crate::validator::{has_unique_solution, is_board_complete, is_board_valid, unique_solution};
 use rand::seq::SliceRandom;
-use rand::{thread_rng, Rng};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 #[cfg(target_arch = "wasm32")]
 use web_sys;
 
 /*──────────────── CONFIG ────────────────*/
 
-#[derive(Debug, Clone)]
+/// Fewest clues a uniquely solvable Sudoku can have
+///
+/// McGuire, Tugemann and Civario proved by exhaustive search that no
+/// 16-clue puzzle has a unique solution.
+pub const MIN_POSSIBLE_CLUES: usize = 17;
+
+/// Clue count from which branching factor limits no longer apply
+///
+/// Above the 45 clues of the easiest presets nearly every empty cell has
+/// one or two candidates, so open puzzles for guided play could never meet
+/// a branching factor window tuned for real solving.
+pub const OPEN_PUZZLE_MIN_CLUES: usize = 46;
+
+/// Attempts between calls to a config's progress callback
+pub const PROGRESS_INTERVAL: u32 = 100;
+
+/// Upcoming removal groups weighed against each other under smooth progression
+const SMOOTH_PROGRESSION_WINDOW: usize = 4;
+
+/// Callback told how many generation attempts have been made so far
+///
+/// Clones share the same function, and only clones compare equal. The
+/// function must be `Send + Sync` so configs and generators can move to
+/// worker threads.
+#[derive(Clone)]
+pub struct ProgressCallback(Arc<dyn Fn(u32) + Send + Sync>);
+
+impl ProgressCallback {
+    /// Wraps a function taking the number of attempts made
+    pub fn new(callback: impl Fn(u32) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    fn call(&self, attempts: u32) {
+        (self.0)(attempts)
+    }
+}
+
+impl std::fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
+
+impl PartialEq for ProgressCallback {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Knobs for puzzle generation
+///
+/// Serializes to a flat JSON object; fields missing when deserializing take
+/// their [`Default`] values.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
 pub struct GeneratorConfig {
     pub target_difficulty: DifficultyLevel,
     pub max_attempts: u32,
     pub min_clues: usize,
     pub max_clues: usize,
+    // Symmetric removal works against minimal clue counts: a 180° pattern
+    // can only shed clues in pairs, and very low counts (17-20) rarely admit
+    // a symmetric unique puzzle at all.
     pub prefer_symmetry: bool,
 
     // Branching factor constraints for fine-tuning difficulty
@@ -27,6 +98,39 @@ pub struct GeneratorConfig {
 
     // Tolerance for branching factor matching
     pub branching_factor_tolerance: f64,
+
+    // Accept any branching factor in the min/max window, with no pull
+    // toward the target
+    pub branching_range_only: bool,
+
+    // Techniques a puzzle's solve path must never need
+    pub forbidden_techniques: TechniqueSet,
+
+    // Reject puzzles that a rotation or reflection maps onto themselves
+    pub reject_automorphic: bool,
+
+    // Reject clue layouts whose `clue_balance_score` exceeds this
+    pub max_clue_imbalance: Option<f64>,
+
+    // Reject puzzles whose starting position already offers a naked single
+    pub avoid_initial_naked_singles: bool,
+
+    // Reject puzzles whose solve needs more nested guesses than this, as
+    // counted by `count_required_guesses`; `Some(0)` means fully logical
+    pub max_guesses: Option<u32>,
+
+    // Bias removals toward clue sets that leave the most single placements,
+    // for a steadier stream of deductions
+    pub smooth_progression: bool,
+
+    // Tolerance widening used by `generate_with_relaxation`
+    pub tolerance_step: f64,
+    pub attempts_per_relaxation: u32,
+
+    // Called every `PROGRESS_INTERVAL` attempts on every target; never
+    // serialized
+    #[serde(skip)]
+    pub log_progress: Option<ProgressCallback>,
 }
 
 impl Default for GeneratorConfig {
@@ -34,7 +138,7 @@ impl Default for GeneratorConfig {
         Self {
             target_difficulty: DifficultyLevel::Medium,
             max_attempts: 3_000,
-            min_clues: 17,
+            min_clues: MIN_POSSIBLE_CLUES,
             max_clues: 35,
             prefer_symmetry: true,
 
@@ -43,6 +147,19 @@ impl Default for GeneratorConfig {
             max_branching_factor: 4.0,
             target_branching_factor: 3.0,
             branching_factor_tolerance: 0.5,
+            branching_range_only: false,
+
+            forbidden_techniques: TechniqueSet::new(),
+            reject_automorphic: false,
+            max_clue_imbalance: None,
+            avoid_initial_naked_singles: false,
+            max_guesses: None,
+            smooth_progression: false,
+
+            tolerance_step: 0.1,
+            attempts_per_relaxation: 1_000,
+
+            log_progress: None,
         }
     }
 }
@@ -50,8 +167,10 @@ impl Default for GeneratorConfig {
 impl GeneratorConfig {
     /// Creates a config optimized for the specified difficulty with branching factor control
     pub fn for_difficulty(difficulty: DifficultyLevel) -> Self {
-        let mut cfg = Self::default();
-        cfg.target_difficulty = difficulty;
+        let mut cfg = Self {
+            target_difficulty: difficulty,
+            ..Self::default()
+        };
 
         match difficulty {
             DifficultyLevel::VeryEasy => {
@@ -88,17 +207,203 @@ impl GeneratorConfig {
                 cfg.max_attempts = 5_000;
             }
             DifficultyLevel::Expert => {
-                cfg.min_clues = 17;
+                cfg.min_clues = MIN_POSSIBLE_CLUES;
                 cfg.max_clues = 24;
-                cfg.min_branching_factor = 4.5; // Updated to match actual results
-                cfg.max_branching_factor = 6.0; // Updated based on observed range
-                cfg.target_branching_factor = 5.0; // Updated to match actual average
-                cfg.branching_factor_tolerance = 0.8;
+                // Symmetry blocks the lowest clue counts
+                cfg.prefer_symmetry = false;
+                // Uniquely solvable 17-24 clue grids sit at 3.6-4.2; see
+                // test_expert_branching_window_fits_unique_grids
+                cfg.min_branching_factor = 3.8;
+                cfg.max_branching_factor = 4.6; // Upper end of the observed unique range
+                cfg.target_branching_factor = 4.0; // Matches the observed average
+                cfg.branching_factor_tolerance = 0.4;
                 cfg.max_attempts = 8_000;
             }
         }
         cfg
     }
+
+    /// Checks that the settings describe puzzles that can exist
+    ///
+    /// # Returns
+    /// `Ok(())`, or the first setting that can never be met
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.min_clues < MIN_POSSIBLE_CLUES {
+            return Err(ConfigError::TooFewClues {
+                min_clues: self.min_clues,
+            });
+        }
+        if self.max_clues >= BOARD_SIZE {
+            return Err(ConfigError::TooManyClues {
+                max_clues: self.max_clues,
+            });
+        }
+        if self.min_clues > self.max_clues {
+            return Err(ConfigError::ClueRangeInverted {
+                min_clues: self.min_clues,
+                max_clues: self.max_clues,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A generator setting that no puzzle can satisfy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigError {
+    /// `min_clues` is below [`MIN_POSSIBLE_CLUES`]
+    TooFewClues { min_clues: usize },
+    /// `max_clues` leaves no empty cell to solve
+    TooManyClues { max_clues: usize },
+    /// `min_clues` is above `max_clues`
+    ClueRangeInverted { min_clues: usize, max_clues: usize },
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::TooFewClues { min_clues } => write!(
+                f,
+                "Minimum clue count {} is too low: no uniquely solvable Sudoku has fewer than {} clues",
+                min_clues, MIN_POSSIBLE_CLUES
+            ),
+            ConfigError::TooManyClues { max_clues } => write!(
+                f,
+                "Maximum clue count {} is too high: a puzzle needs at least one empty cell out of {}",
+                max_clues, BOARD_SIZE
+            ),
+            ConfigError::ClueRangeInverted {
+                min_clues,
+                max_clues,
+            } => write!(
+                f,
+                "Minimum clue count {} is above the maximum of {}",
+                min_clues, max_clues
+            ),
+        }
+    }
+}
+
+/// Branching factor tolerance when imitating a source puzzle
+const SIMILAR_BF_TOLERANCE: f64 = 0.3;
+
+/// Generation rounds tried by `regenerate_similar` before giving up
+const SIMILAR_MAX_ROUNDS: u32 = 10;
+
+/// Rounds over every difficulty tried by `generate_for_time_band` before giving up
+const TIME_BAND_MAX_ROUNDS: u32 = 10;
+
+/// Solution grids carved by `generate_for_curriculum` before giving up
+const CURRICULUM_MAX_ROUNDS: u32 = 200;
+
+/// Solution grids carved by `generate_requiring_technique` before giving up
+const REQUIRED_TECHNIQUE_MAX_ROUNDS: u32 = 200;
+
+/// Extra puzzles `generate_on_level_with_rng` tries when the analyzed level misses the target
+const LEVEL_REGENERATIONS: u32 = 5;
+
+/// Base puzzles `generate_mirror_pair_with_seed` tries before giving up
+const MIRROR_MAX_ROUNDS: u32 = 5;
+
+/// A requirement a candidate puzzle can fail during generation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    /// The puzzle must have exactly one solution
+    Uniqueness,
+    /// The clue count must lie within `min_clues..=max_clues`
+    ClueCount,
+    /// The hardest technique must match the target difficulty
+    Difficulty,
+    /// The branching factor must lie in its window and within tolerance of the target
+    BranchingFactor,
+    /// The solve path must avoid every forbidden technique
+    ForbiddenTechnique,
+    /// The puzzle must not map onto itself under a rotation or reflection
+    Automorphism,
+    /// The clues must be spread evenly enough across the boxes
+    ClueBalance,
+    /// The starting position must not offer a naked single
+    InitialNakedSingle,
+    /// The solve must need no more guesses than allowed
+    Guesses,
+}
+
+impl Constraint {
+    /// Every constraint, in the order they are checked
+    pub const ALL: [Constraint; 9] = [
+        Constraint::Uniqueness,
+        Constraint::ForbiddenTechnique,
+        Constraint::Automorphism,
+        Constraint::ClueBalance,
+        Constraint::InitialNakedSingle,
+        Constraint::Guesses,
+        Constraint::ClueCount,
+        Constraint::Difficulty,
+        Constraint::BranchingFactor,
+    ];
+
+    /// Name used in diagnostics output
+    pub fn name(&self) -> &'static str {
+        match self {
+            Constraint::Uniqueness => "uniqueness",
+            Constraint::ClueCount => "clue_count",
+            Constraint::Difficulty => "difficulty",
+            Constraint::BranchingFactor => "branching_factor",
+            Constraint::ForbiddenTechnique => "forbidden_technique",
+            Constraint::Automorphism => "automorphism",
+            Constraint::ClueBalance => "clue_balance",
+            Constraint::InitialNakedSingle => "initial_naked_single",
+            Constraint::Guesses => "guesses",
+        }
+    }
+}
+
+/// Two puzzles that solve identically, from [`PuzzleGenerator::generate_mirror_pair_with_seed`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MirrorPair {
+    /// The generated puzzle
+    pub first: Vec<Option<u8>>,
+    /// `first` rotated or reflected, with its digits relabeled
+    pub second: Vec<Option<u8>>,
+}
+
+/// Why a diagnosed generation run found no puzzle
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenerationFailure {
+    /// Number of attempts made
+    pub attempts: u32,
+    /// Failed attempts per constraint, indexed like [`Constraint::ALL`]
+    failures: [u32; Constraint::ALL.len()],
+}
+
+impl GenerationFailure {
+    /// Gets how many attempts failed a constraint
+    pub fn failures(&self, constraint: Constraint) -> u32 {
+        self.failures[Self::slot(constraint)]
+    }
+
+    /// Gets the constraint that failed the most attempts
+    ///
+    /// # Returns
+    /// The bottleneck, or `None` if no attempt was made
+    pub fn bottleneck(&self) -> Option<Constraint> {
+        Constraint::ALL
+            .into_iter()
+            .filter(|&constraint| self.failures(constraint) > 0)
+            .max_by_key(|&constraint| self.failures(constraint))
+    }
+
+    fn record(&mut self, constraint: Constraint) {
+        self.attempts += 1;
+        self.failures[Self::slot(constraint)] += 1;
+    }
+
+    fn slot(constraint: Constraint) -> usize {
+        Constraint::ALL
+            .iter()
+            .position(|&c| c == constraint)
+            .unwrap_or_default()
+    }
 }
 
 /*──────────────── GENERATOR ────────────────*/
@@ -108,8 +413,22 @@ pub struct PuzzleGenerator {
 }
 
 impl PuzzleGenerator {
+    /// Creates a generator for a config known to be valid
+    ///
+    /// # Panics
+    /// If [`GeneratorConfig::validate`] rejects the config; use
+    /// [`PuzzleGenerator::try_new`] for settings that come from users
     pub fn new(config: GeneratorConfig) -> Self {
-        Self { config }
+        match Self::try_new(config) {
+            Ok(generator) => generator,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Creates a generator after checking the config with [`GeneratorConfig::validate`]
+    pub fn try_new(config: GeneratorConfig) -> Result<Self, ConfigError> {
+        config.validate()?;
+        Ok(Self { config })
     }
 
     pub fn with_difficulty(difficulty: DifficultyLevel) -> Self {
@@ -118,13 +437,42 @@ impl PuzzleGenerator {
 
     /// Generate a puzzle with fine-tuned branching factor control
     pub fn generate(&self) -> Option<Vec<Option<u8>>> {
+        self.generate_with_rng(&mut make_rng(None))
+    }
+
+    /// Generate a puzzle deterministically from a seed
+    ///
+    /// The same seed and configuration always produce the same puzzle.
+    pub fn generate_with_seed(&self, seed: u64) -> Option<Vec<Option<u8>>> {
+        self.generate_with_rng(&mut make_rng(Some(seed)))
+    }
+
+    /// Lazily generate a stream of puzzles from one seed
+    ///
+    /// Callers can take as many puzzles as they need and stop early. The
+    /// stream ends if a generation fails.
+    pub fn generate_iter(&self, seed: u64) -> impl Iterator<Item = Vec<Option<u8>>> + '_ {
+        let mut rng = make_rng(Some(seed));
+        std::iter::from_fn(move || self.generate_with_rng(&mut rng))
+    }
+
+    /// Generate a puzzle drawing all randomness from the given RNG
+    pub fn generate_with_rng(&self, rng: &mut impl Rng) -> Option<Vec<Option<u8>>> {
+        self.generate_attempts(rng, 0)
+    }
+
+    /// Runs up to `max_attempts` attempts, numbering them after `done`
+    /// earlier ones so progress reports count the running total
+    fn generate_attempts(&self, rng: &mut impl Rng, done: u32) -> Option<Vec<Option<u8>>> {
         for attempt in 0..self.config.max_attempts {
-            if let Some(puzzle) = self.generate_attempt() {
+            if let Some(puzzle) = self.generate_attempt(rng, &mut None) {
                 if self.validate_puzzle_enhanced(&puzzle) {
                     return Some(puzzle);
                 }
             }
 
+            self.report_progress(done + attempt + 1);
+
             #[cfg(target_arch = "wasm32")]
             if attempt % 100 == 0 && attempt > 0 {
                 web_sys::console::log_1(
@@ -135,83 +483,359 @@ impl PuzzleGenerator {
                     .into(),
                 );
             }
-            #[cfg(not(target_arch = "wasm32"))]
-            let _ = attempt; // Use the variable to avoid warnings
         }
         None
     }
 
-    fn generate_attempt(&self) -> Option<Vec<Option<u8>>> {
-        let solution = self.generate_complete_solution()?;
-        self.create_puzzle_with_branching_factor_control(&solution)
+    /// Calls the progress callback if `attempts` completes an interval
+    fn report_progress(&self, attempts: u32) {
+        if let Some(callback) = &self.config.log_progress {
+            if attempts.is_multiple_of(PROGRESS_INTERVAL) {
+                callback.call(attempts);
+            }
+        }
+    }
+
+    /// Generate a puzzle, widening the branching factor tolerance as attempts fail
+    ///
+    /// Every `attempts_per_relaxation` failed attempts the tolerance grows by
+    /// `tolerance_step`. Clue counts, the branching factor window and the
+    /// technique tier are never relaxed, so puzzles stay on target.
+    pub fn generate_with_relaxation(&self) -> Option<Vec<Option<u8>>> {
+        self.generate_with_relaxation_rng(&mut make_rng(None))
+    }
+
+    /// Relaxing generation drawing all randomness from the given RNG
+    pub fn generate_with_relaxation_rng(&self, rng: &mut impl Rng) -> Option<Vec<Option<u8>>> {
+        // Beyond this the min/max window is the only effective limit
+        let ceiling = (self.config.target_branching_factor - self.config.min_branching_factor)
+            .max(self.config.max_branching_factor - self.config.target_branching_factor)
+            .max(self.config.branching_factor_tolerance);
+
+        let mut batch = PuzzleGenerator::new(self.config.clone());
+        let mut remaining = self.config.max_attempts;
+        let mut result = None;
+
+        while remaining > 0 && result.is_none() {
+            let attempts = remaining.min(self.config.attempts_per_relaxation.max(1));
+            batch.config.max_attempts = attempts;
+            let done = self.config.max_attempts - remaining;
+            result = batch.generate_attempts(rng, done);
+            remaining -= attempts;
+
+            if result.is_none() {
+                batch.config.branching_factor_tolerance = (batch.config.branching_factor_tolerance
+                    + self.config.tolerance_step)
+                    .min(ceiling);
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        web_sys::console::log_1(
+            &format!(
+                "Relaxed generation {} with BF tolerance {:.2}",
+                if result.is_some() {
+                    "succeeded"
+                } else {
+                    "failed"
+                },
+                batch.config.branching_factor_tolerance
+            )
+            .into(),
+        );
+
+        result
+    }
+
+    /// Generate a puzzle whose analyzed level matches the target if possible
+    ///
+    /// Generation checks the techniques a puzzle needs, but
+    /// [`analyze_difficulty`] also weighs technique variety and branching,
+    /// so a Hard puzzle can still analyze as Medium. Such puzzles are
+    /// regenerated a bounded number of times; if none lands on target, the
+    /// one closest to it is returned.
+    ///
+    /// # Returns
+    /// The puzzle with the level it actually analyzes as, or `None` if
+    /// generation failed
+    pub fn generate_on_level_with_seed(
+        &self,
+        seed: u64,
+    ) -> Option<(Vec<Option<u8>>, DifficultyLevel)> {
+        self.generate_on_level_with_rng(&mut make_rng(Some(seed)))
+    }
+
+    /// Level-checked generation drawing all randomness from the given RNG
+    pub fn generate_on_level_with_rng(
+        &self,
+        rng: &mut impl Rng,
+    ) -> Option<(Vec<Option<u8>>, DifficultyLevel)> {
+        let target = self.config.target_difficulty;
+        let distance =
+            |level: DifficultyLevel| level.legacy_number().abs_diff(target.legacy_number());
+        let mut best: Option<(Vec<Option<u8>>, DifficultyLevel)> = None;
+
+        for _ in 0..=LEVEL_REGENERATIONS {
+            // A failed generation already spent every attempt; retrying won't help
+            let Some(puzzle) = self.generate_with_relaxation_rng(rng) else {
+                break;
+            };
+            let level = analyze_difficulty(&puzzle).level;
+            if level == target {
+                return Some((puzzle, level));
+            }
+            if best
+                .as_ref()
+                .is_none_or(|(_, closest)| distance(level) < distance(*closest))
+            {
+                best = Some((puzzle, level));
+            }
+        }
+        best
+    }
+
+    /// Generate two puzzles that look different but solve identically
+    ///
+    /// The second puzzle is the first rotated or reflected and with its
+    /// digits relabeled, for head-to-head play where neither player should
+    /// get an easier grid. The solver scans cells in index order, so a
+    /// transform can occasionally change the path it takes; each mirror is
+    /// checked and only one with the same analyzed level, hardest technique
+    /// and solve path length is returned.
+    ///
+    /// # Returns
+    /// The base puzzle and its mirror, or `None` if generation failed
+    pub fn generate_mirror_pair_with_seed(&self, seed: u64) -> Option<MirrorPair> {
+        let mut rng = make_rng(Some(seed));
+        let signature = |puzzle: &[Option<u8>]| {
+            let analysis = analyze_difficulty(puzzle);
+            (
+                analysis.level,
+                analysis.hardest_technique,
+                solve_path_length(puzzle),
+            )
+        };
+
+        for _ in 0..MIRROR_MAX_ROUNDS {
+            let base = self.generate_with_relaxation_rng(&mut rng)?;
+            let target = signature(&base);
+
+            let mut permutation: Vec<u8> = (1..=9).collect();
+            permutation.shuffle(&mut rng);
+            let mut transforms = Transform::ALL[1..].to_vec();
+            transforms.shuffle(&mut rng);
+
+            for transform in transforms {
+                let mirror = relabel_digits(&apply_transform(&base, transform), &permutation)?;
+                if signature(&mirror) == target {
+                    return Some(MirrorPair {
+                        first: base,
+                        second: mirror,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Generate a puzzle from a seed, reporting why generation failed
+    ///
+    /// Follows the same path as [`PuzzleGenerator::generate_with_seed`], but
+    /// tallies the constraint each failed attempt ran into. An attempt whose
+    /// carving found no acceptable puzzle is charged to the constraint its
+    /// most carved unique state failed.
+    ///
+    /// # Returns
+    /// The puzzle, or per-constraint failure counts
+    pub fn generate_with_diagnostics(
+        &self,
+        seed: u64,
+    ) -> Result<Vec<Option<u8>>, GenerationFailure> {
+        let mut rng = make_rng(Some(seed));
+        let mut failure = GenerationFailure::default();
+
+        for attempt in 0..self.config.max_attempts {
+            let mut last_failure = None;
+            let failed = match self.generate_attempt(&mut rng, &mut last_failure) {
+                Some(puzzle) => match self.check_puzzle(&puzzle) {
+                    Ok(()) => return Ok(puzzle),
+                    Err(constraint) => constraint,
+                },
+                None => last_failure.unwrap_or(Constraint::Uniqueness),
+            };
+            failure.record(failed);
+            self.report_progress(attempt + 1);
+        }
+        Err(failure)
+    }
+
+    fn generate_attempt(
+        &self,
+        rng: &mut impl Rng,
+        last_failure: &mut Option<Constraint>,
+    ) -> Option<Vec<Option<u8>>> {
+        let solution = self.generate_complete_solution(rng)?;
+        self.create_puzzle_with_branching_factor_control(&solution, &[], rng, last_failure)
+    }
+
+    /// Generate a puzzle around an author's sketched clues
+    ///
+    /// The sketch's solution is carved like a fresh grid, except the
+    /// sketched clues are never removed.
+    ///
+    /// # Arguments
+    /// * `partial` - The author's clues; must have exactly one solution
+    /// * `seed` - Seed for reproducible generation
+    ///
+    /// # Returns
+    /// A puzzle containing every sketched clue, or `None` if the sketch
+    /// isn't uniquely solvable or no puzzle on target was found
+    pub fn complete_with_seed(&self, partial: &[Option<u8>], seed: u64) -> Option<Vec<Option<u8>>> {
+        let solution = unique_solution(partial)?;
+        let protected: Vec<usize> = (0..BOARD_SIZE).filter(|&i| partial[i].is_some()).collect();
+        let mut rng = make_rng(Some(seed));
+
+        (0..self.config.max_attempts)
+            .filter_map(|_| {
+                self.create_puzzle_with_branching_factor_control(
+                    &solution, &protected, &mut rng, &mut None,
+                )
+            })
+            .find(|puzzle| self.validate_puzzle_enhanced(puzzle))
     }
 
     /// Enhanced puzzle creation with branching factor monitoring
+    ///
+    /// Cells listed in `protected` are never removed. `last_failure` receives
+    /// the constraint the most recently analyzed unique state failed.
     fn create_puzzle_with_branching_factor_control(
         &self,
         solution: &[Option<u8>],
+        protected: &[usize],
+        rng: &mut impl Rng,
+        last_failure: &mut Option<Constraint>,
     ) -> Option<Vec<Option<u8>>> {
         let mut puzzle = solution.to_vec();
         let mut best_puzzle: Option<Vec<Option<u8>>> = None;
         let mut best_score = f64::INFINITY;
-        let order = self.get_removal_order();
-        let mut since_unique_check = 0;
+        let mut clue_count = BOARD_SIZE;
+
+        let mut groups = self.get_removal_groups(rng);
+        for group in &mut groups {
+            group.retain(|idx| !protected.contains(idx));
+        }
+        groups.retain(|group| !group.is_empty());
 
-        for &idx in &order {
-            let saved = puzzle[idx];
-            puzzle[idx] = None;
+        while !groups.is_empty() {
+            let group = groups.remove(self.next_removal_group(&groups, &mut puzzle));
 
-            // Basic constraints
-            let clue_count = puzzle.iter().filter(|c| c.is_some()).count();
-            if clue_count < self.config.min_clues {
+            // A symmetric pair that would dip below the minimum falls back
+            // to a single cell, so the count can still land exactly on it
+            let group = if clue_count - group.len() >= self.config.min_clues {
+                &group[..]
+            } else if clue_count > self.config.min_clues {
+                &group[..1]
+            } else {
                 break;
+            };
+
+            let saved: Vec<Option<u8>> = group.iter().map(|&idx| puzzle[idx]).collect();
+            for &idx in group {
+                puzzle[idx] = None;
             }
+            let restore = |puzzle: &mut Vec<Option<u8>>| {
+                for (&idx, &value) in group.iter().zip(&saved) {
+                    puzzle[idx] = value;
+                }
+            };
 
-            // Periodic uniqueness check to avoid expensive operations
-            let needs_unique_check =
-                since_unique_check >= 3 || clue_count <= self.config.min_clues + 2;
-            if needs_unique_check && !has_unique_solution(&puzzle) {
-                puzzle[idx] = saved;
-                since_unique_check = 0;
+            // Every removal is checked: a skipped check can let uniqueness break
+            // unnoticed, after which no later removal would ever pass
+            let Some(found) = unique_solution(&puzzle) else {
+                restore(&mut puzzle);
                 continue;
-            }
-            since_unique_check = if needs_unique_check {
-                0
-            } else {
-                since_unique_check + 1
             };
+            // Only clues were removed, so the one solution must be the source
+            debug_assert_eq!(
+                divergence_from_source(&found, solution),
+                None,
+                "carved puzzle solves to a different grid than its source"
+            );
+
+            // The full analysis dominates carving time; skip it when the
+            // puzzle is already known to be too hard
+            if cheap_difficulty_lower_bound(&puzzle) > self.config.target_difficulty {
+                *last_failure = Some(Constraint::Difficulty);
+                restore(&mut puzzle);
+                continue;
+            }
 
             // Calculate branching factor and difficulty
             let branching_factor = self.calculate_branching_factor(&puzzle);
             let analysis = analyze_difficulty(&puzzle);
 
             // Check if this meets our constraints
-            if self.meets_all_constraints(&puzzle, &analysis, branching_factor) {
+            let constraints = self.check_constraints(&puzzle, &analysis, branching_factor);
+            *last_failure = constraints.err();
+            if constraints.is_ok() {
                 // Calculate score based on how close to target branching factor
                 let bf_diff = (branching_factor - self.config.target_branching_factor).abs();
-                let score = bf_diff + (clue_count as f64 - self.config.min_clues as f64) * 0.1;
+                let clues_left = clue_count - group.len();
+                let score = bf_diff + (clues_left as f64 - self.config.min_clues as f64) * 0.1;
 
                 if score < best_score {
                     best_score = score;
                     best_puzzle = Some(puzzle.clone());
                 }
 
-                // If we're very close to target, return immediately
-                if bf_diff <= self.config.branching_factor_tolerance * 0.5 {
+                // If we're very close to target, or any puzzle in range will do,
+                // return immediately
+                if self.config.branching_range_only
+                    || bf_diff <= self.config.branching_factor_tolerance * 0.5
+                {
                     return Some(puzzle);
                 }
             }
 
             // Don't continue if difficulty is too high
             if self.difficulty_overshoot(&analysis) {
-                puzzle[idx] = saved;
+                restore(&mut puzzle);
                 continue;
             }
+            clue_count -= group.len();
         }
 
         best_puzzle
     }
 
+    /// Picks which pending removal group to try next
+    ///
+    /// Normally groups are tried in their shuffled order. With
+    /// `smooth_progression`, the first few are compared and the one whose
+    /// removal leaves the most cells open to a single wins, so the puzzle
+    /// keeps offering easy placements instead of stalling early.
+    ///
+    /// # Returns
+    /// An index into `groups`; `puzzle` is left as it was
+    fn next_removal_group(&self, groups: &[Vec<usize>], puzzle: &mut [Option<u8>]) -> usize {
+        if !self.config.smooth_progression {
+            return 0;
+        }
+
+        let mut best = (0, 0);
+        for (position, group) in groups.iter().take(SMOOTH_PROGRESSION_WINDOW).enumerate() {
+            let saved: Vec<Option<u8>> = group.iter().map(|&idx| puzzle[idx].take()).collect();
+            let singles = count_single_placements(puzzle);
+            for (&idx, value) in group.iter().zip(saved) {
+                puzzle[idx] = value;
+            }
+            if position == 0 || singles > best.1 {
+                best = (position, singles);
+            }
+        }
+        best.0
+    }
+
     /// Calculate branching factor for a puzzle state
     pub fn calculate_branching_factor(&self, puzzle: &[Option<u8>]) -> f64 {
         let solver = HumanStyleSolver::new(puzzle);
@@ -219,55 +843,128 @@ impl PuzzleGenerator {
     }
 
     /// Check if puzzle meets all enhanced constraints
-    fn meets_all_constraints(
+    ///
+    /// # Returns
+    /// `Ok(())`, or the first constraint the puzzle fails
+    fn check_constraints(
         &self,
         puzzle: &[Option<u8>],
         analysis: &DifficultyAnalysis,
         branching_factor: f64,
-    ) -> bool {
+    ) -> Result<(), Constraint> {
         let clue_count = puzzle.iter().filter(|c| c.is_some()).count();
 
         // Basic constraints
         if clue_count < self.config.min_clues || clue_count > self.config.max_clues {
-            return false;
+            return Err(Constraint::ClueCount);
         }
 
         // Difficulty constraint
         if !self.difficulty_matches_target(analysis) {
-            return false;
+            return Err(Constraint::Difficulty);
+        }
+
+        if clue_count >= OPEN_PUZZLE_MIN_CLUES {
+            return Ok(());
         }
 
         // Branching factor constraint
         if branching_factor < self.config.min_branching_factor
             || branching_factor > self.config.max_branching_factor
         {
-            return false;
+            return Err(Constraint::BranchingFactor);
         }
 
         // Target branching factor tolerance
         let bf_diff = (branching_factor - self.config.target_branching_factor).abs();
-        bf_diff <= self.config.branching_factor_tolerance
+        if !self.config.branching_range_only && bf_diff > self.config.branching_factor_tolerance {
+            return Err(Constraint::BranchingFactor);
+        }
+        Ok(())
     }
 
     /// Enhanced puzzle validation including branching factor
     fn validate_puzzle_enhanced(&self, puzzle: &[Option<u8>]) -> bool {
+        self.check_puzzle(puzzle).is_ok()
+    }
+
+    /// Check a finished candidate against every constraint
+    ///
+    /// # Returns
+    /// `Ok(())`, or the first constraint the puzzle fails
+    fn check_puzzle(&self, puzzle: &[Option<u8>]) -> Result<(), Constraint> {
         if !has_unique_solution(puzzle) {
-            return false;
+            return Err(Constraint::Uniqueness);
+        }
+
+        if self.uses_forbidden_technique(puzzle) {
+            return Err(Constraint::ForbiddenTechnique);
+        }
+
+        if self.config.reject_automorphic && has_nontrivial_automorphism(puzzle) {
+            return Err(Constraint::Automorphism);
+        }
+
+        if self
+            .config
+            .max_clue_imbalance
+            .is_some_and(|max| clue_balance_score(puzzle) > max)
+        {
+            return Err(Constraint::ClueBalance);
+        }
+
+        if self.config.avoid_initial_naked_singles
+            && HumanStyleSolver::new(puzzle).peek_naked_single().is_some()
+        {
+            return Err(Constraint::InitialNakedSingle);
+        }
+
+        // A solve the guess search gives up on needs too many guesses
+        if self
+            .config
+            .max_guesses
+            .is_some_and(|max| count_required_guesses(puzzle).is_none_or(|guesses| guesses > max))
+        {
+            return Err(Constraint::Guesses);
         }
 
         let analysis = analyze_difficulty(puzzle);
         let branching_factor = self.calculate_branching_factor(puzzle);
 
-        self.meets_all_constraints(puzzle, &analysis, branching_factor)
+        self.check_constraints(puzzle, &analysis, branching_factor)
+    }
+
+    /// Check if solving the puzzle needs any forbidden technique
+    ///
+    /// A puzzle the implemented techniques can't finish counts as needing
+    /// trial and error.
+    fn uses_forbidden_technique(&self, puzzle: &[Option<u8>]) -> bool {
+        let forbidden = &self.config.forbidden_techniques;
+        if forbidden.is_empty() {
+            return false;
+        }
+
+        let mut solver = HumanStyleSolver::new(puzzle);
+        let solved = solver.solve_with_techniques();
+        solver
+            .get_techniques_used()
+            .iter()
+            .any(|technique| forbidden.contains(technique))
+            || (!solved && forbidden.contains(&SolvingTechnique::TrialAndError))
     }
 
     /// Check if difficulty analysis matches target (same as original)
     fn difficulty_matches_target(&self, analysis: &DifficultyAnalysis) -> bool {
         use SolvingTechnique::*;
         match self.config.target_difficulty {
-            DifficultyLevel::VeryEasy => analysis.hardest_technique <= NakedSingle,
+            // High clue counts can hide a path full of hard-to-spot hidden
+            // singles behind a naked-single rating, so check the level too
+            DifficultyLevel::VeryEasy => {
+                analysis.hardest_technique <= NakedSingle && analysis.level <= DifficultyLevel::Easy
+            }
             DifficultyLevel::Easy => analysis.hardest_technique <= HiddenSingle,
-            DifficultyLevel::Medium => analysis.hardest_technique <= BoxLineReduction,
+            // Pointing pairs grade Medium just like box/line reductions do
+            DifficultyLevel::Medium => analysis.hardest_technique <= PointingPairs,
             DifficultyLevel::Hard => {
                 analysis.hardest_technique >= XWing && analysis.hardest_technique <= Swordfish
             }
@@ -279,21 +976,22 @@ impl PuzzleGenerator {
     fn difficulty_overshoot(&self, analysis: &DifficultyAnalysis) -> bool {
         use SolvingTechnique::*;
         match self.config.target_difficulty {
-            DifficultyLevel::VeryEasy => analysis.hardest_technique > NakedSingle,
+            DifficultyLevel::VeryEasy => {
+                analysis.hardest_technique > NakedSingle || analysis.level > DifficultyLevel::Easy
+            }
             DifficultyLevel::Easy => analysis.hardest_technique > HiddenSingle,
-            DifficultyLevel::Medium => analysis.hardest_technique > BoxLineReduction,
+            DifficultyLevel::Medium => analysis.hardest_technique > PointingPairs,
             DifficultyLevel::Hard => analysis.hardest_technique > Swordfish,
             DifficultyLevel::Expert => false, // Expert has no upper bound
         }
     }
 
     // Reuse methods from original generator
-    fn generate_complete_solution(&self) -> Option<Vec<Option<u8>>> {
+    fn generate_complete_solution(&self, rng: &mut impl Rng) -> Option<Vec<Option<u8>>> {
         let mut board = [0u8; BOARD_SIZE];
         let (mut row_m, mut col_m, mut box_m) = ([0u16; 9], [0u16; 9], [0u16; 9]);
-        let mut rng = thread_rng();
 
-        if self.fill_board_fast(&mut board, &mut row_m, &mut col_m, &mut box_m, 0, &mut rng) {
+        if self.fill_board_fast(&mut board, &mut row_m, &mut col_m, &mut box_m, 0, rng) {
             Some(
                 board
                     .iter()
@@ -317,8 +1015,7 @@ impl PuzzleGenerator {
         if idx == BOARD_SIZE {
             return true;
         }
-        let (r, c) = (idx / 9, idx % 9);
-        let bx = (r / 3) * 3 + (c / 3);
+        let (r, c, bx) = unit_indices(idx);
 
         if b[idx] != 0 {
             return self.fill_board_fast(b, row_m, col_m, box_m, idx + 1, rng);
@@ -348,43 +1045,137 @@ impl PuzzleGenerator {
         false
     }
 
-    fn get_removal_order(&self) -> Vec<usize> {
-        let mut rng = thread_rng();
-        let mut indices: Vec<usize> = (0..BOARD_SIZE).collect();
+    /// Cells to remove together: symmetric pairs when symmetry is preferred
+    /// (the center cell alone), otherwise single cells
+    fn get_removal_groups(&self, rng: &mut impl Rng) -> Vec<Vec<usize>> {
+        let mode = if self.config.prefer_symmetry {
+            SymmetryMode::Rotational
+        } else {
+            SymmetryMode::None
+        };
+        let mut groups = removal_groups(mode);
+        groups.shuffle(rng);
+        groups
+    }
+}
 
-        if self.config.prefer_symmetry {
-            let mut pairs = Vec::<(usize, usize)>::new();
-            let mut seen = vec![false; BOARD_SIZE];
-            for i in 0..BOARD_SIZE {
-                if seen[i] {
-                    continue;
-                }
-                let s = self.get_symmetric_index(i);
-                if s != i && !seen[s] {
-                    pairs.push((i, s));
-                    seen[i] = true;
-                    seen[s] = true;
-                } else {
-                    pairs.push((i, i));
-                    seen[i] = true;
-                }
+/// Gets the partner of a cell under a symmetry
+///
+/// # Arguments
+/// * `index` - The cell index (0-80)
+/// * `mode` - The symmetry to apply
+///
+/// # Returns
+/// The partner cell's index, which may be `index` itself
+pub fn symmetric_index(index: usize, mode: SymmetryMode) -> usize {
+    let (row, col) = index_to_coords(index);
+    let last = GRID_SIZE - 1;
+    match mode {
+        SymmetryMode::None => index,
+        SymmetryMode::Rotational => coords_to_index(last - row, last - col),
+        SymmetryMode::Horizontal => coords_to_index(last - row, col),
+        SymmetryMode::Vertical => coords_to_index(row, last - col),
+        SymmetryMode::Diagonal => coords_to_index(col, row),
+    }
+}
+
+/// Splits the grid into the cell groups that symmetric carving removes together
+///
+/// Each cell is paired with its partner under the symmetry. Cells the
+/// symmetry maps onto themselves, such as the center under a half turn or
+/// the main diagonal under a diagonal flip, form groups of one so they are
+/// removed singly.
+///
+/// # Arguments
+/// * `mode` - The symmetry to keep; [`SymmetryMode::None`] leaves every
+///   cell on its own
+///
+/// # Returns
+/// Groups of one or two cells covering every cell once, in index order of
+/// their first cell
+pub fn removal_groups(mode: SymmetryMode) -> Vec<Vec<usize>> {
+    (0..BOARD_SIZE)
+        .filter_map(|index| {
+            let partner = symmetric_index(index, mode);
+            match partner.cmp(&index) {
+                std::cmp::Ordering::Equal => Some(vec![index]),
+                std::cmp::Ordering::Greater => Some(vec![index, partner]),
+                // Already grouped with its partner
+                std::cmp::Ordering::Less => None,
             }
-            pairs.shuffle(&mut rng);
-            indices = pairs
-                .into_iter()
-                .flat_map(|(a, b)| if a == b { vec![a] } else { vec![a, b] })
-                .collect();
-        } else {
-            indices.shuffle(&mut rng);
-        }
-        indices
+        })
+        .collect()
+}
+
+/// Finds the symmetries a puzzle's clue pattern satisfies
+///
+/// Only the positions of the clues matter, not their digits.
+///
+/// # Arguments
+/// * `board` - The puzzle to classify
+///
+/// # Returns
+/// Every satisfied symmetry, or just [`SymmetryMode::None`] if there are none
+pub fn detect_symmetry(board: &[Option<u8>]) -> Vec<SymmetryMode> {
+    let symmetries: Vec<SymmetryMode> = [
+        SymmetryMode::Rotational,
+        SymmetryMode::Horizontal,
+        SymmetryMode::Vertical,
+        SymmetryMode::Diagonal,
+    ]
+    .into_iter()
+    .filter(|&mode| {
+        (0..BOARD_SIZE)
+            .all(|index| board[index].is_some() == board[symmetric_index(index, mode)].is_some())
+    })
+    .collect();
+
+    if symmetries.is_empty() {
+        vec![SymmetryMode::None]
+    } else {
+        symmetries
     }
+}
 
-    fn get_symmetric_index(&self, index: usize) -> usize {
-        let row = index / 9;
-        let col = index % 9;
-        (8 - row) * 9 + (8 - col)
+/// Measures how unevenly a puzzle's clues are spread across the boxes
+///
+/// The score is the variance of the per-box clue counts divided by the
+/// squared mean, so it doesn't grow with the clue count. Clues bunched into
+/// a few boxes look lopsided even when the puzzle itself is fine.
+///
+/// # Arguments
+/// * `board` - The puzzle to measure
+///
+/// # Returns
+/// 0 when every box holds the same number of clues (or there are none);
+/// higher means less balanced, up to 8 with every clue in one box
+pub fn clue_balance_score(board: &[Option<u8>]) -> f64 {
+    let mut box_counts = [0usize; GRID_SIZE];
+    for index in (0..BOARD_SIZE).filter(|&index| board[index].is_some()) {
+        box_counts[unit_indices(index).2] += 1;
     }
+
+    let mean = box_counts.iter().sum::<usize>() as f64 / GRID_SIZE as f64;
+    if mean == 0.0 {
+        return 0.0;
+    }
+    let variance = box_counts
+        .iter()
+        .map(|&count| (count as f64 - mean).powi(2))
+        .sum::<f64>()
+        / GRID_SIZE as f64;
+    variance / (mean * mean)
+}
+
+/// Finds the first cell where a solved grid differs from the carving source
+///
+/// # Returns
+/// The index of the first differing cell, or `None` if the grids agree
+fn divergence_from_source(found: &[Option<u8>], source: &[Option<u8>]) -> Option<usize> {
+    found
+        .iter()
+        .zip(source)
+        .position(|(found_cell, source_cell)| found_cell != source_cell)
 }
 
 /*──────── PUBLIC API ────────*/
@@ -408,26 +1199,491 @@ pub fn generate_puzzle_with_branching_factor(
     config.min_branching_factor = (target_branching_factor - tolerance * 2.0).max(1.0);
     config.max_branching_factor = target_branching_factor + tolerance * 2.0;
 
-    PuzzleGenerator::new(config).generate()
+    PuzzleGenerator::try_new(config).ok()?.generate()
+}
+
+/// Generate a puzzle whose branching factor lies anywhere in a range
+///
+/// Unlike [`generate_puzzle_with_branching_factor`], there is no point
+/// target: the first puzzle inside the window is accepted, so samples
+/// aren't pulled toward the middle of the range.
+///
+/// # Arguments
+/// * `difficulty` - Target difficulty level
+/// * `min_branching_factor` - Lowest acceptable branching factor
+/// * `max_branching_factor` - Highest acceptable branching factor
+/// * `seed` - Seed for reproducible generation
+///
+/// # Returns
+/// The puzzle, or `None` if no puzzle in range was found
+pub fn generate_in_branching_range(
+    difficulty: DifficultyLevel,
+    min_branching_factor: f64,
+    max_branching_factor: f64,
+    seed: u64,
+) -> Option<Vec<Option<u8>>> {
+    let mut config = GeneratorConfig::for_difficulty(difficulty);
+    config.min_branching_factor = min_branching_factor;
+    config.max_branching_factor = max_branching_factor;
+    config.target_branching_factor = (min_branching_factor + max_branching_factor) / 2.0;
+    config.branching_range_only = true;
+
+    PuzzleGenerator::try_new(config)
+        .ok()?
+        .generate_with_seed(seed)
 }
 
-/// Generate puzzle using the standard interface (now with branching factor control)
+/// Generate puzzle using the standard interface, relaxing tolerance if attempts keep failing
 pub fn generate_puzzle(difficulty: DifficultyLevel) -> Option<Vec<Option<u8>>> {
-    PuzzleGenerator::with_difficulty(difficulty).generate()
+    PuzzleGenerator::with_difficulty(difficulty).generate_with_relaxation()
+}
+
+/// Generate the clue layout of a typical puzzle for the given difficulty
+///
+/// Runs a full seeded generation and keeps only which positions are givens,
+/// discarding the digits. Useful for previewing layouts before filling them.
+///
+/// # Returns
+/// An 81-element mask where `true` marks a given, or `None` if generation failed
+pub fn generate_clue_pattern(difficulty: DifficultyLevel, seed: u64) -> Option<Vec<bool>> {
+    let puzzle = PuzzleGenerator::with_difficulty(difficulty).generate_with_seed(seed)?;
+    Some(puzzle.iter().map(|cell| cell.is_some()).collect())
+}
+
+/// Generate a new puzzle with the same feel as an existing one
+///
+/// The source's difficulty level and starting branching factor become the
+/// generation targets, and candidates are kept only if they analyze to the
+/// same level as the source.
+///
+/// # Arguments
+/// * `board` - The puzzle to imitate
+/// * `seed` - Seed for reproducible generation
+///
+/// # Returns
+/// A similar puzzle, or `None` if no match was found within a few rounds
+pub fn regenerate_similar(board: &[Option<u8>], seed: u64) -> Option<Vec<Option<u8>>> {
+    let level = analyze_difficulty(board).level;
+    let branching_factor = HumanStyleSolver::new(board).calculate_branching_factor();
+
+    let mut config = GeneratorConfig::for_difficulty(level);
+    config.target_branching_factor = branching_factor;
+    config.branching_factor_tolerance = SIMILAR_BF_TOLERANCE;
+    config.min_branching_factor = config
+        .min_branching_factor
+        .min(branching_factor - SIMILAR_BF_TOLERANCE);
+    config.max_branching_factor = config
+        .max_branching_factor
+        .max(branching_factor + SIMILAR_BF_TOLERANCE);
+
+    let generator = PuzzleGenerator::try_new(config).ok()?;
+    let mut rng = make_rng(Some(seed));
+    (0..SIMILAR_MAX_ROUNDS)
+        .filter_map(|_| generator.generate_with_relaxation_rng(&mut rng))
+        .find(|puzzle| analyze_difficulty(puzzle).level == level)
+}
+
+/// Generate a puzzle whose estimated solving time falls in a band
+///
+/// Each round generates one candidate per difficulty level, easiest first,
+/// and moves on to the next round as soon as a candidate overshoots the
+/// band, since harder levels only take longer.
+///
+/// # Arguments
+/// * `min_seconds` - Shortest acceptable estimate
+/// * `max_seconds` - Longest acceptable estimate
+/// * `seed` - Seed for reproducible generation
+///
+/// # Returns
+/// The puzzle with its estimated solving time in seconds, or `None` if no
+/// candidate landed in the band
+pub fn generate_for_time_band(
+    min_seconds: f64,
+    max_seconds: f64,
+    seed: u64,
+) -> Option<(Vec<Option<u8>>, f64)> {
+    let levels = [
+        DifficultyLevel::VeryEasy,
+        DifficultyLevel::Easy,
+        DifficultyLevel::Medium,
+        DifficultyLevel::Hard,
+        DifficultyLevel::Expert,
+    ];
+    let mut rng = make_rng(Some(seed));
+
+    for _ in 0..TIME_BAND_MAX_ROUNDS {
+        for level in levels {
+            let Some(puzzle) =
+                PuzzleGenerator::with_difficulty(level).generate_with_relaxation_rng(&mut rng)
+            else {
+                continue;
+            };
+            let seconds = analyze_difficulty(&puzzle).estimated_seconds;
+            if seconds > max_seconds {
+                break;
+            }
+            if seconds >= min_seconds {
+                return Some((puzzle, seconds));
+            }
+        }
+    }
+    None
+}
+
+/// Remove redundant givens until a puzzle grades at a target difficulty
+///
+/// Givens are tried in a seeded random order, and each is dropped if the
+/// puzzle stays unique. Stripping stops as soon as the puzzle analyzes at
+/// `target` or harder, so an over-clued import loses only what it must.
+///
+/// # Arguments
+/// * `board` - The puzzle to strip; must have exactly one solution
+/// * `target` - The difficulty the puzzle was meant to have
+/// * `seed` - Seed for the removal order
+///
+/// # Returns
+/// The stripped puzzle and the level it reached, which is below `target`
+/// if no redundant given was left first, or `None` if `board` isn't
+/// uniquely solvable
+pub fn strip_redundant_to_difficulty(
+    board: &[Option<u8>],
+    target: DifficultyLevel,
+    seed: u64,
+) -> Option<(Vec<Option<u8>>, DifficultyLevel)> {
+    if !has_unique_solution(board) {
+        return None;
+    }
+
+    let mut givens: Vec<usize> = (0..board.len()).filter(|&i| board[i].is_some()).collect();
+    givens.shuffle(&mut make_rng(Some(seed)));
+
+    let mut puzzle = board.to_vec();
+    let mut level = analyze_difficulty(&puzzle).level;
+    for index in givens {
+        if level >= target {
+            break;
+        }
+        let saved = puzzle[index].take();
+        if has_unique_solution(&puzzle) {
+            level = analyze_difficulty(&puzzle).level;
+        } else {
+            puzzle[index] = saved;
+        }
+    }
+    Some((puzzle, level))
 }
 
-/// Generate multiple puzzles
+/// Generate a puzzle of a target difficulty around an author's sketched clues
+///
+/// # Arguments
+/// * `partial` - The author's clues; must have exactly one solution
+/// * `difficulty` - The difficulty to aim for
+/// * `seed` - Seed for reproducible generation
+///
+/// # Returns
+/// A unique puzzle containing every sketched clue, or `None` on failure
+pub fn complete_to_puzzle(
+    partial: &[Option<u8>],
+    difficulty: DifficultyLevel,
+    seed: u64,
+) -> Option<Vec<Option<u8>>> {
+    PuzzleGenerator::with_difficulty(difficulty).complete_with_seed(partial, seed)
+}
+
+/// How well one difficulty level's generated puzzles grade back to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelCalibration {
+    /// The level puzzles were generated for
+    pub level: DifficultyLevel,
+    /// Number of puzzles generated
+    pub samples: u32,
+    /// Fraction of samples that analyze back to `level`
+    pub agreement: f64,
+    /// Mean starting branching factor of the samples
+    pub branching_factor_mean: f64,
+    /// Standard deviation of the starting branching factor
+    pub branching_factor_stddev: f64,
+}
+
+/// Generate samples at every difficulty and check how they grade
+///
+/// A health check for the grading pipeline: after an upgrade, agreement
+/// dropping or branching factor bands drifting shows the generator and the
+/// analyzer no longer match.
+///
+/// # Arguments
+/// * `samples_per_level` - Puzzles to generate per level
+/// * `seed` - Seed for reproducible generation
+///
+/// # Returns
+/// One entry per level, easiest first. Failed generations are skipped, so
+/// `samples` may fall short of `samples_per_level`.
+pub fn calibration_report(samples_per_level: u32, seed: u64) -> Vec<LevelCalibration> {
+    let mut rng = make_rng(Some(seed));
+    [
+        DifficultyLevel::VeryEasy,
+        DifficultyLevel::Easy,
+        DifficultyLevel::Medium,
+        DifficultyLevel::Hard,
+        DifficultyLevel::Expert,
+    ]
+    .into_iter()
+    .map(|level| {
+        let generator = PuzzleGenerator::with_difficulty(level);
+        let mut agreeing = 0;
+        let mut branching_factors = Vec::new();
+        for _ in 0..samples_per_level {
+            if let Some(puzzle) = generator.generate_with_relaxation_rng(&mut rng) {
+                if analyze_difficulty(&puzzle).level == level {
+                    agreeing += 1;
+                }
+                branching_factors.push(generator.calculate_branching_factor(&puzzle));
+            }
+        }
+
+        let samples = branching_factors.len() as u32;
+        let (mean, stddev) = if branching_factors.is_empty() {
+            (0.0, 0.0)
+        } else {
+            let count = branching_factors.len() as f64;
+            let mean = branching_factors.iter().sum::<f64>() / count;
+            let variance = branching_factors
+                .iter()
+                .map(|bf| (bf - mean).powi(2))
+                .sum::<f64>()
+                / count;
+            (mean, variance.sqrt())
+        };
+        LevelCalibration {
+            level,
+            samples,
+            agreement: if samples == 0 {
+                0.0
+            } else {
+                agreeing as f64 / samples as f64
+            },
+            branching_factor_mean: mean,
+            branching_factor_stddev: stddev,
+        }
+    })
+    .collect()
+}
+
+/// Generate a puzzle whose hardest required technique is exactly `max_technique`
+///
+/// For lessons that introduce one new technique: a fresh grid is carved
+/// greedily, keeping each removal only while the puzzle stays unique and
+/// solvable without anything harder than `max_technique`. The result is
+/// kept once its solve path actually needs `max_technique`.
+///
+/// # Arguments
+/// * `max_technique` - The curriculum step, one of [`IMPLEMENTED_TECHNIQUES`]
+/// * `seed` - Seed for reproducible generation
+///
+/// # Returns
+/// The puzzle, or `None` if the technique isn't implemented or no grid
+/// needed it within a few rounds
+pub fn generate_for_curriculum(
+    max_technique: SolvingTechnique,
+    seed: u64,
+) -> Option<Vec<Option<u8>>> {
+    if !IMPLEMENTED_TECHNIQUES.contains(&max_technique) {
+        return None;
+    }
+
+    let generator = PuzzleGenerator::new(GeneratorConfig::default());
+    let mut rng = make_rng(Some(seed));
+    let hardest_needed = |puzzle: &[Option<u8>]| {
+        let mut solver = HumanStyleSolver::new(puzzle);
+        solver
+            .solve_with_techniques()
+            .then(|| solver.get_hardest_technique_used())
+    };
+
+    for _ in 0..CURRICULUM_MAX_ROUNDS {
+        let mut puzzle = generator.generate_complete_solution(&mut rng)?;
+        let mut order: Vec<usize> = (0..BOARD_SIZE).collect();
+        order.shuffle(&mut rng);
+
+        for index in order {
+            let saved = puzzle[index].take();
+            let within = has_unique_solution(&puzzle)
+                && hardest_needed(&puzzle).is_some_and(|hardest| hardest <= max_technique);
+            if !within {
+                puzzle[index] = saved;
+            }
+        }
+
+        if hardest_needed(&puzzle) == Some(max_technique.clone()) {
+            return Some(puzzle);
+        }
+    }
+    None
+}
+
+/// Generate a puzzle that logic can't solve without `technique`
+///
+/// Stronger than asking for `technique` as the hardest step: with it
+/// disabled, no other implemented technique, harder ones included, finishes
+/// the puzzle. A fresh grid is carved greedily while the puzzle stays unique
+/// and solvable by logic, until disabling `technique` makes the solver
+/// stall.
+///
+/// # Arguments
+/// * `technique` - One of [`IMPLEMENTED_TECHNIQUES`] other than naked
+///   singles, which can't be disabled
+/// * `seed` - Seed for reproducible generation
+///
+/// # Returns
+/// The puzzle, or `None` if the technique can't be disabled or no grid
+/// came to depend on it within a few rounds
+pub fn generate_requiring_technique(
+    technique: SolvingTechnique,
+    seed: u64,
+) -> Option<Vec<Option<u8>>> {
+    if technique == SolvingTechnique::NakedSingle || !IMPLEMENTED_TECHNIQUES.contains(&technique) {
+        return None;
+    }
+
+    let generator = PuzzleGenerator::new(GeneratorConfig::default());
+    let mut rng = make_rng(Some(seed));
+    let mut disabled = TechniqueSet::new();
+    disabled.insert(technique);
+    let requires_technique = |puzzle: &[Option<u8>]| {
+        !HumanStyleSolver::new(puzzle)
+            .without_techniques(disabled)
+            .solve_with_techniques()
+    };
+
+    for _ in 0..REQUIRED_TECHNIQUE_MAX_ROUNDS {
+        let mut puzzle = generator.generate_complete_solution(&mut rng)?;
+        let mut order: Vec<usize> = (0..BOARD_SIZE).collect();
+        order.shuffle(&mut rng);
+
+        for index in order {
+            let saved = puzzle[index].take();
+            if !has_unique_solution(&puzzle)
+                || !HumanStyleSolver::new(&puzzle).solve_with_techniques()
+            {
+                puzzle[index] = saved;
+            } else if requires_technique(&puzzle) {
+                return Some(puzzle);
+            }
+        }
+    }
+    None
+}
+
+/// Carve a solution grid greedily until no clue can be removed
+///
+/// Cells are visited once in a seeded random order, and each clue is
+/// dropped if the puzzle stays unique. The result is irreducible, but a
+/// different order may reach fewer clues, so its clue count is only an
+/// upper bound on the grid's true minimum.
+///
+/// # Arguments
+/// * `solution` - A completely filled, valid grid
+/// * `seed` - Seed for the removal order
+///
+/// # Returns
+/// The irreducible puzzle, or `None` if `solution` isn't a valid full grid
+pub fn minimal_puzzle(solution: &[Option<u8>], seed: u64) -> Option<Vec<Option<u8>>> {
+    if !is_board_complete(solution) || !is_board_valid(solution) {
+        return None;
+    }
+
+    let mut order: Vec<usize> = (0..BOARD_SIZE).collect();
+    order.shuffle(&mut make_rng(Some(seed)));
+
+    let mut puzzle = solution.to_vec();
+    for index in order {
+        let saved = puzzle[index].take();
+        if !has_unique_solution(&puzzle) {
+            puzzle[index] = saved;
+        }
+    }
+    Some(puzzle)
+}
+
+/// Deterministically generate the n-th complete solution grid
+///
+/// Handy as a test fixture: each `n` gives a different valid grid, and the
+/// same `n` always gives the same one.
+///
+/// # Arguments
+/// * `n` - Index of the grid, used as the seed
+///
+/// # Returns
+/// A completely filled, valid board
+pub fn nth_solution_grid(n: u64) -> Vec<Option<u8>> {
+    PuzzleGenerator::new(GeneratorConfig::default())
+        .generate_complete_solution(&mut make_rng(Some(n)))
+        .expect("an empty grid always has a solution")
+}
+
+/// Generation attempts allowed per requested puzzle in a distinct batch
+const DISTINCT_ATTEMPTS_PER_PUZZLE: usize = 3;
+
+/// Generate multiple puzzles, none equivalent to another
+///
+/// See [`generate_distinct_puzzles`]; this version draws fresh entropy.
 pub fn generate_multiple_puzzles(
     difficulty: DifficultyLevel,
     count: usize,
 ) -> Vec<Vec<Option<u8>>> {
-    let gen = PuzzleGenerator::with_difficulty(difficulty);
-    (0..count).filter_map(|_| gen.generate()).collect()
+    distinct_puzzles(difficulty, count, &mut make_rng(None))
+}
+
+/// Generate a reproducible batch of distinct puzzles
+///
+/// A puzzle equivalent to an earlier one under rotation, reflection or
+/// digit relabeling is thrown away and generation retried, up to
+/// [`DISTINCT_ATTEMPTS_PER_PUZZLE`] attempts per requested puzzle.
+///
+/// # Arguments
+/// * `difficulty` - Target difficulty for every puzzle
+/// * `count` - Number of puzzles wanted
+/// * `seed` - Seed for the whole batch
+///
+/// # Returns
+/// Up to `count` distinct puzzles; fewer if attempts ran out
+pub fn generate_distinct_puzzles(
+    difficulty: DifficultyLevel,
+    count: usize,
+    seed: u64,
+) -> Vec<Vec<Option<u8>>> {
+    distinct_puzzles(difficulty, count, &mut make_rng(Some(seed)))
+}
+
+/// Generate distinct puzzles drawing all randomness from the given RNG
+fn distinct_puzzles(
+    difficulty: DifficultyLevel,
+    count: usize,
+    rng: &mut impl Rng,
+) -> Vec<Vec<Option<u8>>> {
+    let generator = PuzzleGenerator::with_difficulty(difficulty);
+    let mut seen = Deduplicator::new();
+    let mut puzzles = Vec::with_capacity(count);
+
+    for _ in 0..count * DISTINCT_ATTEMPTS_PER_PUZZLE {
+        if puzzles.len() == count {
+            break;
+        }
+        if let Some(puzzle) = generator.generate_with_rng(rng) {
+            if seen.insert(&puzzle) {
+                puzzles.push(puzzle);
+            }
+        }
+    }
+    puzzles
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_fixtures::{parse, SINGLES_PUZZLE, SOLVED_GRID, STALLED_PUZZLE};
+    use crate::transform::fingerprint;
+    use crate::validator::{enumerate_solutions, validate_board};
 
     #[test]
     fn test_enhanced_medium_generation() {
@@ -446,7 +1702,7 @@ mod tests {
 
             // Should have controlled branching factor
             assert!(
-                branching_factor >= 2.0 && branching_factor <= 3.5,
+                (2.0..=3.5).contains(&branching_factor),
                 "Medium BF should be 2.0-3.5, got {:.2}",
                 branching_factor
             );
@@ -464,6 +1720,640 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_progress_callback_fires_every_interval() {
+        // Only one clue can be removed, so every attempt fails quickly
+        let reported = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reported);
+        let mut config = GeneratorConfig::for_difficulty(DifficultyLevel::Expert);
+        config.min_clues = BOARD_SIZE - 1;
+        config.max_clues = BOARD_SIZE - 1;
+        config.max_attempts = 250;
+        config.attempts_per_relaxation = 30;
+        config.log_progress = Some(ProgressCallback::new(move |attempts| {
+            sink.lock().unwrap().push(attempts)
+        }));
+        let generator = PuzzleGenerator::new(config);
+
+        assert_eq!(generator.generate_with_seed(1), None);
+        assert_eq!(*reported.lock().unwrap(), vec![100, 200]);
+
+        // Relaxation batches of 30 still report every 100 attempts overall
+        reported.lock().unwrap().clear();
+        assert_eq!(
+            generator.generate_with_relaxation_rng(&mut make_rng(Some(1))),
+            None
+        );
+        assert_eq!(*reported.lock().unwrap(), vec![100, 200]);
+
+        // Generators with a callback can move to worker threads
+        let generated = std::thread::spawn(move || generator.generate_with_seed(1))
+            .join()
+            .unwrap();
+        assert_eq!(generated, None);
+    }
+
+    #[test]
+    fn test_generate_on_level_reports_analyzed_level() {
+        let generator = PuzzleGenerator::with_difficulty(DifficultyLevel::Hard);
+        let mut on_target = 0;
+        for seed in 0..6 {
+            let (puzzle, level) = generator.generate_on_level_with_seed(seed).unwrap();
+            assert_eq!(level, analyze_difficulty(&puzzle).level);
+            if level == DifficultyLevel::Hard {
+                on_target += 1;
+            }
+        }
+        assert!(on_target >= 5, "only {} of 6 on target", on_target);
+    }
+
+    #[test]
+    fn test_relaxation_improves_expert_success() {
+        // A deliberately tight tolerance so the fixed path mostly fails
+        let mut config = GeneratorConfig::for_difficulty(DifficultyLevel::Expert);
+        config.max_attempts = 6;
+        config.branching_factor_tolerance = 0.05;
+        config.tolerance_step = 0.5;
+        config.attempts_per_relaxation = 2;
+        let generator = PuzzleGenerator::new(config);
+
+        let (mut fixed, mut relaxed) = (0, 0);
+        for seed in 0..20 {
+            if generator.generate_with_seed(seed).is_some() {
+                fixed += 1;
+            }
+            let mut rng = make_rng(Some(seed));
+            if let Some(puzzle) = generator.generate_with_relaxation_rng(&mut rng) {
+                relaxed += 1;
+                // Only the tolerance is relaxed, never the tier or the window
+                let branching_factor = generator.calculate_branching_factor(&puzzle);
+                assert!(analyze_difficulty(&puzzle).hardest_technique >= SolvingTechnique::XYWing);
+                assert!((3.8..=4.6).contains(&branching_factor));
+            }
+        }
+
+        // Relaxing should turn several of the fixed path's failures into successes
+        assert!(relaxed >= 10, "only {} of 20 relaxed successes", relaxed);
+        assert!(
+            relaxed >= fixed + 5,
+            "relaxed {} vs fixed {}",
+            relaxed,
+            fixed
+        );
+    }
+
+    #[test]
+    fn test_expert_branching_window_fits_unique_grids() {
+        // Carve Expert clue counts with the branching factor window opened up
+        let mut config = GeneratorConfig::for_difficulty(DifficultyLevel::Expert);
+        config.min_branching_factor = 0.0;
+        config.max_branching_factor = 9.0;
+        config.branching_factor_tolerance = 9.0;
+        config.max_attempts = 20;
+        let open = PuzzleGenerator::new(config);
+
+        let expert = GeneratorConfig::for_difficulty(DifficultyLevel::Expert);
+        let factors: Vec<f64> = (0..6)
+            .filter_map(|seed| open.generate_with_seed(seed))
+            .map(|puzzle| open.calculate_branching_factor(&puzzle))
+            .collect();
+        assert!(!factors.is_empty());
+        // Uniquely solvable grids never reach the old 4.5 floor
+        assert!(factors.iter().all(|&factor| factor < 4.5), "{:?}", factors);
+        assert!(
+            factors.iter().all(|&factor| factor
+                >= expert.target_branching_factor - expert.branching_factor_tolerance),
+            "{:?}",
+            factors
+        );
+    }
+
+    #[test]
+    fn test_medium_accepts_pointing_pairs() {
+        // 30 clues whose solve needs pointing pairs and which grades Medium
+        let puzzle = parse(
+            ".346..9..6..195..8..8....6..5.7.........53.......2.8.6.6.53.28.2.7..9.....52.6..9",
+        );
+        let analysis = analyze_difficulty(&puzzle);
+        assert_eq!(analysis.hardest_technique, SolvingTechnique::PointingPairs);
+        assert_eq!(analysis.level, DifficultyLevel::Medium);
+
+        let generator = PuzzleGenerator::with_difficulty(DifficultyLevel::Medium);
+        assert!(generator.difficulty_matches_target(&analysis));
+        assert!(!generator.difficulty_overshoot(&analysis));
+    }
+
+    #[test]
+    fn test_every_constructor_validates_the_config() {
+        let mut config = GeneratorConfig::for_difficulty(DifficultyLevel::Expert);
+        config.min_clues = 16;
+        assert_eq!(
+            PuzzleGenerator::try_new(config.clone()).err(),
+            Some(ConfigError::TooFewClues { min_clues: 16 })
+        );
+        let built = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            PuzzleGenerator::new(config)
+        }));
+        assert!(built.is_err());
+
+        let mut config = GeneratorConfig::for_difficulty(DifficultyLevel::Easy);
+        config.max_clues = BOARD_SIZE;
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::TooManyClues { max_clues: 81 })
+        );
+        config.max_clues = 34;
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::ClueRangeInverted {
+                min_clues: 35,
+                max_clues: 34
+            })
+        );
+
+        for level in [
+            DifficultyLevel::VeryEasy,
+            DifficultyLevel::Easy,
+            DifficultyLevel::Medium,
+            DifficultyLevel::Hard,
+            DifficultyLevel::Expert,
+        ] {
+            assert!(GeneratorConfig::for_difficulty(level).validate().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_carved_solution_matches_source() {
+        let generator = PuzzleGenerator::with_difficulty(DifficultyLevel::Medium);
+        let mut rng = make_rng(Some(7));
+        let source = generator.generate_complete_solution(&mut rng).unwrap();
+        let puzzle = generator
+            .create_puzzle_with_branching_factor_control(&source, &[], &mut rng, &mut None)
+            .expect("carving should yield a puzzle");
+
+        let found = unique_solution(&puzzle).expect("carved puzzles are unique");
+        assert_eq!(divergence_from_source(&found, &source), None);
+
+        // 6 and 7 can swap across rows 0 and 3 in columns 3 and 4, so this
+        // carving is solvable but not unique. The old solvability-only check
+        // passed it, and the completion it found need not be the source
+        let source = parse(SOLVED_GRID);
+        let mut carved = source.clone();
+        for index in [3, 4, 30, 31] {
+            carved[index] = None;
+        }
+        let divergences: Vec<Option<usize>> = enumerate_solutions(&carved, 2)
+            .iter()
+            .map(|found| divergence_from_source(found, &source))
+            .collect();
+        assert_eq!(divergences, vec![None, Some(3)]);
+        assert_eq!(unique_solution(&carved), None);
+    }
+
+    #[test]
+    fn test_detect_symmetry() {
+        let generator = PuzzleGenerator::with_difficulty(DifficultyLevel::Medium);
+        let mut rng = make_rng(Some(3));
+        let solution = generator.generate_complete_solution(&mut rng).unwrap();
+
+        // Clues removed in 180° pairs
+        let mut symmetric = solution.clone();
+        for index in [0, 10, 22, 31, 39] {
+            symmetric[index] = None;
+            symmetric[symmetric_index(index, SymmetryMode::Rotational)] = None;
+        }
+        assert_eq!(detect_symmetry(&symmetric), vec![SymmetryMode::Rotational]);
+
+        let mut asymmetric = solution.clone();
+        asymmetric[0] = None;
+        asymmetric[1] = None;
+        assert_eq!(detect_symmetry(&asymmetric), vec![SymmetryMode::None]);
+
+        // An empty or full board has every symmetry
+        assert_eq!(detect_symmetry(&solution).len(), 4);
+    }
+
+    #[test]
+    fn test_clue_balance_score() {
+        let solution = PuzzleGenerator::with_difficulty(DifficultyLevel::Medium)
+            .generate_complete_solution(&mut make_rng(Some(3)))
+            .unwrap();
+
+        // Three clues in every box versus 27 clues packed into the
+        // top-left corner
+        let mut even = vec![None; BOARD_SIZE];
+        let mut clustered = vec![None; BOARD_SIZE];
+        for index in 0..BOARD_SIZE {
+            let (row, col) = index_to_coords(index);
+            if row % 3 == col % 3 {
+                even[index] = solution[index];
+            }
+        }
+        let corner = (0..BOARD_SIZE).filter(|&index| {
+            let (row, col) = index_to_coords(index);
+            row < 6 && col < 6
+        });
+        for index in corner.take(27) {
+            clustered[index] = solution[index];
+        }
+
+        assert_eq!(clue_balance_score(&even), 0.0);
+        assert!(clue_balance_score(&clustered) > clue_balance_score(&even));
+        assert_eq!(clue_balance_score(&solution), 0.0);
+    }
+
+    #[test]
+    fn test_very_easy_puzzles_never_rate_medium() {
+        let puzzles = generate_distinct_puzzles(DifficultyLevel::VeryEasy, 6, 10);
+        assert_eq!(puzzles.len(), 6);
+        for puzzle in puzzles {
+            let level = analyze_difficulty(&puzzle).level;
+            assert!(
+                level <= DifficultyLevel::Easy,
+                "VeryEasy puzzle rated {:?}",
+                level
+            );
+        }
+    }
+
+    #[test]
+    fn test_distinct_puzzles_share_no_fingerprint() {
+        let puzzles = generate_distinct_puzzles(DifficultyLevel::Easy, 25, 5);
+        assert_eq!(puzzles.len(), 25);
+
+        let mut fingerprints: Vec<String> = puzzles.iter().map(|p| fingerprint(p)).collect();
+        fingerprints.sort_unstable();
+        fingerprints.dedup();
+        assert_eq!(fingerprints.len(), puzzles.len());
+        assert_eq!(
+            generate_distinct_puzzles(DifficultyLevel::Easy, 25, 5),
+            puzzles
+        );
+    }
+
+    #[test]
+    fn test_removal_groups_keep_fixed_points_single() {
+        // The center is its own partner under a half turn
+        assert_eq!(symmetric_index(40, SymmetryMode::Rotational), 40);
+        let rotational = removal_groups(SymmetryMode::Rotational);
+        assert_eq!(rotational.len(), 41);
+        assert!(rotational.contains(&vec![40]));
+        assert!(rotational.contains(&vec![0, 80]));
+
+        // The main diagonal is fixed under a diagonal flip
+        let diagonal = removal_groups(SymmetryMode::Diagonal);
+        let singles: Vec<usize> = diagonal
+            .iter()
+            .filter(|group| group.len() == 1)
+            .map(|group| group[0])
+            .collect();
+        assert_eq!(
+            singles,
+            (0..GRID_SIZE)
+                .map(|i| coords_to_index(i, i))
+                .collect::<Vec<_>>()
+        );
+        assert!(diagonal.contains(&vec![coords_to_index(0, 1), coords_to_index(1, 0)]));
+
+        for (mode, fixed) in [
+            (SymmetryMode::None, BOARD_SIZE),
+            (SymmetryMode::Rotational, 1),
+            (SymmetryMode::Horizontal, GRID_SIZE),
+            (SymmetryMode::Vertical, GRID_SIZE),
+            (SymmetryMode::Diagonal, GRID_SIZE),
+        ] {
+            let groups = removal_groups(mode);
+            let mut cells: Vec<usize> = groups.iter().flatten().copied().collect();
+            cells.sort_unstable();
+            assert_eq!(cells, (0..BOARD_SIZE).collect::<Vec<_>>(), "{:?}", mode);
+            assert_eq!(
+                groups.iter().filter(|g| g.len() == 1).count(),
+                fixed,
+                "{:?}",
+                mode
+            );
+            assert!(groups
+                .iter()
+                .all(|g| g.iter().all(|&i| g.contains(&symmetric_index(i, mode)))));
+        }
+    }
+
+    #[test]
+    fn test_regenerate_similar_matches_tier() {
+        let source = PuzzleGenerator::with_difficulty(DifficultyLevel::Hard)
+            .generate_with_seed(5)
+            .expect("Hard generation should succeed");
+
+        let similar = regenerate_similar(&source, 6).expect("a similar puzzle should be found");
+        assert_ne!(similar, source);
+        assert!(has_unique_solution(&similar));
+        assert_eq!(
+            analyze_difficulty(&similar).level,
+            analyze_difficulty(&source).level
+        );
+    }
+
+    #[test]
+    fn test_forbidden_techniques_leave_singles_only() {
+        let mut config = GeneratorConfig::for_difficulty(DifficultyLevel::Medium);
+        config.forbidden_techniques = TechniqueSet::harder_than(SolvingTechnique::HiddenSingle);
+        let puzzle = PuzzleGenerator::new(config)
+            .generate_with_seed(9)
+            .expect("Medium generation should succeed");
+
+        let mut solver = HumanStyleSolver::new(&puzzle);
+        assert!(solver.solve_with_techniques());
+        assert!(solver.get_hardest_technique_used() <= SolvingTechnique::HiddenSingle);
+    }
+
+    #[test]
+    fn test_reject_automorphic_puzzles() {
+        let mut config = GeneratorConfig::for_difficulty(DifficultyLevel::Easy);
+        config.reject_automorphic = true;
+        let puzzle = PuzzleGenerator::new(config)
+            .generate_with_seed(4)
+            .expect("Easy generation should succeed");
+
+        assert!(!has_nontrivial_automorphism(&puzzle));
+    }
+
+    #[test]
+    fn test_complete_to_puzzle_keeps_author_clues() {
+        let partial = parse(STALLED_PUZZLE);
+
+        let puzzle = complete_to_puzzle(&partial, DifficultyLevel::Medium, 3)
+            .expect("Medium completion should succeed");
+        assert!(partial
+            .iter()
+            .zip(&puzzle)
+            .all(|(given, cell)| given.is_none() || given == cell));
+        assert!(has_unique_solution(&puzzle));
+
+        // A sketch with many solutions can't be completed
+        assert_eq!(
+            complete_to_puzzle(&[None; BOARD_SIZE], DifficultyLevel::Medium, 3),
+            None
+        );
+    }
+
+    #[test]
+    fn test_smooth_progression_offers_more_singles_along_the_path() {
+        // Average number of cells open to a single at each step of the solve
+        let singles_per_step = |puzzle: &[Option<u8>]| {
+            let mut board = puzzle.to_vec();
+            let trace = crate::solver::solve_trace(puzzle);
+            let mut total = 0;
+            for step in &trace {
+                total += count_single_placements(&board);
+                board[step.index] = Some(step.value);
+            }
+            total as f64 / trace.len().max(1) as f64
+        };
+        let average_over_seeds = |smooth_progression: bool| {
+            let mut config = GeneratorConfig::for_difficulty(DifficultyLevel::Medium);
+            config.smooth_progression = smooth_progression;
+            let generator = PuzzleGenerator::new(config);
+            (0..4)
+                .map(|seed| singles_per_step(&generator.generate_with_seed(seed).unwrap()))
+                .sum::<f64>()
+                / 4.0
+        };
+
+        let plain = average_over_seeds(false);
+        let smooth = average_over_seeds(true);
+        assert!(smooth > plain, "smooth {} vs plain {}", smooth, plain);
+    }
+
+    #[test]
+    fn test_max_guesses_zero_keeps_puzzles_logical() {
+        let mut config = GeneratorConfig::for_difficulty(DifficultyLevel::Hard);
+        config.max_guesses = Some(0);
+        let generator = PuzzleGenerator::new(config);
+
+        for seed in 0..3 {
+            let puzzle = generator
+                .generate_with_seed(seed)
+                .expect("Hard generation should succeed");
+            assert_eq!(count_required_guesses(&puzzle), Some(0));
+            assert!(HumanStyleSolver::new(&puzzle).solve_with_techniques());
+        }
+    }
+
+    #[test]
+    fn test_mirror_pair_solves_identically() {
+        let generator = PuzzleGenerator::with_difficulty(DifficultyLevel::Medium);
+        let MirrorPair { first, second } = generator
+            .generate_mirror_pair_with_seed(4)
+            .expect("Medium generation should succeed");
+        assert_ne!(first, second);
+        assert_eq!(fingerprint(&first), fingerprint(&second));
+
+        let (a, b) = (analyze_difficulty(&first), analyze_difficulty(&second));
+        assert_eq!(a.level, b.level);
+        assert_eq!(a.hardest_technique, b.hardest_technique);
+        assert_eq!(solve_path_length(&first), solve_path_length(&second));
+    }
+
+    #[test]
+    fn test_avoid_initial_naked_singles() {
+        let mut config = GeneratorConfig::for_difficulty(DifficultyLevel::Hard);
+        config.avoid_initial_naked_singles = true;
+        let generator = PuzzleGenerator::new(config);
+
+        for seed in 0..2 {
+            let puzzle = generator
+                .generate_with_seed(seed)
+                .expect("Hard generation should succeed");
+            assert_eq!(HumanStyleSolver::new(&puzzle).peek_naked_single(), None);
+            assert!(has_unique_solution(&puzzle));
+        }
+    }
+
+    #[test]
+    fn test_generation_diagnostics_name_bottleneck() {
+        let mut config = GeneratorConfig::for_difficulty(DifficultyLevel::Expert);
+        config.min_clues = MIN_POSSIBLE_CLUES;
+        config.max_clues = MIN_POSSIBLE_CLUES;
+        config.max_attempts = 5;
+        let failure = PuzzleGenerator::new(config)
+            .generate_with_diagnostics(1)
+            .expect_err("carving rarely reaches 17 clues");
+
+        assert_eq!(failure.attempts, 5);
+        assert_eq!(failure.failures(Constraint::ClueCount), 5);
+        assert_eq!(failure.bottleneck(), Some(Constraint::ClueCount));
+
+        // A feasible config succeeds with the same puzzle as plain generation
+        let generator = PuzzleGenerator::with_difficulty(DifficultyLevel::Easy);
+        assert_eq!(
+            generator.generate_with_diagnostics(8).ok(),
+            generator.generate_with_seed(8)
+        );
+    }
+
+    #[test]
+    fn test_generate_in_branching_range() {
+        let puzzle = generate_in_branching_range(DifficultyLevel::Medium, 2.0, 3.0, 11)
+            .expect("medium puzzles span this range");
+        let branching_factor = HumanStyleSolver::new(&puzzle).calculate_branching_factor();
+        assert!(
+            (2.0..=3.0).contains(&branching_factor),
+            "branching factor {} outside 2.0..=3.0",
+            branching_factor
+        );
+        assert!(has_unique_solution(&puzzle));
+    }
+
+    #[test]
+    fn test_generate_for_time_band() {
+        let (puzzle, seconds) =
+            generate_for_time_band(200.0, 260.0, 4).expect("medium puzzles take about 4 minutes");
+        assert!(
+            (200.0..=260.0).contains(&seconds),
+            "estimate {} outside 200..=260",
+            seconds
+        );
+        assert_eq!(analyze_difficulty(&puzzle).estimated_seconds, seconds);
+        assert!(has_unique_solution(&puzzle));
+    }
+
+    #[test]
+    fn test_calibration_report_covers_every_level() {
+        let report = calibration_report(1, 3);
+        let levels: Vec<DifficultyLevel> = report.iter().map(|entry| entry.level).collect();
+        assert_eq!(
+            levels,
+            vec![
+                DifficultyLevel::VeryEasy,
+                DifficultyLevel::Easy,
+                DifficultyLevel::Medium,
+                DifficultyLevel::Hard,
+                DifficultyLevel::Expert,
+            ]
+        );
+        for entry in &report {
+            assert_eq!(entry.samples, 1, "{:?}", entry.level);
+            assert!((0.0..=1.0).contains(&entry.agreement));
+            assert!(entry.branching_factor_mean >= 1.0);
+            assert!(entry.branching_factor_stddev >= 0.0);
+        }
+    }
+
+    #[test]
+    fn test_strip_redundant_to_difficulty() {
+        let intended = parse(SINGLES_PUZZLE);
+        let solution = unique_solution(&intended).unwrap();
+
+        // Eight extra clues make the Medium puzzle look trivial
+        let mut over_clued = intended.clone();
+        for index in (0..BOARD_SIZE)
+            .step_by(3)
+            .filter(|&i| intended[i].is_none())
+            .take(8)
+        {
+            over_clued[index] = solution[index];
+        }
+        assert!(analyze_difficulty(&over_clued).level < DifficultyLevel::Medium);
+
+        let (stripped, level) =
+            strip_redundant_to_difficulty(&over_clued, DifficultyLevel::Medium, 1).unwrap();
+        assert_eq!(level, DifficultyLevel::Medium);
+        assert_eq!(analyze_difficulty(&stripped).level, level);
+        assert_eq!(unique_solution(&stripped), Some(solution));
+        assert!((0..BOARD_SIZE).all(|i| stripped[i].is_none() || stripped[i] == over_clued[i]));
+
+        assert_eq!(
+            strip_redundant_to_difficulty(&[None; BOARD_SIZE], DifficultyLevel::Medium, 1),
+            None
+        );
+    }
+
+    #[test]
+    fn test_generate_for_curriculum_hits_step() {
+        for step in [
+            SolvingTechnique::HiddenSingle,
+            SolvingTechnique::PointingPairs,
+        ] {
+            let puzzle = generate_for_curriculum(step.clone(), 2).expect("step is implemented");
+            assert!(has_unique_solution(&puzzle));
+
+            let mut solver = HumanStyleSolver::new(&puzzle);
+            assert!(solver.solve_with_techniques());
+            assert_eq!(solver.get_hardest_technique_used(), step);
+        }
+
+        // A stubbed technique can never be the hardest one used
+        assert_eq!(generate_for_curriculum(SolvingTechnique::XYWing, 2), None);
+    }
+
+    #[test]
+    fn test_generate_requiring_technique_stalls_without_it() {
+        let technique = SolvingTechnique::PointingPairs;
+        let puzzle = generate_requiring_technique(technique.clone(), 1).expect("found a puzzle");
+        assert!(has_unique_solution(&puzzle));
+        assert!(HumanStyleSolver::new(&puzzle).solve_with_techniques());
+
+        let mut disabled = TechniqueSet::new();
+        disabled.insert(technique);
+        assert!(!HumanStyleSolver::new(&puzzle)
+            .without_techniques(disabled)
+            .solve_with_techniques());
+
+        assert_eq!(
+            generate_requiring_technique(SolvingTechnique::NakedSingle, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_minimal_puzzle_is_irreducible() {
+        let solution = nth_solution_grid(3);
+        let puzzle = minimal_puzzle(&solution, 8).expect("a full grid can be carved");
+        let clue_count = puzzle.iter().filter(|c| c.is_some()).count();
+        assert!(clue_count >= MIN_POSSIBLE_CLUES, "{} clues", clue_count);
+        assert_eq!(unique_solution(&puzzle), Some(solution.clone()));
+
+        for index in (0..BOARD_SIZE).filter(|&i| puzzle[i].is_some()) {
+            let mut reduced = puzzle.clone();
+            reduced[index] = None;
+            assert!(
+                !has_unique_solution(&reduced),
+                "clue {} is redundant",
+                index
+            );
+        }
+        assert_eq!(minimal_puzzle(&solution, 8), Some(puzzle));
+
+        let mut partial = solution;
+        partial[0] = None;
+        assert_eq!(minimal_puzzle(&partial, 8), None);
+    }
+
+    #[test]
+    fn test_nth_solution_grid() {
+        let first = nth_solution_grid(0);
+        let second = nth_solution_grid(1);
+
+        for grid in [&first, &second] {
+            assert!(validate_board(grid).is_complete);
+        }
+        assert_ne!(first, second);
+        assert_eq!(nth_solution_grid(0), first);
+    }
+
+    #[test]
+    fn test_generate_iter_yields_unique_puzzles() {
+        let generator = PuzzleGenerator::with_difficulty(DifficultyLevel::Easy);
+        let puzzles: Vec<_> = generator.generate_iter(21).take(3).collect();
+
+        assert_eq!(puzzles.len(), 3);
+        for puzzle in &puzzles {
+            assert!(validate_board(puzzle).invalid_indices.is_empty());
+            assert!(has_unique_solution(puzzle));
+        }
+        assert_ne!(puzzles[0], puzzles[1]);
+    }
+
     #[test]
     fn test_custom_branching_factor() {
         println!("Testing custom branching factor generation...");
@@ -498,6 +2388,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_clue_pattern_matches_clue_range() {
+        let config = GeneratorConfig::for_difficulty(DifficultyLevel::Medium);
+        let pattern = generate_clue_pattern(DifficultyLevel::Medium, 42)
+            .expect("Medium clue pattern should generate");
+
+        assert_eq!(pattern.len(), BOARD_SIZE);
+        let givens = pattern.iter().filter(|&&given| given).count();
+        assert!(
+            (config.min_clues..=config.max_clues).contains(&givens),
+            "Medium pattern should have {}-{} givens, got {}",
+            config.min_clues,
+            config.max_clues,
+            givens
+        );
+
+        // Same seed, same layout
+        assert_eq!(
+            generate_clue_pattern(DifficultyLevel::Medium, 42),
+            Some(pattern)
+        );
+    }
+
+    #[test]
+    fn test_symmetric_carving_respects_clue_window() {
+        // An odd minimum can only be reached by falling back to a single cell
+        let mut config = GeneratorConfig::for_difficulty(DifficultyLevel::Hard);
+        config.prefer_symmetry = true;
+        config.min_clues = 27;
+        config.max_clues = 28;
+        let generator = PuzzleGenerator::new(config);
+
+        for seed in 0..5 {
+            let puzzle = generator
+                .generate_with_seed(seed)
+                .expect("Hard generation should succeed");
+            let clue_count = puzzle.iter().filter(|c| c.is_some()).count();
+            assert!(
+                (27..=28).contains(&clue_count),
+                "seed {} gave {} clues",
+                seed,
+                clue_count
+            );
+        }
+    }
+
+    #[test]
+    fn test_expert_prefers_asymmetric_removal() {
+        assert!(!GeneratorConfig::for_difficulty(DifficultyLevel::Expert).prefer_symmetry);
+
+        // Seeds whose Expert generation reaches the 17-24 clue range
+        let reached = |prefer_symmetry: bool| {
+            let mut config = GeneratorConfig::for_difficulty(DifficultyLevel::Expert);
+            config.prefer_symmetry = prefer_symmetry;
+            config.max_attempts = 50;
+            let generator = PuzzleGenerator::new(config);
+            (0..6)
+                .filter_map(|seed| generator.generate_with_seed(seed))
+                .filter(|puzzle| {
+                    let clue_count = puzzle.iter().filter(|c| c.is_some()).count();
+                    (MIN_POSSIBLE_CLUES..=24).contains(&clue_count)
+                })
+                .count()
+        };
+        let asymmetric = reached(false);
+        let symmetric = reached(true);
+        assert!(
+            asymmetric > symmetric,
+            "Asymmetric removal should reach the Expert range more often ({} vs {} of 6)",
+            asymmetric,
+            symmetric
+        );
+    }
+
     #[test]
     fn test_very_easy_generation() {
         println!("Testing VeryEasy generation with branching factor control...");
@@ -515,7 +2479,7 @@ mod tests {
 
             // Should have controlled branching factor
             assert!(
-                branching_factor >= 1.0 && branching_factor <= 1.7,
+                (1.0..=1.7).contains(&branching_factor),
                 "VeryEasy BF should be 1.0-1.7, got {:.2}",
                 branching_factor
             );
@@ -3,7 +3,21 @@
 //! This module provides utility functions for converting between different
 //! coordinate systems and performing basic Sudoku grid operations.
 
-use crate::types::{BOX_SIZE, GRID_SIZE};
+use std::sync::OnceLock;
+
+use crate::types::{BOARD_SIZE, BOX_SIZE, GRID_SIZE};
+
+/// Number of cells sharing a row, column or box with any one cell
+pub const PEER_COUNT: usize = 20;
+
+/// Number of units: nine rows, nine columns and nine boxes
+pub const UNIT_COUNT: usize = 3 * GRID_SIZE;
+
+/// Each cell's peers in ascending order, built on first use
+static PEERS: OnceLock<[[usize; PEER_COUNT]; BOARD_SIZE]> = OnceLock::new();
+
+/// The cells of every unit: rows, then columns, then boxes
+static UNITS: OnceLock<[[usize; GRID_SIZE]; UNIT_COUNT]> = OnceLock::new();
 
 /// Converts row and column coordinates (0-8, 0-8) to a board index (0-80).
 ///
@@ -31,6 +45,28 @@ pub fn index_to_coords(index: usize) -> (usize, usize) {
     (index / GRID_SIZE, index % GRID_SIZE)
 }
 
+/// Converts 1-based competition coordinates (1-9, 1-9) to a board index (0-80).
+///
+/// Returns `None` if either coordinate is outside 1-9.
+///
+/// # Examples
+/// ```
+/// use sudoku_wasm::grid::rc_to_index;
+/// assert_eq!(rc_to_index(1, 1), Some(0));   // R1C1
+/// assert_eq!(rc_to_index(9, 9), Some(80));  // R9C9
+/// assert_eq!(rc_to_index(0, 5), None);      // No row 0
+/// ```
+pub fn rc_to_index(row: usize, col: usize) -> Option<usize> {
+    let in_range = |coord: usize| (1..=GRID_SIZE).contains(&coord);
+    (in_range(row) && in_range(col)).then(|| coords_to_index(row - 1, col - 1))
+}
+
+/// Names a cell in competition notation, e.g. `"R3C5"` for index 22.
+pub fn index_to_rc_string(index: usize) -> String {
+    let (row, col) = index_to_coords(index);
+    format!("R{}C{}", row + 1, col + 1)
+}
+
 /// Calculates the 3x3 box index (0-8) for given row and column coordinates.
 /// Boxes are numbered left-to-right, top-to-bottom.
 ///
@@ -52,6 +88,26 @@ pub fn get_box_index(row: usize, col: usize) -> usize {
     (row / BOX_SIZE) * BOX_SIZE + (col / BOX_SIZE)
 }
 
+/// Gets the row, column and box a cell belongs to
+///
+/// # Examples
+/// ```
+/// use sudoku_wasm::grid::unit_indices;
+/// assert_eq!(unit_indices(0), (0, 0, 0));   // Top-left corner
+/// assert_eq!(unit_indices(40), (4, 4, 4));  // Center cell
+/// assert_eq!(unit_indices(80), (8, 8, 8));  // Bottom-right corner
+/// ```
+///
+/// # Arguments
+/// * `index` - The cell index (0-80)
+///
+/// # Returns
+/// A tuple (row, col, box), each in 0-8
+pub fn unit_indices(index: usize) -> (usize, usize, usize) {
+    let (row, col) = index_to_coords(index);
+    (row, col, get_box_index(row, col))
+}
+
 /// Gets the starting coordinates of a 3x3 box given the box index
 ///
 /// # Arguments
@@ -206,6 +262,113 @@ pub fn get_box_indices(index: usize) -> Vec<usize> {
     indices
 }
 
+/// Gets the cells of every unit from the precomputed table
+///
+/// Rows 0-8 come first, then columns 0-8, then boxes 0-8 in reading order.
+/// Each unit lists its cells in ascending index order.
+///
+/// # Returns
+/// The 27 units, computed once and shared
+pub fn units() -> &'static [[usize; GRID_SIZE]; UNIT_COUNT] {
+    UNITS.get_or_init(|| {
+        std::array::from_fn(|unit| {
+            let i = unit % GRID_SIZE;
+            let (box_row, box_col) = get_box_start_coords(i);
+            std::array::from_fn(|j| match unit / GRID_SIZE {
+                0 => coords_to_index(i, j),
+                1 => coords_to_index(j, i),
+                _ => coords_to_index(box_row + j / BOX_SIZE, box_col + j % BOX_SIZE),
+            })
+        })
+    })
+}
+
+/// Gets the cells of a row from the precomputed table
+///
+/// # Arguments
+/// * `row` - The row (0-8)
+pub fn row_cells(row: usize) -> &'static [usize; GRID_SIZE] {
+    &units()[row]
+}
+
+/// Gets the cells of a column from the precomputed table
+///
+/// # Arguments
+/// * `col` - The column (0-8)
+pub fn column_cells(col: usize) -> &'static [usize; GRID_SIZE] {
+    &units()[GRID_SIZE + col]
+}
+
+/// Gets the cells of a box from the precomputed table
+///
+/// # Arguments
+/// * `box_index` - The box (0-8, in reading order)
+pub fn box_cells(box_index: usize) -> &'static [usize; GRID_SIZE] {
+    &units()[2 * GRID_SIZE + box_index]
+}
+
+/// Gets the cells that share a row, column or box with a cell
+///
+/// Built once on first use, so tight solver loops don't recompute and
+/// allocate index lists.
+///
+/// # Arguments
+/// * `index` - The cell index (0-80)
+///
+/// # Returns
+/// The 20 peers in ascending order, excluding the cell itself
+pub fn peers(index: usize) -> &'static [usize; PEER_COUNT] {
+    &PEERS.get_or_init(|| {
+        let mut peers = [[0; PEER_COUNT]; BOARD_SIZE];
+        for (index, cell_peers) in peers.iter_mut().enumerate() {
+            let (row, col, box_index) = unit_indices(index);
+            let mut seen = [false; BOARD_SIZE];
+            for &peer in row_cells(row)
+                .iter()
+                .chain(column_cells(col))
+                .chain(box_cells(box_index))
+            {
+                seen[peer] = true;
+            }
+            seen[index] = false;
+            for (slot, peer) in cell_peers
+                .iter_mut()
+                .zip((0..BOARD_SIZE).filter(|&peer| seen[peer]))
+            {
+                *slot = peer;
+            }
+        }
+        peers
+    })[index]
+}
+
+/// Finds where a number can still go in each box by cross-hatching
+///
+/// Each placed copy of `num` rules out its row and column; the remaining
+/// empty cells of each box are where `num` could still go. Boxes that
+/// already contain `num` have no cells left.
+///
+/// # Arguments
+/// * `board` - The current board state
+/// * `num` - The number to cross-hatch (1-9)
+///
+/// # Returns
+/// Nine lists of cell indices, one per box in box order
+pub fn crosshatch(board: &[Option<u8>], num: u8) -> Vec<Vec<usize>> {
+    (0..GRID_SIZE)
+        .map(|box_index| {
+            let (start_row, start_col) = get_box_start_coords(box_index);
+            get_box_indices(coords_to_index(start_row, start_col))
+                .into_iter()
+                .filter(|&index| {
+                    let (row, col) = index_to_coords(index);
+                    board[index].is_none() && is_valid_placement(board, row, col, num)
+                })
+                .collect()
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,6 +391,20 @@ mod tests {
         assert_eq!(index_to_coords(40), (4, 4));
     }
 
+    #[test]
+    fn test_rc_notation() {
+        assert_eq!(rc_to_index(1, 1), Some(0));
+        assert_eq!(rc_to_index(9, 9), Some(80));
+        assert_eq!(index_to_rc_string(40), "R5C5");
+        assert_eq!(index_to_rc_string(rc_to_index(3, 5).unwrap()), "R3C5");
+
+        // Zero and ten are outside competition notation
+        assert_eq!(rc_to_index(0, 1), None);
+        assert_eq!(rc_to_index(1, 0), None);
+        assert_eq!(rc_to_index(10, 1), None);
+        assert_eq!(rc_to_index(1, 10), None);
+    }
+
     #[test]
     fn test_get_box_index() {
         assert_eq!(get_box_index(0, 0), 0); // Top-left box
@@ -237,4 +414,47 @@ mod tests {
         assert_eq!(get_box_index(4, 4), 4); // Center box
         assert_eq!(get_box_index(8, 8), 8); // Bottom-right box
     }
+
+    #[test]
+    fn test_crosshatch_single_cell() {
+        // 5s in rows 0-1 and columns 0-1 leave only (2, 2) in box 0
+        let mut board = vec![None; 81];
+        for (row, col) in [(0, 3), (1, 6), (4, 0), (7, 1)] {
+            board[coords_to_index(row, col)] = Some(5);
+        }
+
+        let cells = crosshatch(&board, 5);
+        assert_eq!(cells.len(), 9);
+        assert_eq!(cells[0], vec![coords_to_index(2, 2)]);
+        assert!(cells[1].is_empty()); // Box 1 already has a 5
+        assert_eq!(cells[8].len(), 4); // Column 6 and row 7 are blocked
+    }
+
+    #[test]
+    fn test_precomputed_tables_match_index_lists() {
+        for index in 0..BOARD_SIZE {
+            let (row, col, box_index) = unit_indices(index);
+            assert_eq!(row_cells(row).to_vec(), get_row_indices(index));
+            assert_eq!(column_cells(col).to_vec(), get_column_indices(index));
+            assert_eq!(box_cells(box_index).to_vec(), get_box_indices(index));
+
+            let mut expected = get_row_indices(index);
+            expected.extend(get_column_indices(index));
+            expected.extend(get_box_indices(index));
+            expected.sort_unstable();
+            expected.dedup();
+            expected.retain(|&peer| peer != index);
+            assert_eq!(peers(index).to_vec(), expected);
+        }
+    }
+
+    #[test]
+    fn test_unit_indices() {
+        assert_eq!(unit_indices(0), (0, 0, 0)); // Top-left corner
+        assert_eq!(unit_indices(8), (0, 8, 2)); // Top-right corner
+        assert_eq!(unit_indices(72), (8, 0, 6)); // Bottom-left corner
+        assert_eq!(unit_indices(80), (8, 8, 8)); // Bottom-right corner
+        assert_eq!(unit_indices(40), (4, 4, 4)); // Center cell
+        assert_eq!(unit_indices(30), (3, 3, 4)); // Top-left of center box
+    }
 }
@@ -12,18 +12,28 @@
 //! # Module Organization
 //!
 //! - [`types`] - Core type definitions and constants
+//! - [`delta`] - Single-cell board deltas for undo/redo
 //! - [`grid`] - Grid coordinate utilities and basic operations  
+//! - [`hexadoku`] - 16x16 grids with 4x4 boxes
+//! - [`transform`] - Rotations, reflections and canonical forms
 //! - [`validator`] - Board validation and constraint checking
 //! - [`difficulty`] - Puzzle difficulty analysis and classification
+//! - [`rng`] - Seeded and entropy-backed random number generators
 //! - [`solver`] - Human-style solving with logical techniques
 //! - [`generator`] - Puzzle generation with difficulty targeting
 //! - [`wasm_exports`] - WebAssembly interface for JavaScript
 
 // Module declarations
+pub mod delta;
 pub mod difficulty;
 pub mod generator;
 pub mod grid;
+pub mod hexadoku;
+pub mod rng;
 pub mod solver;
+#[cfg(test)]
+mod test_fixtures;
+pub mod transform;
 pub mod types;
 pub mod validator;
 pub mod wasm_exports;
@@ -31,11 +41,14 @@ pub mod wasm_exports;
 // Re-export main functionality for easier access
 pub use difficulty::analyze_difficulty;
 pub use generator::{
-    generate_puzzle, generate_puzzle_enhanced, generate_puzzle_with_branching_factor,
-    GeneratorConfig, PuzzleGenerator,
+    generate_puzzle, generate_puzzle_enhanced, generate_puzzle_with_branching_factor, ConfigError,
+    GeneratorConfig, PuzzleGenerator, MIN_POSSIBLE_CLUES,
 };
 pub use solver::HumanStyleSolver;
-pub use types::{DifficultyAnalysis, DifficultyLevel, SolvingTechnique, BOARD_SIZE, GRID_SIZE};
+pub use types::{
+    Confidence, DifficultyAnalysis, DifficultyLevel, InputError, SolvingTechnique, SymmetryMode,
+    TechniqueSet, BOARD_SIZE, GRID_SIZE,
+};
 pub use validator::{has_unique_solution, solve_board, validate_board};
 
 // Re-export WASM functions for direct access
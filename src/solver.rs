@@ -3,14 +3,142 @@
 //! This module implements a solver that mimics human logical reasoning
 //! by applying various solving techniques in order of increasing complexity.
 
-use crate::types::{SolvingTechnique, CandidateGrid, BOARD_SIZE, GRID_SIZE, BOX_SIZE};
-use crate::grid::{index_to_coords, coords_to_index};
+use crate::types::{
+    SolvingTechnique, CandidateGrid, TechniqueSet, UnitKind, BOARD_SIZE, GRID_SIZE, BOX_SIZE,
+};
+use crate::grid::{
+    box_cells, column_cells, coords_to_index, get_box_index, index_to_coords, index_to_rc_string,
+    peers, row_cells, unit_indices, units,
+};
+use crate::validator::{check_board_values, is_board_valid, solve_board};
+
+/// Techniques the solver actually applies, in difficulty order
+///
+/// The others are recognized for grading but not implemented yet, so a
+/// solve path never uses them.
+pub const IMPLEMENTED_TECHNIQUES: [SolvingTechnique; 11] = [
+    SolvingTechnique::NakedSingle,
+    SolvingTechnique::HiddenSingle,
+    SolvingTechnique::BoxLineReduction,
+    SolvingTechnique::PointingPairs,
+    SolvingTechnique::XWing,
+    SolvingTechnique::PointingTriples,
+    SolvingTechnique::EmptyRectangle,
+    SolvingTechnique::FinnedXWing,
+    SolvingTechnique::RemotePairs,
+    SolvingTechnique::Swordfish,
+    SolvingTechnique::AlmostLockedSet,
+];
+
+/// Lists the techniques the solver can genuinely detect
+///
+/// Difficulty ratings that cite any other technique come from heuristics,
+/// not from the solver finding it on the board.
+///
+/// # Returns
+/// Display names of [`IMPLEMENTED_TECHNIQUES`], in difficulty order
+pub fn implemented_techniques() -> Vec<String> {
+    IMPLEMENTED_TECHNIQUES
+        .iter()
+        .map(|technique| technique.display_name().to_string())
+        .collect()
+}
+
+/// Largest almost locked set, in cells, that the solver looks for
+///
+/// Bigger sets are rarely needed and multiply the pairs to compare.
+const MAX_ALS_SIZE: usize = 3;
+
+/// Maximum nesting of guesses explored by [`count_required_guesses`]
+const MAX_GUESS_DEPTH: u32 = 16;
+
+/// Maximum number of guessed branches explored by [`count_required_guesses`]
+const MAX_GUESS_NODES: usize = 50_000;
+
+/// A single suggested placement and the technique that justifies it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hint {
+    /// The cell to fill (0-80)
+    pub index: usize,
+    /// The number to place (1-9)
+    pub value: u8,
+    /// The hardest technique needed to reach this placement
+    pub technique: SolvingTechnique,
+    /// For hidden singles, the unit where the number had one spot left
+    pub unit: Option<(UnitKind, usize)>,
+}
+
+/// One placement in a solve trace
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolveStep {
+    /// The cell that was filled (0-80)
+    pub index: usize,
+    /// The number placed (1-9)
+    pub value: u8,
+    /// The hardest technique needed to reach this placement
+    pub technique: SolvingTechnique,
+    /// The technique's difficulty rank (see [`SolvingTechnique::rank`])
+    pub rank: u8,
+    /// Average candidates per empty cell just before the placement
+    pub branching_factor: f64,
+    /// For hidden singles, the unit where the number had one spot left
+    pub unit: Option<(UnitKind, usize)>,
+}
+
+/// Where logical solving ran out of moves
+#[derive(Debug, Clone, PartialEq)]
+pub struct StuckPoint {
+    /// The board after every implemented technique was exhausted
+    pub board: Vec<Option<u8>>,
+    /// Cells still empty, in index order
+    pub empty_cells: Vec<usize>,
+    /// The most constrained empty cell and its candidates, where a guess
+    /// would be made; `None` if logic solved the puzzle
+    pub branch: Option<(usize, Vec<u8>)>,
+}
+
+/// The point in a solve where a harder technique is first needed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Milestone {
+    /// Fraction of the initially empty cells already filled (0.0-1.0)
+    pub fraction_solved: f64,
+    /// The technique that becomes necessary at this point
+    pub technique: SolvingTechnique,
+}
+
+/// A basic fish: a number confined to as many cover lines as base lines
+///
+/// Size 2 is an X-Wing, 3 a Swordfish and 4 a Jellyfish.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FishPattern {
+    /// [`UnitKind::Row`] or [`UnitKind::Column`]; cover lines are the other kind
+    pub base_kind: UnitKind,
+    /// The lines whose every candidate position lies in a cover line (0-8)
+    pub base_lines: Vec<usize>,
+    /// The lines the number is confined to within the base lines (0-8)
+    pub cover_lines: Vec<usize>,
+    /// Cells in the cover lines, outside the base lines, that lose the number
+    pub eliminations: Vec<usize>,
+}
+
+/// How one cell of a solution was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellAnnotation {
+    /// The cell (0-80)
+    pub index: usize,
+    /// The number in the solution (1-9)
+    pub value: u8,
+    /// The technique that placed it, or [`SolvingTechnique::TrialAndError`]
+    /// if logic stalled and backtracking filled it
+    pub technique: SolvingTechnique,
+}
 
 /// A human-style Sudoku solver that tracks which techniques are used
 ///
 /// This solver attempts to solve puzzles using logical techniques that
 /// humans would use, rather than brute-force backtracking. It maintains
 /// a candidate grid and applies techniques in order of increasing difficulty.
+#[derive(Clone)]
 pub struct HumanStyleSolver {
     /// The current state of the board
     board: Vec<Option<u8>>,
@@ -18,6 +146,22 @@ pub struct HumanStyleSolver {
     candidates: CandidateGrid,
     /// List of techniques that were used during solving
     techniques_used: Vec<SolvingTechnique>,
+    /// Hardest elimination technique applied since the last placement
+    pending_technique: Option<SolvingTechnique>,
+    /// Placements in order, when tracing is enabled
+    trace: Option<Vec<SolveStep>>,
+    /// Number of elimination passes that removed candidates
+    elimination_count: u32,
+    /// Applications of each technique, indexed by rank - 1
+    technique_counts: [u32; SolvingTechnique::ALL.len()],
+    /// Hardest technique the solver may apply, if limited
+    technique_limit: Option<SolvingTechnique>,
+    /// Techniques the solver must not apply
+    disabled_techniques: TechniqueSet,
+    /// Sum of the branching factors sampled after each successful pass
+    branching_total: f64,
+    /// Number of branching factor samples taken
+    branching_samples: u32,
 }
 
 impl HumanStyleSolver {
@@ -33,11 +177,57 @@ impl HumanStyleSolver {
             board: board.to_vec(),
             candidates: CandidateGrid::new(),
             techniques_used: Vec::new(),
+            pending_technique: None,
+            trace: None,
+            elimination_count: 0,
+            technique_counts: [0; SolvingTechnique::ALL.len()],
+            technique_limit: None,
+            disabled_techniques: TechniqueSet::new(),
+            branching_total: 0.0,
+            branching_samples: 0,
         };
         solver.initialize_candidates();
         solver
     }
 
+    /// Enables recording of every placement made while solving
+    ///
+    /// # Returns
+    /// The solver with an empty trace attached
+    pub fn with_trace(mut self) -> Self {
+        self.trace = Some(Vec::new());
+        self
+    }
+
+    /// Restricts solving to techniques no harder than `limit`
+    ///
+    /// Naked singles are always allowed, as every solve depends on them.
+    ///
+    /// # Arguments
+    /// * `limit` - The hardest technique the solver may apply
+    ///
+    /// # Returns
+    /// The solver with the limit set
+    pub fn with_technique_limit(mut self, limit: SolvingTechnique) -> Self {
+        self.technique_limit = Some(limit);
+        self
+    }
+
+    /// Keeps the solver from applying the given techniques
+    ///
+    /// Unlike [`Self::with_technique_limit`], harder techniques stay
+    /// available. Naked singles can't be disabled.
+    ///
+    /// # Arguments
+    /// * `disabled` - The techniques to skip
+    ///
+    /// # Returns
+    /// The solver with the techniques disabled
+    pub fn without_techniques(mut self, disabled: TechniqueSet) -> Self {
+        self.disabled_techniques = disabled;
+        self
+    }
+
     /// Initializes the candidate grid based on the given clues
     ///
     /// For each filled cell, removes that number as a candidate from
@@ -56,39 +246,64 @@ impl HumanStyleSolver {
     /// * `index` - The cell index where to place the number
     /// * `num` - The number to place (1-9)
     fn place_number(&mut self, index: usize, num: u8) {
-        let (row, col) = index_to_coords(index);
-
         // Set only this candidate for the cell
         self.candidates.set_only_candidate(index, num);
 
         // Remove this number from row, column, and box candidates
-        self.eliminate_candidates_in_units(row, col, num);
+        self.eliminate_candidates_in_units(index, num);
     }
 
-    /// Eliminates a number as candidate from all cells in the same units
+    /// Fills a cell as the result of a solving technique
     ///
     /// # Arguments
-    /// * `row` - The row of the placed number
-    /// * `col` - The column of the placed number  
-    /// * `num` - The number that was placed
-    fn eliminate_candidates_in_units(&mut self, row: usize, col: usize, num: u8) {
-        // Remove from row and column
-        for i in 0..GRID_SIZE {
-            let row_idx = coords_to_index(row, i);
-            let col_idx = coords_to_index(i, col);
-            self.candidates.remove_candidate(row_idx, num);
-            self.candidates.remove_candidate(col_idx, num);
-        }
+    /// * `index` - The cell index to fill
+    /// * `num` - The number to place (1-9)
+    /// * `technique` - The technique that found the placement
+    /// * `unit` - For hidden singles, the unit that forced the placement
+    fn fill_cell(
+        &mut self,
+        index: usize,
+        num: u8,
+        technique: SolvingTechnique,
+        unit: Option<(UnitKind, usize)>,
+    ) {
+        self.technique_counts[technique.rank() as usize - 1] += 1;
+        let technique = self
+            .pending_technique
+            .take()
+            .map_or(technique.clone(), |pending| pending.max(technique));
 
-        // Remove from 3x3 box
-        let box_start_row = (row / BOX_SIZE) * BOX_SIZE;
-        let box_start_col = (col / BOX_SIZE) * BOX_SIZE;
-        for r in box_start_row..box_start_row + BOX_SIZE {
-            for c in box_start_col..box_start_col + BOX_SIZE {
-                let box_idx = coords_to_index(r, c);
-                self.candidates.remove_candidate(box_idx, num);
+        if self.trace.is_some() {
+            let step = SolveStep {
+                index,
+                value: num,
+                rank: technique.rank(),
+                technique,
+                branching_factor: self.calculate_branching_factor(),
+                unit,
+            };
+            if let Some(trace) = self.trace.as_mut() {
+                trace.push(step);
             }
         }
+
+        self.board[index] = Some(num);
+        self.place_number(index, num);
+    }
+
+    /// Eliminates a number as candidate from all cells in the same units
+    ///
+    /// The placed cell itself loses the candidate too, as it sits in all
+    /// three units.
+    ///
+    /// # Arguments
+    /// * `index` - The cell of the placed number
+    /// * `num` - The number that was placed
+    fn eliminate_candidates_in_units(&mut self, index: usize, num: u8) {
+        self.candidates.remove_candidate(index, num);
+        for &peer in peers(index) {
+            self.candidates.remove_candidate(peer, num);
+        }
     }
 
     /// Attempts to solve the puzzle using human-style techniques
@@ -103,13 +318,12 @@ impl HumanStyleSolver {
             let initial_board = self.board.clone();
 
             // Apply techniques in order of increasing complexity
-            if self.apply_basic_techniques() {
-                continue;
-            }
-            if self.apply_intermediate_techniques() {
-                continue;
-            }
-            if self.apply_advanced_techniques() {
+            if self.apply_basic_techniques()
+                || self.apply_intermediate_techniques()
+                || self.apply_advanced_techniques()
+            {
+                self.branching_total += self.calculate_branching_factor();
+                self.branching_samples += 1;
                 continue;
             }
 
@@ -127,7 +341,8 @@ impl HumanStyleSolver {
     /// # Returns
     /// `true` if any progress was made
     pub fn apply_basic_techniques(&mut self) -> bool {
-        self.find_naked_singles() || self.find_hidden_singles()
+        self.find_naked_singles()
+            || self.apply_if_allowed(SolvingTechnique::HiddenSingle, Self::find_hidden_singles)
     }
 
     /// Applies intermediate solving techniques
@@ -137,8 +352,11 @@ impl HumanStyleSolver {
     fn apply_intermediate_techniques(&mut self) -> bool {
         self.find_naked_pairs()
             || self.find_hidden_pairs()
-            || self.find_box_line_reduction()
-            || self.find_pointing_pairs()
+            || self.apply_if_allowed(
+                SolvingTechnique::BoxLineReduction,
+                Self::find_box_line_reduction,
+            )
+            || self.apply_if_allowed(SolvingTechnique::PointingPairs, Self::find_pointing_pairs)
     }
 
     /// Applies advanced solving techniques
@@ -146,10 +364,41 @@ impl HumanStyleSolver {
     /// # Returns
     /// `true` if any progress was made
     fn apply_advanced_techniques(&mut self) -> bool {
-        self.find_x_wing()
-            || self.find_pointing_triples()
-            || self.find_swordfish()
+        self.apply_if_allowed(SolvingTechnique::XWing, Self::find_x_wing)
+            || self.apply_if_allowed(
+                SolvingTechnique::PointingTriples,
+                Self::find_pointing_triples,
+            )
+            || self.apply_if_allowed(SolvingTechnique::EmptyRectangle, Self::find_empty_rectangle)
+            || self.apply_if_allowed(SolvingTechnique::FinnedXWing, Self::find_finned_x_wing)
+            || self.apply_if_allowed(SolvingTechnique::RemotePairs, Self::find_remote_pairs)
+            || self.apply_if_allowed(SolvingTechnique::Swordfish, Self::find_swordfish)
             || self.find_xy_wing()
+            || self.apply_if_allowed(
+                SolvingTechnique::AlmostLockedSet,
+                Self::find_almost_locked_sets,
+            )
+    }
+
+    /// Runs one technique unless the technique limit or disabled set excludes it
+    ///
+    /// # Arguments
+    /// * `technique` - The technique `find` applies
+    /// * `find` - The method that looks for and applies it
+    ///
+    /// # Returns
+    /// `true` if the technique was allowed and made progress
+    fn apply_if_allowed(
+        &mut self,
+        technique: SolvingTechnique,
+        find: fn(&mut Self) -> bool,
+    ) -> bool {
+        let allowed = self
+            .technique_limit
+            .as_ref()
+            .is_none_or(|limit| technique <= *limit)
+            && !self.disabled_techniques.contains(&technique);
+        allowed && find(self)
     }
 
     /// Finds naked singles - cells with only one possible candidate
@@ -166,8 +415,7 @@ impl HumanStyleSolver {
             if self.board[index].is_none() && self.candidates.candidate_count(index) == 1 {
                 let candidates = self.candidates.get_candidates(index);
                 if let Some(&num) = candidates.first() {
-                    self.board[index] = Some(num);
-                    self.place_number(index, num);
+                    self.fill_cell(index, num, SolvingTechnique::NakedSingle, None);
                     self.record_technique_used(SolvingTechnique::NakedSingle);
                     progress = true;
                 }
@@ -201,94 +449,126 @@ impl HumanStyleSolver {
 
     /// Finds hidden singles in all rows
     fn find_hidden_singles_in_rows(&mut self) -> bool {
+        self.find_hidden_singles_in(UnitKind::Row, &units()[..GRID_SIZE])
+    }
+
+    /// Finds hidden singles in all columns
+    fn find_hidden_singles_in_columns(&mut self) -> bool {
+        self.find_hidden_singles_in(UnitKind::Column, &units()[GRID_SIZE..2 * GRID_SIZE])
+    }
+
+    /// Finds hidden singles in all 3x3 boxes
+    fn find_hidden_singles_in_boxes(&mut self) -> bool {
+        self.find_hidden_singles_in(UnitKind::Box, &units()[2 * GRID_SIZE..])
+    }
+
+    /// Fills every number that has one spot left in one of the given units
+    ///
+    /// # Arguments
+    /// * `kind` - The kind of the units, recorded on each placement
+    /// * `units` - The units to scan, in order
+    ///
+    /// # Returns
+    /// `true` if any hidden singles were found and filled
+    fn find_hidden_singles_in(&mut self, kind: UnitKind, units: &[[usize; GRID_SIZE]]) -> bool {
         let mut progress = false;
-        
-        for row in 0..GRID_SIZE {
-            for num in 1..=9 {
-                let possible_positions: Vec<usize> = (0..GRID_SIZE)
-                    .map(|col| coords_to_index(row, col))
-                    .filter(|&index| {
-                        self.board[index].is_none() && self.candidates.has_candidate(index, num)
-                    })
-                    .collect();
 
-                if possible_positions.len() == 1 {
-                    let index = possible_positions[0];
-                    self.board[index] = Some(num);
-                    self.place_number(index, num);
+        for (unit_index, unit) in units.iter().enumerate() {
+            for num in 1..=9 {
+                let mut positions = unit.iter().filter(|&&index| {
+                    self.board[index].is_none() && self.candidates.has_candidate(index, num)
+                });
+                if let (Some(&index), None) = (positions.next(), positions.next()) {
+                    let unit = Some((kind, unit_index));
+                    self.fill_cell(index, num, SolvingTechnique::HiddenSingle, unit);
                     progress = true;
                 }
             }
         }
-        
+
         progress
     }
 
-    /// Finds hidden singles in all columns
-    fn find_hidden_singles_in_columns(&mut self) -> bool {
+    /// Records that a technique was used (avoiding duplicates)
+    ///
+    /// Techniques beyond singles only eliminate candidates, so they are also
+    /// held as pending and credited to the next placement.
+    fn record_technique_used(&mut self, technique: SolvingTechnique) {
+        if technique > SolvingTechnique::HiddenSingle {
+            self.elimination_count += 1;
+            self.technique_counts[technique.rank() as usize - 1] += 1;
+            if self.pending_technique.as_ref().is_none_or(|pending| technique > *pending) {
+                self.pending_technique = Some(technique.clone());
+            }
+        }
+        if !self.techniques_used.contains(&technique) {
+            self.techniques_used.push(technique);
+        }
+    }
+
+    /// Removes candidates using box and line intersections
+    ///
+    /// # Arguments
+    /// * `positions` - How many cells the confined number must occupy
+    /// * `line_to_box` - `true` to look for numbers confined to one box within
+    ///   a line and eliminate in the box; `false` to look for numbers confined
+    ///   to one line within a box and eliminate along the line
+    ///
+    /// # Returns
+    /// `true` if any candidate was removed
+    fn eliminate_intersections(&mut self, positions: usize, line_to_box: bool) -> bool {
         let mut progress = false;
-        
-        for col in 0..GRID_SIZE {
+        // Units are ordered rows, columns, then boxes
+        let units = units();
+        let sources = if line_to_box {
+            &units[..2 * GRID_SIZE]
+        } else {
+            &units[2 * GRID_SIZE..]
+        };
+
+        for unit in sources {
             for num in 1..=9 {
-                let possible_positions: Vec<usize> = (0..GRID_SIZE)
-                    .map(|row| coords_to_index(row, col))
+                let cells: Vec<usize> = unit
+                    .iter()
+                    .copied()
                     .filter(|&index| {
                         self.board[index].is_none() && self.candidates.has_candidate(index, num)
                     })
                     .collect();
-
-                if possible_positions.len() == 1 {
-                    let index = possible_positions[0];
-                    self.board[index] = Some(num);
-                    self.place_number(index, num);
-                    progress = true;
+                if cells.len() != positions {
+                    continue;
                 }
-            }
-        }
-        
-        progress
-    }
 
-    /// Finds hidden singles in all 3x3 boxes
-    fn find_hidden_singles_in_boxes(&mut self) -> bool {
-        let mut progress = false;
-        
-        for box_row in 0..3 {
-            for box_col in 0..3 {
-                let start_row = box_row * BOX_SIZE;
-                let start_col = box_col * BOX_SIZE;
-                
-                for num in 1..=9 {
-                    let mut possible_positions = Vec::new();
-                    
-                    for r in start_row..start_row + BOX_SIZE {
-                        for c in start_col..start_col + BOX_SIZE {
-                            let index = coords_to_index(r, c);
-                            if self.board[index].is_none() 
-                                && self.candidates.has_candidate(index, num) {
-                                possible_positions.push(index);
-                            }
-                        }
+                let coords: Vec<(usize, usize)> =
+                    cells.iter().map(|&index| index_to_coords(index)).collect();
+                let targets = if line_to_box {
+                    let (row, col) = coords[0];
+                    let box_index = get_box_index(row, col);
+                    if coords.iter().any(|&(r, c)| get_box_index(r, c) != box_index) {
+                        continue;
                     }
+                    box_cells(box_index)
+                } else if coords.iter().all(|&(r, _)| r == coords[0].0) {
+                    row_cells(coords[0].0)
+                } else if coords.iter().all(|&(_, c)| c == coords[0].1) {
+                    column_cells(coords[0].1)
+                } else {
+                    continue;
+                };
 
-                    if possible_positions.len() == 1 {
-                        let index = possible_positions[0];
-                        self.board[index] = Some(num);
-                        self.place_number(index, num);
+                for &index in targets {
+                    if !unit.contains(&index)
+                        && self.board[index].is_none()
+                        && self.candidates.has_candidate(index, num)
+                    {
+                        self.candidates.remove_candidate(index, num);
                         progress = true;
                     }
                 }
             }
         }
-        
-        progress
-    }
 
-    /// Records that a technique was used (avoiding duplicates)
-    fn record_technique_used(&mut self, technique: SolvingTechnique) {
-        if !self.techniques_used.contains(&technique) {
-            self.techniques_used.push(technique);
-        }
+        progress
     }
 
     // Placeholder implementations for more advanced techniques
@@ -308,108 +588,1334 @@ impl HumanStyleSolver {
     }
 
     /// Finds box-line reduction patterns
+    ///
+    /// When a number's candidates within a box sit in exactly two cells of
+    /// one row or column, it can be removed from the rest of that line.
+    /// The reverse direction is [`Self::find_pointing_pairs`]; each records
+    /// only its own technique.
     fn find_box_line_reduction(&mut self) -> bool {
-        // Implementation would go here
-        false
+        let progress = self.eliminate_intersections(2, false);
+        if progress {
+            self.record_technique_used(SolvingTechnique::BoxLineReduction);
+        }
+        progress
     }
 
     /// Finds pointing pairs patterns
+    ///
+    /// When a number's candidates within a row or column sit in exactly two
+    /// cells of one box, it can be removed from the rest of that box.
     fn find_pointing_pairs(&mut self) -> bool {
-        // Implementation would go here
-        false
+        let progress = self.eliminate_intersections(2, true);
+        if progress {
+            self.record_technique_used(SolvingTechnique::PointingPairs);
+        }
+        progress
     }
 
     /// Finds X-Wing patterns
+    ///
+    /// When a number's candidates in two rows sit in the same two columns,
+    /// it can be removed from the rest of those columns; likewise with rows
+    /// and columns swapped.
     fn find_x_wing(&mut self) -> bool {
-        // Implementation would go here
-        false
+        self.apply_fish(2, SolvingTechnique::XWing)
     }
 
     /// Finds pointing triples patterns
+    ///
+    /// The three-cell versions of pointing pairs and box-line reduction.
+    /// Only recorded when candidates are actually removed, so puzzles that
+    /// pairs already handle aren't rated higher.
     fn find_pointing_triples(&mut self) -> bool {
-        // Implementation would go here
-        false
+        let line_to_box = self.eliminate_intersections(3, true);
+        let box_to_line = self.eliminate_intersections(3, false);
+        let progress = line_to_box || box_to_line;
+        if progress {
+            self.record_technique_used(SolvingTechnique::PointingTriples);
+        }
+        progress
     }
 
-    /// Finds Swordfish patterns
-    fn find_swordfish(&mut self) -> bool {
-        // Implementation would go here
-        false
-    }
+    /// Finds empty rectangle patterns
+    ///
+    /// Applies the eliminations from [`Self::empty_rectangle_eliminations`].
+    fn find_empty_rectangle(&mut self) -> bool {
+        let eliminations = self.empty_rectangle_eliminations();
+        for &(index, num) in &eliminations {
+            self.candidates.remove_candidate(index, num);
+        }
 
-    /// Finds XY-Wing patterns
-    fn find_xy_wing(&mut self) -> bool {
-        // Implementation would go here
-        false
+        let progress = !eliminations.is_empty();
+        if progress {
+            self.record_technique_used(SolvingTechnique::EmptyRectangle);
+        }
+        progress
     }
 
-    /// Checks if the puzzle is completely solved
+    /// Collects the candidates empty rectangles rule out
+    ///
+    /// A box holding a number only on the cross of one row and one column
+    /// (with cells off each arm) forms an empty rectangle. If a column
+    /// outside the box has a conjugate pair with one end on the cross row,
+    /// the cell where the pair's other end meets the cross column can't
+    /// hold the number; likewise with rows and columns swapped.
     ///
     /// # Returns
-    /// `true` if all cells are filled
-    pub fn is_solved(&self) -> bool {
-        self.board.iter().all(|&cell| cell.is_some())
+    /// `(index, number)` for each candidate to remove, in index order
+    fn empty_rectangle_eliminations(&self) -> Vec<(usize, u8)> {
+        let mut eliminations = Vec::new();
+
+        for num in 1..=9 {
+            let has = |index: usize| {
+                self.board[index].is_none() && self.candidates.has_candidate(index, num)
+            };
+
+            for box_index in 0..GRID_SIZE {
+                let (band, stack) = index_to_coords(box_cells(box_index)[0]);
+                let cells: Vec<(usize, usize)> = box_cells(box_index)
+                    .iter()
+                    .copied()
+                    .filter(|&index| has(index))
+                    .map(index_to_coords)
+                    .collect();
+                if cells.len() < 2 {
+                    continue;
+                }
+
+                for row in band..band + BOX_SIZE {
+                    for col in stack..stack + BOX_SIZE {
+                        let on_cross = cells.iter().all(|&(r, c)| r == row || c == col);
+                        let both_arms = cells.iter().any(|&(r, _)| r != row)
+                            && cells.iter().any(|&(_, c)| c != col);
+                        if !on_cross || !both_arms {
+                            continue;
+                        }
+
+                        // Conjugate pair in a column, one end on the cross row
+                        for pair_col in
+                            (0..GRID_SIZE).filter(|&c| c / BOX_SIZE != col / BOX_SIZE)
+                        {
+                            let ends: Vec<usize> = (0..GRID_SIZE)
+                                .filter(|&r| has(coords_to_index(r, pair_col)))
+                                .collect();
+                            let other = match ends[..] {
+                                [a, b] if a == row => b,
+                                [a, b] if b == row => a,
+                                _ => continue,
+                            };
+                            let target = coords_to_index(other, col);
+                            if other / BOX_SIZE != row / BOX_SIZE && has(target) {
+                                eliminations.push((target, num));
+                            }
+                        }
+
+                        // Conjugate pair in a row, one end on the cross column
+                        for pair_row in
+                            (0..GRID_SIZE).filter(|&r| r / BOX_SIZE != row / BOX_SIZE)
+                        {
+                            let ends: Vec<usize> = (0..GRID_SIZE)
+                                .filter(|&c| has(coords_to_index(pair_row, c)))
+                                .collect();
+                            let other = match ends[..] {
+                                [a, b] if a == col => b,
+                                [a, b] if b == col => a,
+                                _ => continue,
+                            };
+                            let target = coords_to_index(row, other);
+                            if other / BOX_SIZE != col / BOX_SIZE && has(target) {
+                                eliminations.push((target, num));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        eliminations.sort_unstable();
+        eliminations.dedup();
+        eliminations
     }
 
-    /// Calculates the branching factor (average candidates per empty cell)
+    /// Finds remote pair chains
     ///
-    /// This metric indicates puzzle complexity - higher values mean more
-    /// choices and potentially more difficult puzzles.
+    /// Applies the eliminations from [`Self::remote_pair_eliminations`].
+    fn find_remote_pairs(&mut self) -> bool {
+        let eliminations = self.remote_pair_eliminations();
+        for &(index, num) in &eliminations {
+            self.candidates.remove_candidate(index, num);
+        }
+
+        let progress = !eliminations.is_empty();
+        if progress {
+            self.record_technique_used(SolvingTechnique::RemotePairs);
+        }
+        progress
+    }
+
+    /// Collects the candidates remote pair chains rule out
+    ///
+    /// Cells holding the same two candidates that see each other must hold
+    /// different numbers, so a chain of them alternates between the two.
+    /// Two cells an odd number of links apart hold one number each, and any
+    /// cell seeing both loses both numbers. Chains shorter than three links
+    /// are left to naked pairs.
     ///
     /// # Returns
-    /// The average number of candidates per empty cell
-    pub fn calculate_branching_factor(&self) -> f64 {
-        let empty_cells: Vec<_> = self
-            .board
-            .iter()
-            .enumerate()
-            .filter(|(_, &cell)| cell.is_none())
+    /// `(index, number)` for each candidate to remove, in index order
+    fn remote_pair_eliminations(&self) -> Vec<(usize, u8)> {
+        let mut eliminations = Vec::new();
+        let empty = |index: &usize| self.board[*index].is_none();
+
+        let mut pairs: Vec<u16> = (0..BOARD_SIZE)
+            .filter(empty)
+            .map(|index| self.candidates.get_mask(index))
+            .filter(|mask| mask.count_ones() == 2)
             .collect();
+        pairs.sort_unstable();
+        pairs.dedup();
 
-        if empty_cells.is_empty() {
-            return 1.0;
-        }
+        for pair in pairs {
+            let cells: Vec<usize> = (0..BOARD_SIZE)
+                .filter(|index| empty(index) && self.candidates.get_mask(*index) == pair)
+                .collect();
+            let digits: Vec<u8> = (1..=9).filter(|&num| pair & (1 << (num - 1)) != 0).collect();
 
-        let total_candidates: usize = empty_cells
-            .iter()
-            .map(|(index, _)| self.candidates.candidate_count(*index))
-            .sum();
+            // Unlinked groups of cells are separate chains
+            let mut components: Vec<Vec<usize>> = Vec::new();
+            let mut seen = vec![false; cells.len()];
+            for start in 0..cells.len() {
+                if seen[start] {
+                    continue;
+                }
+                seen[start] = true;
+                let mut component = vec![cells[start]];
+                let mut at = 0;
+                while at < component.len() {
+                    for next in 0..cells.len() {
+                        if !seen[next] && peers(component[at]).contains(&cells[next]) {
+                            seen[next] = true;
+                            component.push(cells[next]);
+                        }
+                    }
+                    at += 1;
+                }
+                components.push(component);
+            }
 
-        total_candidates as f64 / empty_cells.len() as f64
+            for chain in components.into_iter().filter(|chain| chain.len() >= 4) {
+                eliminations.extend(self.remote_chain_eliminations(&chain, &digits));
+            }
+        }
+
+        eliminations.sort_unstable();
+        eliminations.dedup();
+        eliminations
     }
 
-    /// Gets the hardest technique that was used during solving
+    /// Eliminations from one connected chain of cells sharing a pair
+    ///
+    /// # Arguments
+    /// * `chain` - Linked cells holding only the pair
+    /// * `digits` - The two numbers of the pair
     ///
     /// # Returns
-    /// The most advanced technique from the solving process
-    pub fn get_hardest_technique_used(&self) -> SolvingTechnique {
-        self.techniques_used
-            .iter()
-            .max()
-            .cloned()
-            .unwrap_or(SolvingTechnique::NakedSingle)
+    /// `(index, number)` for each candidate to remove, unsorted
+    fn remote_chain_eliminations(&self, chain: &[usize], digits: &[u8]) -> Vec<(usize, u8)> {
+        let mut eliminations = Vec::new();
+
+        // Links between chain cells, by position in `chain`
+        let distances: Vec<Vec<Option<usize>>> = (0..chain.len())
+            .map(|start| {
+                let mut distance = vec![None; chain.len()];
+                distance[start] = Some(0);
+                let mut queue = std::collections::VecDeque::from([start]);
+                while let Some(at) = queue.pop_front() {
+                    for next in 0..chain.len() {
+                        if distance[next].is_none() && peers(chain[at]).contains(&chain[next]) {
+                            distance[next] = distance[at].map(|d| d + 1);
+                            queue.push_back(next);
+                        }
+                    }
+                }
+                distance
+            })
+            .collect();
+
+        // Linked cells must alternate, which an odd loop would contradict
+        let parity = |from: usize, to: usize| distances[from][to].map(|d| d % 2);
+        let alternates = (0..chain.len()).all(|a| {
+            (0..chain.len()).all(|b| {
+                !peers(chain[a]).contains(&chain[b])
+                    || (0..chain.len()).all(|c| parity(c, a) != parity(c, b))
+            })
+        });
+        if !alternates {
+            return eliminations;
+        }
+
+        for a in 0..chain.len() {
+            for b in a + 1..chain.len() {
+                if !distances[a][b].is_some_and(|d| d >= 3 && d % 2 == 1) {
+                    continue;
+                }
+                for &target in peers(chain[a]) {
+                    if chain.contains(&target)
+                        || self.board[target].is_some()
+                        || !peers(chain[b]).contains(&target)
+                    {
+                        continue;
+                    }
+                    for &num in digits {
+                        if self.candidates.has_candidate(target, num) {
+                            eliminations.push((target, num));
+                        }
+                    }
+                }
+            }
+        }
+        eliminations
     }
 
-    /// Gets all techniques that were used during solving
+    /// Finds almost locked set pairs (ALS-XZ)
     ///
-    /// # Returns
-    /// Reference to the list of techniques used
-    pub fn get_techniques_used(&self) -> &[SolvingTechnique] {
-        &self.techniques_used
+    /// Applies the eliminations from [`Self::als_xz_eliminations`].
+    fn find_almost_locked_sets(&mut self) -> bool {
+        let eliminations = self.als_xz_eliminations();
+        for &(index, num) in &eliminations {
+            self.candidates.remove_candidate(index, num);
+        }
+
+        let progress = !eliminations.is_empty();
+        if progress {
+            self.record_technique_used(SolvingTechnique::AlmostLockedSet);
+        }
+        progress
     }
 
-    /// Gets the current board state
+    /// Lists the almost locked sets of up to [`MAX_ALS_SIZE`] cells
+    ///
+    /// An almost locked set is `n` empty cells of one unit holding `n + 1`
+    /// candidates between them; a two-candidate cell is the smallest.
     ///
     /// # Returns
-    /// Reference to the current board
-    pub fn get_board(&self) -> &[Option<u8>] {
+    /// `(cells, candidate mask)` for each set, cells in ascending order
+    fn almost_locked_sets(&self) -> Vec<(Vec<usize>, u16)> {
+        let mut sets = Vec::new();
+
+        for unit in units() {
+            let empty: Vec<usize> = unit
+                .iter()
+                .copied()
+                .filter(|&index| self.board[index].is_none())
+                .collect();
+            for subset in 1u32..1 << empty.len() {
+                let size = subset.count_ones() as usize;
+                if size > MAX_ALS_SIZE {
+                    continue;
+                }
+                let cells: Vec<usize> = (0..empty.len())
+                    .filter(|&bit| subset & (1 << bit) != 0)
+                    .map(|bit| empty[bit])
+                    .collect();
+                let mask = cells
+                    .iter()
+                    .fold(0, |mask, &index| mask | self.candidates.get_mask(index));
+                if mask.count_ones() as usize == size + 1 {
+                    sets.push((cells, mask));
+                }
+            }
+        }
+
+        // Cells sharing a line and a box are found from both units
+        sets.sort_unstable();
+        sets.dedup();
+        sets
+    }
+
+    /// Collects the candidates ALS-XZ rules out
+    ///
+    /// Two disjoint almost locked sets share a restricted common candidate
+    /// X when every X cell of one sees every X cell of the other, so X sits
+    /// in at most one of them and the other becomes locked. For any other
+    /// candidate Z they share, one set must then hold Z, and a cell seeing
+    /// every Z cell of both can't.
+    ///
+    /// # Returns
+    /// `(index, number)` for each candidate to remove, in index order
+    fn als_xz_eliminations(&self) -> Vec<(usize, u8)> {
+        let mut eliminations = Vec::new();
+        let sets = self.almost_locked_sets();
+        let sees = |a: usize, b: usize| peers(a).contains(&b);
+
+        for (i, (cells_a, mask_a)) in sets.iter().enumerate() {
+            for (cells_b, mask_b) in &sets[i + 1..] {
+                let common = mask_a & mask_b;
+                if common.count_ones() < 2 || cells_a.iter().any(|cell| cells_b.contains(cell)) {
+                    continue;
+                }
+                let holding = |num: u8| -> Vec<usize> {
+                    cells_a
+                        .iter()
+                        .chain(cells_b)
+                        .copied()
+                        .filter(|&index| self.candidates.has_candidate(index, num))
+                        .collect()
+                };
+                let digits: Vec<u8> = (1..=9)
+                    .filter(|&num| common & (1 << (num - 1)) != 0)
+                    .collect();
+
+                for &x in &digits {
+                    let restricted = cells_a
+                        .iter()
+                        .filter(|&&a| self.candidates.has_candidate(a, x))
+                        .all(|&a| {
+                            cells_b
+                                .iter()
+                                .filter(|&&b| self.candidates.has_candidate(b, x))
+                                .all(|&b| sees(a, b))
+                        });
+                    if !restricted {
+                        continue;
+                    }
+
+                    for &z in digits.iter().filter(|&&z| z != x) {
+                        let z_cells = holding(z);
+                        for &target in peers(z_cells[0]) {
+                            if self.board[target].is_none()
+                                && self.candidates.has_candidate(target, z)
+                                && !z_cells.contains(&target)
+                                && z_cells.iter().all(|&cell| sees(cell, target))
+                            {
+                                eliminations.push((target, z));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        eliminations.sort_unstable();
+        eliminations.dedup();
+        eliminations
+    }
+
+    /// Finds finned and sashimi X-Wing patterns
+    ///
+    /// One base line holds the number in exactly two cells. The other base
+    /// line holds it in at least one of the same two cover lines plus a fin
+    /// confined to one box. Either the fin or the X-Wing is true, so the
+    /// number can be removed from cover-line cells inside the fin's box,
+    /// outside the base lines.
+    fn find_finned_x_wing(&mut self) -> bool {
+        let mut progress = false;
+
+        for by_rows in [true, false] {
+            // Base lines are rows and cover lines columns, or the reverse
+            let cell = |line: usize, pos: usize| {
+                if by_rows {
+                    coords_to_index(line, pos)
+                } else {
+                    coords_to_index(pos, line)
+                }
+            };
+
+            for num in 1..=9 {
+                let positions: Vec<Vec<usize>> = (0..GRID_SIZE)
+                    .map(|line| {
+                        (0..GRID_SIZE)
+                            .filter(|&pos| {
+                                let index = cell(line, pos);
+                                self.board[index].is_none()
+                                    && self.candidates.has_candidate(index, num)
+                            })
+                            .collect()
+                    })
+                    .collect();
+
+                for clean in 0..GRID_SIZE {
+                    let [first, second] = positions[clean][..] else {
+                        continue;
+                    };
+
+                    for finned in (0..GRID_SIZE).filter(|&line| line != clean) {
+                        let (corners, fins): (Vec<usize>, Vec<usize>) = positions[finned]
+                            .iter()
+                            .partition(|&&pos| pos == first || pos == second);
+                        if corners.is_empty() || fins.is_empty() {
+                            continue;
+                        }
+                        let stack = fins[0] / BOX_SIZE;
+                        if fins.iter().any(|&pos| pos / BOX_SIZE != stack) {
+                            continue;
+                        }
+
+                        let band = finned / BOX_SIZE * BOX_SIZE;
+                        for cover in [first, second] {
+                            if cover / BOX_SIZE != stack {
+                                continue;
+                            }
+                            for line in band..band + BOX_SIZE {
+                                let index = cell(line, cover);
+                                if line != finned
+                                    && line != clean
+                                    && self.board[index].is_none()
+                                    && self.candidates.has_candidate(index, num)
+                                {
+                                    self.candidates.remove_candidate(index, num);
+                                    progress = true;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if progress {
+            self.record_technique_used(SolvingTechnique::FinnedXWing);
+        }
+        progress
+    }
+
+    /// Removes the candidates ruled out by the first productive fish of a size
+    ///
+    /// # Arguments
+    /// * `size` - How many base lines the fish spans
+    /// * `technique` - The technique to record if a fish is found
+    ///
+    /// # Returns
+    /// `true` if a fish removed candidates
+    fn apply_fish(&mut self, size: usize, technique: SolvingTechnique) -> bool {
+        let Some((num, fish)) = (1..=9).find_map(|num| Some((num, self.fish_pattern(num, size)?)))
+        else {
+            return false;
+        };
+        for &index in &fish.eliminations {
+            self.candidates.remove_candidate(index, num);
+        }
+        self.record_technique_used(technique);
+        true
+    }
+
+    /// Looks for a basic fish of one size without applying it
+    ///
+    /// Rows are tried as base lines before columns. Only fish that remove
+    /// at least one candidate count.
+    ///
+    /// # Arguments
+    /// * `num` - The number the fish is made of (1-9)
+    /// * `size` - How many base lines the fish spans
+    ///
+    /// # Returns
+    /// The first productive fish, if any
+    fn fish_pattern(&self, num: u8, size: usize) -> Option<FishPattern> {
+        for base_kind in [UnitKind::Row, UnitKind::Column] {
+            let cell = |line: usize, pos: usize| match base_kind {
+                UnitKind::Row => coords_to_index(line, pos),
+                _ => coords_to_index(pos, line),
+            };
+            let has = |index: usize| {
+                self.board[index].is_none() && self.candidates.has_candidate(index, num)
+            };
+            // Candidate positions of each line as bit flags
+            let positions: Vec<u16> = (0..GRID_SIZE)
+                .map(|line| {
+                    (0..GRID_SIZE)
+                        .filter(|&pos| has(cell(line, pos)))
+                        .fold(0, |mask, pos| mask | 1 << pos)
+                })
+                .collect();
+
+            for lines in 0u16..1 << GRID_SIZE {
+                if lines.count_ones() as usize != size {
+                    continue;
+                }
+                let base_lines: Vec<usize> = (0..GRID_SIZE)
+                    .filter(|&line| lines >> line & 1 == 1)
+                    .collect();
+                if base_lines
+                    .iter()
+                    .any(|&line| positions[line].count_ones() < 2)
+                {
+                    continue;
+                }
+                let covers = base_lines
+                    .iter()
+                    .fold(0, |mask, &line| mask | positions[line]);
+                if covers.count_ones() as usize != size {
+                    continue;
+                }
+
+                let cover_lines: Vec<usize> = (0..GRID_SIZE)
+                    .filter(|&pos| covers >> pos & 1 == 1)
+                    .collect();
+                let mut eliminations: Vec<usize> = cover_lines
+                    .iter()
+                    .flat_map(|&pos| {
+                        (0..GRID_SIZE)
+                            .filter(|line| !base_lines.contains(line))
+                            .map(move |line| cell(line, pos))
+                    })
+                    .filter(|&index| has(index))
+                    .collect();
+                if eliminations.is_empty() {
+                    continue;
+                }
+                eliminations.sort_unstable();
+
+                return Some(FishPattern {
+                    base_kind,
+                    base_lines,
+                    cover_lines,
+                    eliminations,
+                });
+            }
+        }
+        None
+    }
+
+    /// Finds Swordfish patterns
+    ///
+    /// The three-line version of [`Self::find_x_wing`]: a number confined
+    /// to the same three columns across three rows is removed from the rest
+    /// of those columns.
+    fn find_swordfish(&mut self) -> bool {
+        self.apply_fish(3, SolvingTechnique::Swordfish)
+    }
+
+    /// Finds XY-Wing patterns
+    fn find_xy_wing(&mut self) -> bool {
+        // Implementation would go here
+        false
+    }
+
+    /// Checks if the puzzle is completely solved
+    ///
+    /// # Returns
+    /// `true` if all cells are filled
+    pub fn is_solved(&self) -> bool {
+        self.board.iter().all(|&cell| cell.is_some())
+    }
+
+    /// Calculates the branching factor (average candidates per empty cell)
+    ///
+    /// This metric indicates puzzle complexity - higher values mean more
+    /// choices and potentially more difficult puzzles.
+    ///
+    /// # Returns
+    /// The average number of candidates per empty cell, or `f64::INFINITY`
+    /// if some empty cell has no candidates left (the board is contradictory)
+    pub fn calculate_branching_factor(&self) -> f64 {
+        if self.has_contradiction() {
+            return f64::INFINITY;
+        }
+
+        let empty_cells: Vec<_> = self
+            .board
+            .iter()
+            .enumerate()
+            .filter(|(_, &cell)| cell.is_none())
+            .collect();
+
+        if empty_cells.is_empty() {
+            return 1.0;
+        }
+
+        let total_candidates: usize = empty_cells
+            .iter()
+            .map(|(index, _)| self.candidates.candidate_count(*index))
+            .sum();
+
+        total_candidates as f64 / empty_cells.len() as f64
+    }
+
+    /// Gets the branching factor averaged over the solve so far
+    ///
+    /// A sample is taken after every pass that made progress, so the value
+    /// shows how quickly the choices narrow rather than only where the
+    /// solve ended.
+    ///
+    /// # Returns
+    /// The mean of the samples, or the current branching factor if no pass
+    /// has made progress yet
+    pub fn get_average_branching_factor(&self) -> f64 {
+        if self.branching_samples == 0 {
+            return self.calculate_branching_factor();
+        }
+        self.branching_total / self.branching_samples as f64
+    }
+
+    /// Gets the hardest technique that was used during solving
+    ///
+    /// # Returns
+    /// The most advanced technique from the solving process
+    pub fn get_hardest_technique_used(&self) -> SolvingTechnique {
+        self.techniques_used
+            .iter()
+            .max()
+            .cloned()
+            .unwrap_or(SolvingTechnique::NakedSingle)
+    }
+
+    /// Gets all techniques that were used during solving
+    ///
+    /// # Returns
+    /// Reference to the list of techniques used
+    pub fn get_techniques_used(&self) -> &[SolvingTechnique] {
+        &self.techniques_used
+    }
+
+    /// Gets the placements recorded so far
+    ///
+    /// # Returns
+    /// The trace, or an empty slice if tracing wasn't enabled
+    pub fn get_trace(&self) -> &[SolveStep] {
+        self.trace.as_deref().unwrap_or(&[])
+    }
+
+    /// Gets how many elimination passes removed candidates so far
+    ///
+    /// # Returns
+    /// The number of successful applications of techniques beyond singles
+    pub fn get_elimination_count(&self) -> u32 {
+        self.elimination_count
+    }
+
+    /// Gets how often each technique was applied so far
+    ///
+    /// Singles count once per placement, other techniques once per
+    /// elimination pass that removed candidates.
+    ///
+    /// # Returns
+    /// `(technique, count)` for every technique used, in difficulty order
+    pub fn get_technique_counts(&self) -> Vec<(SolvingTechnique, u32)> {
+        SolvingTechnique::ALL
+            .into_iter()
+            .zip(self.technique_counts)
+            .filter(|&(_, count)| count > 0)
+            .collect()
+    }
+
+    /// Gets the current board state
+    ///
+    /// # Returns
+    /// Reference to the current board
+    pub fn get_board(&self) -> &[Option<u8>] {
         &self.board
     }
+
+    /// Checks if the current state can no longer lead to a solution
+    ///
+    /// # Returns
+    /// `true` if some empty cell has no candidates left
+    pub fn has_contradiction(&self) -> bool {
+        (0..BOARD_SIZE)
+            .any(|index| self.board[index].is_none() && self.candidates.candidate_count(index) == 0)
+    }
+
+    /// Looks for a naked single without placing it
+    ///
+    /// # Returns
+    /// The first `(index, value)` whose cell has exactly one candidate
+    pub fn peek_naked_single(&self) -> Option<(usize, u8)> {
+        (0..BOARD_SIZE)
+            .find(|&index| self.board[index].is_none() && self.candidates.candidate_count(index) == 1)
+            .map(|index| (index, self.candidates.get_candidates(index)[0]))
+    }
+
+    /// Looks for a hidden single without placing it
+    ///
+    /// # Returns
+    /// The first `(index, value, unit)` where a number has one spot left in a unit
+    fn peek_hidden_single(&self) -> Option<(usize, u8, (UnitKind, usize))> {
+        for (position, unit) in units().iter().enumerate() {
+            for num in 1..=9 {
+                let mut positions = unit.iter().filter(|&&index| {
+                    self.board[index].is_none() && self.candidates.has_candidate(index, num)
+                });
+                if let (Some(&index), None) = (positions.next(), positions.next()) {
+                    return Some((index, num, unit_label(position)));
+                }
+            }
+        }
+        None
+    }
+
+    /// Finds a unit of the cell where a number has no other spot left
+    ///
+    /// # Returns
+    /// The first such row, column or box with its index (0-8)
+    fn sole_position_unit(&self, index: usize, num: u8) -> Option<(UnitKind, usize)> {
+        let (row, col) = index_to_coords(index);
+        [
+            (UnitKind::Row, row, row_cells(row)),
+            (UnitKind::Column, col, column_cells(col)),
+            (UnitKind::Box, get_box_index(row, col), box_cells(get_box_index(row, col))),
+        ]
+        .into_iter()
+        .find(|(_, _, cells)| {
+            cells.iter().all(|&other| {
+                other == index
+                    || self.board[other].is_some()
+                    || !self.candidates.has_candidate(other, num)
+            })
+        })
+        .map(|(kind, unit_index, _)| (kind, unit_index))
+    }
+
+    /// Finds the empty cell with the fewest candidates
+    ///
+    /// # Returns
+    /// The cell index, or `None` if the board is full
+    fn most_constrained_cell(&self) -> Option<usize> {
+        (0..BOARD_SIZE)
+            .filter(|&index| self.board[index].is_none())
+            .min_by_key(|&index| self.candidates.candidate_count(index))
+    }
+}
+
+/// Solves a puzzle with human-style techniques and records every placement
+///
+/// # Arguments
+/// * `board` - The puzzle to solve
+///
+/// # Returns
+/// The placements in solving order; stops early if logic stalls
+pub fn solve_trace(board: &[Option<u8>]) -> Vec<SolveStep> {
+    let mut solver = HumanStyleSolver::new(board).with_trace();
+    solver.solve_with_techniques();
+    solver.trace.unwrap_or_default()
+}
+
+/// Finds a basic fish for one number, leaving the board untouched
+///
+/// Meant for drawing the pattern. X-Wings and Swordfish are the same
+/// patterns the solver applies, so a hint drawn from here matches the
+/// rating; Jellyfish (size 4) are found for display only and don't change
+/// how a puzzle solves or rates. Candidates come from the clues alone.
+///
+/// # Arguments
+/// * `board` - The current board state
+/// * `num` - The number the fish is made of (1-9)
+/// * `size` - 2 for an X-Wing, 3 for a Swordfish, 4 for a Jellyfish
+///
+/// # Returns
+/// The first fish that eliminates something, or `None` if there is none,
+/// `num` or `size` is out of range or the board fails [`check_board_values`]
+pub fn find_fish(board: &[Option<u8>], num: u8, size: usize) -> Option<FishPattern> {
+    if !(1..=9).contains(&num) || !(2..=4).contains(&size) || check_board_values(board).is_err() {
+        return None;
+    }
+    HumanStyleSolver::new(board).fish_pattern(num, size)
+}
+
+/// Solves a puzzle and notes how each empty cell was filled
+///
+/// Cells are credited to the technique that placed them during the
+/// human-style solve. Once logic stalls, backtracking fills the rest.
+///
+/// # Arguments
+/// * `board` - The puzzle to solve
+///
+/// # Returns
+/// One annotation per empty cell, in index order, or `None` if the puzzle
+/// has no solution
+pub fn annotated_solution(board: &[Option<u8>]) -> Option<Vec<CellAnnotation>> {
+    let mut solver = HumanStyleSolver::new(board).with_trace();
+    solver.solve_with_techniques();
+
+    let mut solution = solver.board.clone();
+    if !solve_board(&mut solution) {
+        return None;
+    }
+
+    let mut techniques = vec![SolvingTechnique::TrialAndError; BOARD_SIZE];
+    for step in solver.trace.unwrap_or_default() {
+        techniques[step.index] = step.technique;
+    }
+    (0..BOARD_SIZE)
+        .filter(|&index| board[index].is_none())
+        .map(|index| {
+            Some(CellAnnotation {
+                index,
+                value: solution[index]?,
+                technique: techniques[index].clone(),
+            })
+        })
+        .collect()
+}
+
+/// Counts the logical steps needed to solve a puzzle
+///
+/// Two puzzles that need the same hardest technique can still differ
+/// greatly in how long they take; this measures the length of the path.
+///
+/// # Arguments
+/// * `board` - The puzzle to solve
+///
+/// # Returns
+/// Placements plus elimination passes; stops counting if logic stalls
+pub fn solve_path_length(board: &[Option<u8>]) -> u32 {
+    let mut solver = HumanStyleSolver::new(board).with_trace();
+    solver.solve_with_techniques();
+    solver.get_trace().len() as u32 + solver.elimination_count
+}
+
+/// Finds how far each technique tier carries a solve
+///
+/// A milestone is recorded whenever the trace needs a technique harder than
+/// any before it. If logic stalls, a final trial-and-error milestone marks
+/// where guessing starts.
+///
+/// # Arguments
+/// * `board` - The puzzle to solve
+///
+/// # Returns
+/// Milestones in solving order, with rising fractions and technique ranks
+pub fn technique_milestones(board: &[Option<u8>]) -> Vec<Milestone> {
+    let empty = board.iter().filter(|cell| cell.is_none()).count();
+    let trace = solve_trace(board);
+    let fraction = |filled: usize| filled as f64 / empty.max(1) as f64;
+
+    let mut milestones: Vec<Milestone> = Vec::new();
+    for (filled, step) in trace.iter().enumerate() {
+        if milestones.last().is_none_or(|last| step.technique > last.technique) {
+            milestones.push(Milestone {
+                fraction_solved: fraction(filled),
+                technique: step.technique.clone(),
+            });
+        }
+    }
+    if trace.len() < empty {
+        milestones.push(Milestone {
+            fraction_solved: fraction(trace.len()),
+            technique: SolvingTechnique::TrialAndError,
+        });
+    }
+    milestones
+}
+
+/// Applies logical techniques until they stall, without guessing
+///
+/// # Arguments
+/// * `board` - The puzzle to solve
+///
+/// # Returns
+/// The partially solved board and the point where a guess would be needed
+pub fn solve_until_stuck(board: &[Option<u8>]) -> StuckPoint {
+    let mut solver = HumanStyleSolver::new(board);
+    solver.solve_with_techniques();
+
+    let branch = solver
+        .most_constrained_cell()
+        .map(|index| (index, solver.candidates.get_candidates(index)));
+    let empty_cells = (0..BOARD_SIZE)
+        .filter(|&index| solver.board[index].is_none())
+        .collect();
+
+    StuckPoint {
+        board: solver.board,
+        empty_cells,
+        branch,
+    }
+}
+
+/// Finds the next placement a human solver would make
+///
+/// Singles are preferred. When none are available, elimination techniques
+/// are applied until a single appears, and the hint is credited to the
+/// hardest technique that was needed. If logic stalls entirely, the most
+/// constrained cell is revealed from the backtracking solution as
+/// [`SolvingTechnique::TrialAndError`].
+///
+/// # Arguments
+/// * `board` - The current board state
+///
+/// # Returns
+/// The suggested placement, or `None` if the board is full or unsolvable
+pub fn find_hint(board: &[Option<u8>]) -> Option<Hint> {
+    let mut solver = HumanStyleSolver::new(board);
+
+    loop {
+        let eliminations = solver.techniques_used.iter().max().cloned();
+        let single = solver
+            .peek_naked_single()
+            .map(|(index, value)| (index, value, SolvingTechnique::NakedSingle, None))
+            .or_else(|| {
+                solver.peek_hidden_single().map(|(index, value, unit)| {
+                    (index, value, SolvingTechnique::HiddenSingle, Some(unit))
+                })
+            });
+
+        if let Some((index, value, technique, unit)) = single {
+            let technique = eliminations.map_or(technique.clone(), |e| e.max(technique));
+            return Some(Hint {
+                index,
+                value,
+                technique,
+                unit,
+            });
+        }
+
+        if !(solver.apply_intermediate_techniques() || solver.apply_advanced_techniques()) {
+            // Logic is stuck: reveal the most constrained cell from the solution
+            let index = solver.most_constrained_cell()?;
+            let mut solution = board.to_vec();
+            if !solve_board(&mut solution) {
+                return None;
+            }
+            return Some(Hint {
+                index,
+                value: solution[index]?,
+                technique: SolvingTechnique::TrialAndError,
+                unit: None,
+            });
+        }
+    }
+}
+
+/// Finds the unit a beginner should look at first
+///
+/// Softer than [`find_hint`], whose placement it is based on: only the
+/// region holding the cell is named. A hidden single points at the unit
+/// where its number has one spot left. Any other move points at whichever
+/// of the cell's box, row and column has the most numbers filled in, where
+/// the missing ones are easiest to spot.
+///
+/// # Arguments
+/// * `board` - The current board state
+///
+/// # Returns
+/// The unit kind and index (0-8, boxes in reading order), or `None` if the
+/// board is full or unsolvable
+pub fn first_move_region(board: &[Option<u8>]) -> Option<(UnitKind, usize)> {
+    let hint = find_hint(board)?;
+    if hint.unit.is_some() {
+        return hint.unit;
+    }
+
+    let (row, col, box_index) = unit_indices(hint.index);
+    let empty = |cells: &[usize; GRID_SIZE]| cells.iter().filter(|&&i| board[i].is_none()).count();
+    [
+        (UnitKind::Box, box_index, box_cells(box_index)),
+        (UnitKind::Row, row, row_cells(row)),
+        (UnitKind::Column, col, column_cells(col)),
+    ]
+    .into_iter()
+    .min_by_key(|(_, _, cells)| empty(cells))
+    .map(|(kind, unit_index, _)| (kind, unit_index))
+}
+
+/// Finds every single available on the board, without placing any
+///
+/// Unlike [`find_hint`], which suggests one move, this scans the whole
+/// board once so a front-end can fill all obvious cells together. A cell
+/// that is both a naked and a hidden single is reported as a naked single.
+///
+/// # Arguments
+/// * `board` - The current board state
+///
+/// # Returns
+/// One placement per cell, in index order
+pub fn all_immediate_placements(board: &[Option<u8>]) -> Vec<Hint> {
+    let solver = HumanStyleSolver::new(board);
+
+    (0..BOARD_SIZE)
+        .filter(|&index| solver.board[index].is_none())
+        .filter_map(|index| {
+            let candidates = solver.candidates.get_candidates(index);
+            if let [value] = candidates[..] {
+                return Some(Hint {
+                    index,
+                    value,
+                    technique: SolvingTechnique::NakedSingle,
+                    unit: None,
+                });
+            }
+            candidates.into_iter().find_map(|value| {
+                solver.sole_position_unit(index, value).map(|unit| Hint {
+                    index,
+                    value,
+                    technique: SolvingTechnique::HiddenSingle,
+                    unit: Some(unit),
+                })
+            })
+        })
+        .collect()
+}
+
+/// Names a unit by its position in [`units`]
+fn unit_label(position: usize) -> (UnitKind, usize) {
+    let kinds = [UnitKind::Row, UnitKind::Column, UnitKind::Box];
+    (kinds[position / GRID_SIZE], position % GRID_SIZE)
+}
+
+/// Finds cells that are the last empty cell of their row, column or box
+///
+/// The gentlest hint there is: the missing digit can be read straight off
+/// the unit, with no candidates to track. Only units with 8 distinct
+/// digits count, so these are a subset of [`all_immediate_placements`].
+///
+/// # Arguments
+/// * `board` - The current board state
+///
+/// # Returns
+/// One hidden-single hint per forced cell, in index order, with `unit`
+/// set to the first near-complete unit (rows, then columns, then boxes)
+pub fn trivially_forced_cells(board: &[Option<u8>]) -> Vec<Hint> {
+    let mut forced: Vec<Hint> = Vec::new();
+
+    for (position, unit) in units().iter().enumerate() {
+        let mut empty = unit.iter().filter(|&&index| board[index].is_none());
+        let (Some(&index), None) = (empty.next(), empty.next()) else {
+            continue;
+        };
+        let mut missing = (1..=9).filter(|&num| !unit.iter().any(|&i| board[i] == Some(num)));
+        if let (Some(value), None) = (missing.next(), missing.next()) {
+            if forced.iter().all(|hint| hint.index != index) {
+                forced.push(Hint {
+                    index,
+                    value,
+                    technique: SolvingTechnique::HiddenSingle,
+                    unit: Some(unit_label(position)),
+                });
+            }
+        }
+    }
+
+    forced.sort_by_key(|hint| hint.index);
+    forced
+}
+
+/// Gets every cell's candidates as bit flags
+///
+/// A compact alternative to listing candidate digits, e.g. for syncing
+/// solver state over the network. Only the clues are taken into account;
+/// no solving techniques are applied.
+///
+/// # Arguments
+/// * `board` - The current board state
+///
+/// # Returns
+/// 81 masks where bit `k` set means `k + 1` is a candidate; filled cells
+/// have no candidates
+pub fn candidate_masks(board: &[Option<u8>]) -> Vec<u16> {
+    let solver = HumanStyleSolver::new(board);
+    (0..BOARD_SIZE)
+        .map(|index| match solver.board[index] {
+            Some(_) => 0,
+            None => solver.candidates.get_mask(index),
+        })
+        .collect()
+}
+
+/// Counts the empty cells a single could fill right now
+///
+/// A cell counts if it has one candidate left, or if it is the only spot
+/// for some number in one of its units. Like [`candidate_masks`], only the
+/// clues are taken into account.
+///
+/// # Arguments
+/// * `board` - The current board state
+///
+/// # Returns
+/// The number of distinct cells open to a naked or hidden single
+pub fn count_single_placements(board: &[Option<u8>]) -> usize {
+    let masks = candidate_masks(board);
+    let mut solvable: Vec<bool> = masks.iter().map(|mask| mask.count_ones() == 1).collect();
+    for unit in units() {
+        for bit in 0..GRID_SIZE {
+            let mut spots = unit.iter().filter(|&&index| masks[index] >> bit & 1 == 1);
+            if let (Some(&index), None) = (spots.next(), spots.next()) {
+                solvable[index] = true;
+            }
+        }
+    }
+    solvable.iter().filter(|&&hit| hit).count()
+}
+
+/// Describes a solve step in one sentence
+///
+/// Meant for hint tooltips and tutorials, so front-ends don't need their own
+/// wording per technique. Cells are named `R<row>C<column>` and units are
+/// numbered from 1, boxes in reading order.
+///
+/// # Arguments
+/// * `step` - A step from [`solve_trace`]
+///
+/// # Returns
+/// A sentence ending with the technique name in parentheses
+pub fn explain_step(step: &SolveStep) -> String {
+    explain_placement(step.index, step.value, &step.technique, step.unit)
+}
+
+/// Describes a hint in one sentence, worded like [`explain_step`]
+///
+/// # Arguments
+/// * `hint` - A hint from [`find_hint`]
+///
+/// # Returns
+/// A sentence ending with the technique name in parentheses
+pub fn explain_hint(hint: &Hint) -> String {
+    explain_placement(hint.index, hint.value, &hint.technique, hint.unit)
+}
+
+/// Builds the sentence behind [`explain_step`] and [`explain_hint`]
+fn explain_placement(
+    index: usize,
+    value: u8,
+    technique: &SolvingTechnique,
+    unit: Option<(UnitKind, usize)>,
+) -> String {
+    let cell = index_to_rc_string(index);
+    let name = technique.display_name().to_lowercase();
+    let sole_spot = match unit {
+        Some((kind, unit_index)) => {
+            let kind = match kind {
+                UnitKind::Row => "row",
+                UnitKind::Column => "column",
+                UnitKind::Box => "box",
+            };
+            format!("the only place {} can go in {} {}", value, kind, unit_index + 1)
+        }
+        None => format!("left with {} as its only candidate", value),
+    };
+
+    let reason = match technique {
+        SolvingTechnique::NakedSingle => {
+            return format!(
+                "Cell {} can only hold {}, since every other number already appears in its row, column or box ({}).",
+                cell, value, name
+            );
+        }
+        SolvingTechnique::HiddenSingle => {
+            return format!("Cell {} is {} ({}).", cell, sole_spot, name);
+        }
+        SolvingTechnique::TrialAndError => {
+            return format!(
+                "No logical step is left, so trying candidates shows that cell {} holds {} ({}).",
+                cell, value, name
+            );
+        }
+        SolvingTechnique::NakedPair => {
+            "Two cells in a unit share the same two candidates, so those numbers are removed from the rest of the unit"
+        }
+        SolvingTechnique::HiddenPair => {
+            "Two numbers fit in only two cells of a unit, so every other candidate is removed from those cells"
+        }
+        SolvingTechnique::BoxLineReduction => {
+            "A number confined to one row or column inside a box is removed from the rest of that line"
+        }
+        SolvingTechnique::PointingPairs => {
+            "A number confined to one box inside a row or column is removed from the rest of that box"
+        }
+        SolvingTechnique::XWing => {
+            "A number fits in only two cells in each of two rows, lined up in the same two columns, so it is removed from the rest of those columns"
+        }
+        SolvingTechnique::PointingTriples => {
+            "A number confined to three cells shared by a box and a line is removed from the rest of the other unit"
+        }
+        SolvingTechnique::EmptyRectangle => {
+            "A box holds a number only along one row and one column, and a conjugate pair outside the box rules out the cell where they meet"
+        }
+        SolvingTechnique::FinnedXWing => {
+            "A number would form an X-Wing but for a fin in one box, so it is removed from cells that see both the pattern and the fin"
+        }
+        SolvingTechnique::RemotePairs => {
+            "A chain of cells sharing the same two candidates alternates between them, so cells that see two chain cells an odd number of links apart lose both numbers"
+        }
+        SolvingTechnique::Swordfish => {
+            "A number is confined to the same three columns across three rows, so it is removed from the rest of those columns"
+        }
+        SolvingTechnique::Coloring => {
+            "Following cells where a number has only two spots shows which cells cannot hold it"
+        }
+        SolvingTechnique::XYWing => {
+            "Three cells with two candidates each form a wing, so the number shared by its ends is removed from cells that see both"
+        }
+        SolvingTechnique::XYChain => {
+            "A chain of two-candidate cells forces one of its ends to hold a number, so cells that see both ends lose it"
+        }
+        SolvingTechnique::AlmostLockedSet => {
+            "Two groups of cells each hold one more candidate than cells and share a number that only one of them can take, so cells that see every spot of another shared number in both groups lose it"
+        }
+        SolvingTechnique::ForcingChain => {
+            "Every candidate of one cell leads to the same conclusion, which removes candidates elsewhere"
+        }
+    };
+    format!("{}. Cell {} is then {} ({}).", reason, cell, sole_spot, name)
+}
+
+/// Counts the guesses needed to solve a puzzle when logic alone stalls
+///
+/// Solves with human-style techniques first. Whenever they run out, the
+/// most constrained cell is guessed and each candidate is explored with
+/// logic again; the result is the smallest number of nested guesses along
+/// a branch that reaches a valid solution.
+///
+/// The search is bounded by [`MAX_GUESS_DEPTH`] and [`MAX_GUESS_NODES`], so
+/// pathological inputs give up rather than run indefinitely.
+///
+/// # Arguments
+/// * `board` - The puzzle to analyze
+///
+/// # Returns
+/// `Some(0)` if logic alone solves the puzzle, `Some(n)` for `n` guesses,
+/// or `None` if no solution was found within the search budget
+pub fn count_required_guesses(board: &[Option<u8>]) -> Option<u32> {
+    if !is_board_valid(board) {
+        return None;
+    }
+
+    let mut nodes = 0;
+    min_guesses(HumanStyleSolver::new(board), 0, &mut nodes)
+}
+
+/// Recursive helper for [`count_required_guesses`]
+fn min_guesses(mut solver: HumanStyleSolver, depth: u32, nodes: &mut usize) -> Option<u32> {
+    solver.solve_with_techniques();
+
+    if solver.has_contradiction() {
+        return None;
+    }
+    if solver.is_solved() {
+        return is_board_valid(&solver.board).then_some(0);
+    }
+    if depth >= MAX_GUESS_DEPTH {
+        return None;
+    }
+
+    let index = solver.most_constrained_cell()?;
+    let mut best: Option<u32> = None;
+
+    for num in solver.candidates.get_candidates(index) {
+        if *nodes >= MAX_GUESS_NODES {
+            break;
+        }
+        *nodes += 1;
+
+        let mut branch = solver.clone();
+        branch.board[index] = Some(num);
+        branch.place_number(index, num);
+
+        if let Some(guesses) = min_guesses(branch, depth + 1, nodes) {
+            best = Some(best.map_or(guesses + 1, |b| b.min(guesses + 1)));
+            if guesses == 0 {
+                // A single guess is the best this level can do
+                break;
+            }
+        }
+    }
+
+    best
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_fixtures::{parse, SINGLES_PUZZLE, SOLVED_GRID, STALLED_PUZZLE};
 
     #[test]
     fn test_solver_creation() {
@@ -427,6 +1933,744 @@ mod tests {
         assert!(solver.calculate_branching_factor() > 5.0);
     }
 
+    #[test]
+    fn test_candidate_mask_round_trip() {
+        let mut grid = CandidateGrid::new();
+        grid.set_candidates(10, 0b1_0100_0101);
+        assert_eq!(grid.get_mask(10), 0b1_0100_0101);
+        assert_eq!(grid.candidate_count(10), 4);
+        assert_eq!(grid.get_candidates(10), vec![1, 3, 7, 9]);
+
+        grid.set_candidates(10, 0);
+        assert_eq!(grid.candidate_count(10), 0);
+        assert_eq!(grid.candidate_count(11), 9);
+
+        let overflow = std::panic::catch_unwind(|| CandidateGrid::new().set_candidates(0, 1 << 9));
+        assert!(overflow.is_err());
+    }
+
+    #[test]
+    fn test_count_required_guesses() {
+        // A few cells removed from a complete grid: singles finish it
+        let singles_only = parse(SOLVED_GRID)
+            .iter()
+            .enumerate()
+            .map(|(i, &cell)| if i % 7 == 0 { None } else { cell })
+            .collect::<Vec<_>>();
+        assert_eq!(count_required_guesses(&singles_only), Some(0));
+
+        // A well-known expert puzzle that singles cannot crack
+        let expert = parse(STALLED_PUZZLE);
+        let guesses = count_required_guesses(&expert).expect("expert puzzle is solvable");
+        assert!(guesses >= 1, "expert puzzle should need a guess, got {}", guesses);
+    }
+
+    #[test]
+    fn test_annotated_solution_covers_every_empty_cell() {
+        // Singles finish the first puzzle; the second needs guessing
+        for (puzzle, needs_guess) in [(SINGLES_PUZZLE, false), (STALLED_PUZZLE, true)] {
+            let board = parse(puzzle);
+            let mut solution = board.clone();
+            assert!(solve_board(&mut solution));
+
+            let annotations = annotated_solution(&board).expect("puzzle is solvable");
+            let empty: Vec<usize> = (0..BOARD_SIZE).filter(|&i| board[i].is_none()).collect();
+            assert_eq!(annotations.iter().map(|a| a.index).collect::<Vec<_>>(), empty);
+            assert!(annotations.iter().all(|a| solution[a.index] == Some(a.value)));
+            assert_eq!(
+                annotations.iter().any(|a| a.technique == SolvingTechnique::TrialAndError),
+                needs_guess
+            );
+        }
+    }
+
+    #[test]
+    fn test_find_hint_costs() {
+        let solution = parse(SOLVED_GRID);
+        let mut board = solution.clone();
+        board[0] = None;
+
+        let hint = find_hint(&board).expect("one empty cell should give a hint");
+        assert_eq!((hint.index, hint.value), (0, 5));
+        assert_eq!(hint.technique, SolvingTechnique::NakedSingle);
+
+        // Singles can't start this puzzle, so the hint falls back to a guess
+        let expert = parse(STALLED_PUZZLE);
+        let hard_hint = find_hint(&expert).expect("expert puzzle should give a hint");
+        assert!(hard_hint.technique.hint_cost() > hint.technique.hint_cost());
+        assert!(SolvingTechnique::XWing.hint_cost() > SolvingTechnique::NakedSingle.hint_cost());
+
+        // The revealed value must belong to the real solution
+        let mut solved = expert.clone();
+        assert!(solve_board(&mut solved));
+        assert_eq!(solved[hard_hint.index], Some(hard_hint.value));
+    }
+
+    #[test]
+    fn test_all_immediate_placements() {
+        let solution = parse(SOLVED_GRID);
+        let mut board = solution.clone();
+        for index in [0, 40, 80] {
+            board[index] = None;
+        }
+        let placements = all_immediate_placements(&board);
+        assert_eq!(
+            placements.iter().map(|hint| hint.index).collect::<Vec<_>>(),
+            vec![0, 40, 80]
+        );
+        assert!(placements.iter().all(|hint| {
+            hint.technique == SolvingTechnique::NakedSingle
+                && Some(hint.value) == solution[hint.index]
+        }));
+
+        // Hidden singles are reported alongside naked ones, none applied
+        let board = parse(SINGLES_PUZZLE);
+        let mut solved = board.clone();
+        assert!(solve_board(&mut solved));
+        let placements = all_immediate_placements(&board);
+        assert!(placements.len() > 1);
+        assert!(placements
+            .iter()
+            .any(|hint| hint.technique == SolvingTechnique::HiddenSingle && hint.unit.is_some()));
+        assert!(placements
+            .iter()
+            .all(|hint| board[hint.index].is_none() && Some(hint.value) == solved[hint.index]));
+    }
+
+    #[test]
+    fn test_trivially_forced_cells() {
+        // Row 2 holds every digit but 6, which belongs in column 5
+        let mut board = vec![None; BOARD_SIZE];
+        for (col, num) in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (6, 7), (7, 8), (8, 9)] {
+            board[coords_to_index(2, col)] = Some(num);
+        }
+
+        let forced = trivially_forced_cells(&board);
+        assert_eq!(forced.len(), 1);
+        assert_eq!(forced[0].index, coords_to_index(2, 5));
+        assert_eq!(forced[0].value, 6);
+        assert_eq!(forced[0].unit, Some((UnitKind::Row, 2)));
+
+        // Two gaps leave nothing forced by the row alone
+        board[coords_to_index(2, 0)] = None;
+        assert!(trivially_forced_cells(&board).is_empty());
+    }
+
+    #[test]
+    fn test_candidate_masks_bits_match_candidates() {
+        let board = parse(STALLED_PUZZLE);
+        let masks = candidate_masks(&board);
+        assert_eq!(masks.len(), BOARD_SIZE);
+
+        for (index, &mask) in masks.iter().enumerate() {
+            if board[index].is_some() {
+                assert_eq!(mask, 0);
+                continue;
+            }
+            for bit in 0..9 {
+                let num = bit as u8 + 1;
+                let free = peers(index).iter().all(|&peer| board[peer] != Some(num));
+                assert_eq!(mask & (1 << bit) != 0, free, "cell {} digit {}", index, num);
+            }
+            assert_eq!(mask >> 9, 0);
+        }
+    }
+
+    #[test]
+    fn test_explain_step_covers_every_technique() {
+        for technique in SolvingTechnique::ALL {
+            let step = SolveStep {
+                index: 22,
+                value: 7,
+                rank: technique.rank(),
+                unit: (technique != SolvingTechnique::NakedSingle).then_some((UnitKind::Box, 1)),
+                technique: technique.clone(),
+                branching_factor: 2.0,
+            };
+            let sentence = explain_step(&step);
+            assert!(sentence.contains("R3C5"), "{}", sentence);
+            assert!(sentence.contains('7'), "{}", sentence);
+            let suffix = format!("({}).", technique.display_name().to_lowercase());
+            assert!(sentence.ends_with(&suffix), "{}", sentence);
+        }
+
+        let step = SolveStep {
+            index: 22,
+            value: 7,
+            technique: SolvingTechnique::HiddenSingle,
+            rank: 2,
+            branching_factor: 2.0,
+            unit: Some((UnitKind::Box, 1)),
+        };
+        assert_eq!(
+            explain_step(&step),
+            "Cell R3C5 is the only place 7 can go in box 2 (hidden single)."
+        );
+
+        // Hidden singles found while solving name the unit they came from
+        let board = parse(SINGLES_PUZZLE);
+        assert!(solve_trace(&board)
+            .iter()
+            .filter(|step| step.technique == SolvingTechnique::HiddenSingle)
+            .all(|step| step.unit.is_some()));
+    }
+
+    #[test]
+    fn test_solve_trace_steps() {
+        let solution = parse(SOLVED_GRID);
+        let board: Vec<Option<u8>> = solution
+            .iter()
+            .enumerate()
+            .map(|(i, &cell)| if i % 4 == 0 { None } else { cell })
+            .collect();
+
+        let trace = solve_trace(&board);
+        assert_eq!(trace.len(), board.iter().filter(|c| c.is_none()).count());
+
+        for step in &trace {
+            assert_eq!(solution[step.index], Some(step.value));
+            assert_eq!(step.rank, step.technique.rank());
+            assert!(step.rank >= 1);
+            assert!(step.branching_factor >= 1.0);
+        }
+
+        // Without tracing nothing is recorded
+        let mut solver = HumanStyleSolver::new(&board);
+        assert!(solver.solve_with_techniques());
+        assert!(solver.get_trace().is_empty());
+    }
+
+    #[test]
+    fn test_hidden_single_records_forcing_unit() {
+        let mut board = parse(SINGLES_PUZZLE);
+        let forces = |board: &[Option<u8>], index: usize, value: u8, (kind, unit_index)| {
+            let cells = match kind {
+                UnitKind::Row => row_cells(unit_index),
+                UnitKind::Column => column_cells(unit_index),
+                UnitKind::Box => box_cells(unit_index),
+            };
+            let solver = HumanStyleSolver::new(board);
+            cells.contains(&index)
+                && cells.iter().all(|&other| {
+                    other == index
+                        || board[other].is_some()
+                        || !solver.candidates.has_candidate(other, value)
+                })
+        };
+
+        let mut kinds = Vec::new();
+        for step in solve_trace(&board) {
+            let hint = find_hint(&board).unwrap();
+            if let Some(unit) = hint.unit {
+                assert!(forces(&board, hint.index, hint.value, unit), "{:?}", hint);
+            }
+            if step.technique == SolvingTechnique::HiddenSingle {
+                let unit = step.unit.expect("hidden singles record their unit");
+                assert!(forces(&board, step.index, step.value, unit), "{:?}", step);
+                kinds.push(unit.0);
+            } else {
+                assert_eq!(step.unit, None);
+            }
+            board[step.index] = Some(step.value);
+        }
+        assert!(kinds.contains(&UnitKind::Column) && kinds.contains(&UnitKind::Box));
+    }
+
+    #[test]
+    fn test_solve_path_length_shorter_when_denser() {
+        let solution = parse(SOLVED_GRID);
+        let blank_every = |n: usize| -> Vec<Option<u8>> {
+            solution
+                .iter()
+                .enumerate()
+                .map(|(i, &cell)| if i % n == 0 { None } else { cell })
+                .collect()
+        };
+        let dense = blank_every(8);
+        let sparse = blank_every(4);
+
+        assert_eq!(solve_path_length(&dense), 11);
+        assert!(solve_path_length(&dense) < solve_path_length(&sparse));
+    }
+
+    #[test]
+    fn test_technique_milestones_monotonic() {
+        let puzzles = [SINGLES_PUZZLE, STALLED_PUZZLE];
+        for puzzle in puzzles {
+            let milestones = technique_milestones(&parse(puzzle));
+            assert!(!milestones.is_empty());
+            assert_eq!(milestones[0].fraction_solved, 0.0);
+            for pair in milestones.windows(2) {
+                assert!(pair[0].fraction_solved <= pair[1].fraction_solved);
+                assert!(pair[0].technique.rank() < pair[1].technique.rank());
+            }
+        }
+
+        // Logic stalls on the second puzzle, so guessing is the last tier
+        let stalled = technique_milestones(&parse(puzzles[1]));
+        assert_eq!(
+            stalled.last().map(|m| &m.technique),
+            Some(&SolvingTechnique::TrialAndError)
+        );
+    }
+
+    #[test]
+    fn test_empty_rectangle_eliminations() {
+        let empty = vec![None; BOARD_SIZE];
+
+        // Box 0 keeps 4 at R1C1, R1C3 and R3C2: the cross of row 0 and
+        // column 1. Column 5 holds 4 only at rows 0 and 6.
+        let mut solver = HumanStyleSolver::new(&empty);
+        for &index in box_cells(0) {
+            if ![0, 2, 19].contains(&index) {
+                solver.candidates.remove_candidate(index, 4);
+            }
+        }
+        for row in (0..GRID_SIZE).filter(|&row| row != 0 && row != 6) {
+            solver.candidates.remove_candidate(coords_to_index(row, 5), 4);
+        }
+
+        // R7C2 would force R7C6 empty, R1C6 to 4, and box 0's 4 into column 1
+        assert_eq!(
+            solver.empty_rectangle_eliminations(),
+            vec![(coords_to_index(6, 1), 4)]
+        );
+        assert!(solver.find_empty_rectangle());
+        assert!(!solver.candidates.has_candidate(coords_to_index(6, 1), 4));
+        assert_eq!(
+            solver.get_hardest_technique_used(),
+            SolvingTechnique::EmptyRectangle
+        );
+        assert!(!solver.find_empty_rectangle());
+
+        // Candidates on one line of the box are a pointing pattern, not a rectangle
+        let mut solver = HumanStyleSolver::new(&empty);
+        for &index in box_cells(0) {
+            if ![0, 2].contains(&index) {
+                solver.candidates.remove_candidate(index, 4);
+            }
+        }
+        for row in (0..GRID_SIZE).filter(|&row| row != 0 && row != 6) {
+            solver.candidates.remove_candidate(coords_to_index(row, 5), 4);
+        }
+        assert!(solver.empty_rectangle_eliminations().is_empty());
+    }
+
+    #[test]
+    fn test_empty_rectangle_on_generated_puzzle() {
+        // A generated Hard puzzle that singles finish only after an empty rectangle
+        let board = parse(
+            "..8......74..53....532....6.....1..2..74.53..5..9.....6....857....63..14......2..",
+        );
+        let mut solution = board.clone();
+        assert!(solve_board(&mut solution));
+
+        // Everything easier stalls; the eliminations keep every solution digit
+        let mut solver =
+            HumanStyleSolver::new(&board).with_technique_limit(SolvingTechnique::PointingTriples);
+        assert!(!solver.solve_with_techniques());
+        let eliminations = solver.empty_rectangle_eliminations();
+        assert!(!eliminations.is_empty());
+        for (index, value) in eliminations {
+            assert_ne!(solution[index], Some(value));
+        }
+
+        let mut solver = HumanStyleSolver::new(&board);
+        assert!(solver.solve_with_techniques());
+        assert_eq!(solver.get_board(), &solution[..]);
+        assert!(solver
+            .get_techniques_used()
+            .contains(&SolvingTechnique::EmptyRectangle));
+    }
+
+    #[test]
+    fn test_first_move_region_contains_next_placement() {
+        for puzzle in [
+            SINGLES_PUZZLE,
+            "9...1..........251.83.7....4..8....98..69......7.......1...2..5...5..17.3....9..8",
+            STALLED_PUZZLE,
+        ] {
+            let board = parse(puzzle);
+            let hint = find_hint(&board).expect("puzzle has a next move");
+            let (kind, unit_index) = first_move_region(&board).expect("puzzle has a next move");
+            let (row, col, box_index) = unit_indices(hint.index);
+            let expected = match kind {
+                UnitKind::Row => row,
+                UnitKind::Column => col,
+                UnitKind::Box => box_index,
+            };
+            assert_eq!(unit_index, expected, "{}", puzzle);
+        }
+
+        let solved = parse(SOLVED_GRID);
+        assert_eq!(first_move_region(&solved), None);
+    }
+
+    #[test]
+    fn test_find_fish_reports_x_wing() {
+        let board = parse(
+            ".5.4....7....83.51..32.596442.8.1..9.........5..3.4.166187.24..24.56....3....9.2.",
+        );
+        let fish = find_fish(&board, 2, 2).expect("2 forms an X-Wing");
+        assert_eq!(fish.base_kind, UnitKind::Row);
+        assert_eq!(fish.base_lines, vec![0, 1]);
+        assert_eq!(fish.cover_lines, vec![2, 6]);
+        assert_eq!(fish.eliminations, vec![42, 51]);
+
+        // Out-of-range digits, sizes and cell values find nothing
+        assert_eq!(find_fish(&board, 0, 2), None);
+        assert_eq!(find_fish(&board, 2, 5), None);
+        let mut bad = board.clone();
+        bad[0] = Some(12);
+        assert_eq!(find_fish(&bad, 2, 2), None);
+
+        // The solver removes the same candidates when it applies the X-Wing
+        let mut solver = HumanStyleSolver::new(&board);
+        assert!(solver.find_x_wing());
+        assert!(!solver.candidates.has_candidate(42, 2));
+        assert!(!solver.candidates.has_candidate(51, 2));
+        assert_eq!(solver.get_hardest_technique_used(), SolvingTechnique::XWing);
+    }
+
+    #[test]
+    fn test_implemented_techniques_exclude_stubs() {
+        let names = implemented_techniques();
+        let all: Vec<&str> = SolvingTechnique::ALL.iter().map(|t| t.display_name()).collect();
+        assert!(names.iter().all(|name| all.contains(&name.as_str())));
+        assert!(names.len() < all.len());
+
+        for stub in [
+            SolvingTechnique::NakedPair,
+            SolvingTechnique::HiddenPair,
+            SolvingTechnique::XYWing,
+        ] {
+            assert!(!names.contains(&stub.display_name().to_string()), "{:?}", stub);
+        }
+        assert_eq!(names[0], SolvingTechnique::NakedSingle.display_name());
+    }
+
+    #[test]
+    fn test_remote_pairs_eliminations() {
+        let empty = vec![None; BOARD_SIZE];
+        let chain = [
+            coords_to_index(0, 0),
+            coords_to_index(0, 4),
+            coords_to_index(4, 4),
+            coords_to_index(4, 8),
+        ];
+
+        // R1C1 - R1C5 - R5C5 - R5C9, all holding only 1 and 2
+        let mut solver = HumanStyleSolver::new(&empty);
+        for &index in &chain {
+            for num in 3..=9 {
+                solver.candidates.remove_candidate(index, num);
+            }
+        }
+
+        // The chain ends hold different numbers, so R1C9 and R5C1 can't hold either
+        assert_eq!(
+            solver.remote_pair_eliminations(),
+            vec![
+                (coords_to_index(0, 8), 1),
+                (coords_to_index(0, 8), 2),
+                (coords_to_index(4, 0), 1),
+                (coords_to_index(4, 0), 2),
+            ]
+        );
+        assert!(solver.find_remote_pairs());
+        assert_eq!(
+            solver.get_hardest_technique_used(),
+            SolvingTechnique::RemotePairs
+        );
+        assert!(!solver.find_remote_pairs());
+
+        // A cell with the same pair elsewhere on the board is a separate chain
+        let mut solver = HumanStyleSolver::new(&empty);
+        for &index in chain.iter().chain([&coords_to_index(8, 2)]) {
+            for num in 3..=9 {
+                solver.candidates.remove_candidate(index, num);
+            }
+        }
+        assert_eq!(solver.remote_pair_eliminations().len(), 4);
+
+        // Two links only pin the ends to the same number
+        let mut solver = HumanStyleSolver::new(&empty);
+        for &index in &chain[..3] {
+            for num in 3..=9 {
+                solver.candidates.remove_candidate(index, num);
+            }
+        }
+        assert!(solver.remote_pair_eliminations().is_empty());
+    }
+
+    #[test]
+    fn test_als_xz_eliminations() {
+        let empty = vec![None; BOARD_SIZE];
+        let mut solver = HumanStyleSolver::new(&empty);
+        let mut keep_only = |index: usize, digits: &[u8]| {
+            for num in (1..=9).filter(|num| !digits.contains(num)) {
+                solver.candidates.remove_candidate(index, num);
+            }
+        };
+
+        // ALS A = R1C1 {1,2} + R1C2 {2,3}, ALS B = R5C1 {1,3}. The 1s see
+        // each other down column 1, so 1 is a restricted common candidate:
+        // whichever set lacks it is locked, and one of R1C2 and R5C1 is 3.
+        keep_only(coords_to_index(0, 0), &[1, 2]);
+        keep_only(coords_to_index(0, 1), &[2, 3]);
+        keep_only(coords_to_index(4, 0), &[1, 3]);
+
+        // R2C1 and R3C1 share box 1 with R1C2 and column 1 with R5C1;
+        // R4C2, R5C2 and R6C2 share column 2 and box 4 the other way round
+        assert_eq!(
+            solver.als_xz_eliminations(),
+            vec![
+                (coords_to_index(1, 0), 3),
+                (coords_to_index(2, 0), 3),
+                (coords_to_index(3, 1), 3),
+                (coords_to_index(4, 1), 3),
+                (coords_to_index(5, 1), 3),
+            ]
+        );
+        assert!(solver.find_almost_locked_sets());
+        assert_eq!(
+            solver.get_hardest_technique_used(),
+            SolvingTechnique::AlmostLockedSet
+        );
+        assert!(!solver.find_almost_locked_sets());
+    }
+
+    #[test]
+    fn test_finned_x_wing_restricts_eliminations() {
+        let empty = vec![None; BOARD_SIZE];
+
+        // Row 1 keeps 5 only in columns 1 and 7; row 7 has those two plus a
+        // fin at column 8, in box 8 alongside the column-7 corner
+        let mut solver = HumanStyleSolver::new(&empty);
+        for col in 0..GRID_SIZE {
+            if col != 1 && col != 7 {
+                solver.candidates.remove_candidate(coords_to_index(1, col), 5);
+            }
+            if col != 1 && col != 7 && col != 8 {
+                solver.candidates.remove_candidate(coords_to_index(7, col), 5);
+            }
+        }
+        assert!(solver.find_finned_x_wing());
+
+        // Only column-7 cells that also see the fin lose the 5
+        assert!(!solver.candidates.has_candidate(coords_to_index(6, 7), 5));
+        assert!(!solver.candidates.has_candidate(coords_to_index(8, 7), 5));
+        assert!(solver.candidates.has_candidate(coords_to_index(3, 7), 5));
+        assert!(solver.candidates.has_candidate(coords_to_index(6, 1), 5));
+        assert!(solver.candidates.has_candidate(coords_to_index(6, 8), 5));
+        assert!(solver.candidates.has_candidate(coords_to_index(7, 7), 5));
+        assert_eq!(
+            solver.get_hardest_technique_used(),
+            SolvingTechnique::FinnedXWing
+        );
+        assert!(!solver.find_finned_x_wing());
+
+        // Sashimi: the corner beside the fin is missing, same eliminations
+        let mut solver = HumanStyleSolver::new(&empty);
+        for col in 0..GRID_SIZE {
+            if col != 1 && col != 7 {
+                solver.candidates.remove_candidate(coords_to_index(1, col), 5);
+            }
+            if col != 1 && col != 8 {
+                solver.candidates.remove_candidate(coords_to_index(7, col), 5);
+            }
+        }
+        assert!(solver.find_finned_x_wing());
+        assert!(!solver.candidates.has_candidate(coords_to_index(6, 7), 5));
+        assert!(!solver.candidates.has_candidate(coords_to_index(8, 7), 5));
+        assert!(solver.candidates.has_candidate(coords_to_index(3, 7), 5));
+    }
+
+    #[test]
+    fn test_x_wing_on_generated_puzzle() {
+        // A generated Hard puzzle that singles finish only after an X-Wing
+        let board = parse(
+            "..2..8....3...7.8.....426.3......81...73695...25......2.147.....8.9...3....8..1..",
+        );
+        let mut solution = board.clone();
+        assert!(solve_board(&mut solution));
+
+        // Everything easier stalls; the X-Wing keeps every solution digit
+        let mut solver =
+            HumanStyleSolver::new(&board).with_technique_limit(SolvingTechnique::PointingPairs);
+        assert!(!solver.solve_with_techniques());
+        assert!(solver.find_x_wing());
+        for index in (0..BOARD_SIZE).filter(|&index| solver.board[index].is_none()) {
+            let value = solution[index].unwrap();
+            assert!(solver.candidates.has_candidate(index, value));
+        }
+
+        let mut solver = HumanStyleSolver::new(&board);
+        assert!(solver.solve_with_techniques());
+        assert_eq!(solver.get_board(), &solution[..]);
+        assert_eq!(solver.get_hardest_technique_used(), SolvingTechnique::XWing);
+    }
+
+    #[test]
+    fn test_swordfish_on_generated_puzzle() {
+        // A generated Expert puzzle that stalls on easier techniques at a Swordfish
+        let board = parse(
+            ".9.4...2......5.....16..7....6..14..483.2..1.....8..7.8....2...1.....3...7..5..4.",
+        );
+        let mut solution = board.clone();
+        assert!(solve_board(&mut solution));
+
+        // Everything easier stalls; the Swordfish keeps every solution digit
+        let mut solver =
+            HumanStyleSolver::new(&board).with_technique_limit(SolvingTechnique::RemotePairs);
+        assert!(!solver.solve_with_techniques());
+        assert!(solver.find_swordfish());
+        for index in (0..BOARD_SIZE).filter(|&index| solver.board[index].is_none()) {
+            let value = solution[index].unwrap();
+            assert!(solver.candidates.has_candidate(index, value));
+        }
+        assert_eq!(
+            solver.get_hardest_technique_used(),
+            SolvingTechnique::Swordfish
+        );
+
+        let mut solver = HumanStyleSolver::new(&board);
+        solver.solve_with_techniques();
+        assert!(solver
+            .get_techniques_used()
+            .contains(&SolvingTechnique::Swordfish));
+    }
+
+    #[test]
+    fn test_finned_x_wing_on_generated_puzzle() {
+        // A generated Hard puzzle that singles finish only after a finned X-Wing
+        let board = parse(
+            ".4..152....9....6.1....8...7......8.3.1...4.6.6......2...5....4.9....1...1293..7.",
+        );
+        let mut solution = board.clone();
+        assert!(solve_board(&mut solution));
+
+        // Everything easier stalls; the finned X-Wing keeps every solution digit
+        let mut solver =
+            HumanStyleSolver::new(&board).with_technique_limit(SolvingTechnique::EmptyRectangle);
+        assert!(!solver.solve_with_techniques());
+        assert!(solver.find_finned_x_wing());
+        for index in (0..BOARD_SIZE).filter(|&index| solver.board[index].is_none()) {
+            let value = solution[index].unwrap();
+            assert!(solver.candidates.has_candidate(index, value));
+        }
+
+        let mut solver = HumanStyleSolver::new(&board);
+        assert!(solver.solve_with_techniques());
+        assert_eq!(solver.get_board(), &solution[..]);
+        assert!(solver
+            .get_techniques_used()
+            .contains(&SolvingTechnique::FinnedXWing));
+    }
+
+    #[test]
+    fn test_intersection_directions_record_their_own_technique() {
+        let empty = vec![None; BOARD_SIZE];
+
+        // Box 0 keeps 5 only in row 0, which still has 5 outside the box
+        let mut solver = HumanStyleSolver::new(&empty);
+        for &index in box_cells(0) {
+            if index_to_coords(index).0 != 0 || index == 2 {
+                solver.candidates.remove_candidate(index, 5);
+            }
+        }
+        assert!(!solver.find_pointing_pairs());
+        assert!(solver.find_box_line_reduction());
+        assert!(!solver.candidates.has_candidate(coords_to_index(0, 5), 5));
+        assert!(solver.candidates.has_candidate(coords_to_index(1, 5), 5));
+        assert_eq!(
+            solver.techniques_used,
+            vec![SolvingTechnique::BoxLineReduction]
+        );
+
+        // Row 0 keeps 5 only in box 0, which still has 5 outside the row
+        let mut solver = HumanStyleSolver::new(&empty);
+        for col in 2..GRID_SIZE {
+            solver.candidates.remove_candidate(col, 5);
+        }
+        assert!(!solver.find_box_line_reduction());
+        assert!(solver.find_pointing_pairs());
+        assert!(!solver.candidates.has_candidate(coords_to_index(2, 1), 5));
+        assert!(solver.candidates.has_candidate(coords_to_index(2, 5), 5));
+        assert_eq!(
+            solver.techniques_used,
+            vec![SolvingTechnique::PointingPairs]
+        );
+    }
+
+    #[test]
+    fn test_pointing_triples_only_when_pairs_fall_short() {
+        let empty = vec![None; BOARD_SIZE];
+
+        // Row 0 keeps 5 only in its first three cells, all in box 0
+        let mut solver = HumanStyleSolver::new(&empty);
+        for col in 3..GRID_SIZE {
+            solver.candidates.remove_candidate(col, 5);
+        }
+        assert!(!solver.find_pointing_pairs());
+        assert!(solver.find_pointing_triples());
+        assert!(!solver.candidates.has_candidate(coords_to_index(1, 0), 5));
+        assert!(!solver.candidates.has_candidate(coords_to_index(2, 2), 5));
+        assert_eq!(
+            solver.get_hardest_technique_used(),
+            SolvingTechnique::PointingTriples
+        );
+
+        // With only two cells left the pair does the work; triples add nothing
+        let mut solver = HumanStyleSolver::new(&empty);
+        for col in 2..GRID_SIZE {
+            solver.candidates.remove_candidate(col, 5);
+        }
+        assert!(solver.find_pointing_pairs());
+        assert!(!solver.candidates.has_candidate(coords_to_index(1, 0), 5));
+        assert!(!solver.find_pointing_triples());
+        assert_eq!(
+            solver.get_hardest_technique_used(),
+            SolvingTechnique::PointingPairs
+        );
+    }
+
+    #[test]
+    fn test_solve_until_stuck() {
+        let expert = parse(STALLED_PUZZLE);
+        let stuck = solve_until_stuck(&expert);
+        assert!(!stuck.empty_cells.is_empty());
+
+        let (index, candidates) = stuck.branch.expect("a branch point should be reported");
+        assert!(stuck.empty_cells.contains(&index));
+        assert!(candidates.len() >= 2, "a single candidate isn't a guess");
+
+        // The true value is among the branch candidates
+        let mut solved = expert.clone();
+        assert!(solve_board(&mut solved));
+        assert!(candidates.contains(&solved[index].unwrap()));
+
+        // Every empty cell offers at least as many choices as the branch cell
+        let solver = HumanStyleSolver::new(&stuck.board);
+        for &empty in &stuck.empty_cells {
+            assert!(solver.candidates.candidate_count(empty) >= candidates.len());
+        }
+    }
+
+    #[test]
+    fn test_branching_factor_contradiction() {
+        // Row 0 holds 1-8 and column 8 already has a 9, so (0, 8) is dead
+        let mut board = vec![None; BOARD_SIZE];
+        for (col, cell) in board.iter_mut().take(8).enumerate() {
+            *cell = Some(col as u8 + 1);
+        }
+        board[coords_to_index(5, 8)] = Some(9);
+
+        let solver = HumanStyleSolver::new(&board);
+        assert!(solver.has_contradiction());
+        assert_eq!(solver.calculate_branching_factor(), f64::INFINITY);
+    }
+
     #[test]
     fn test_branching_factor_full_board() {
         let board = vec![Some(1); BOARD_SIZE];
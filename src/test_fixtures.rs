@@ -0,0 +1,26 @@
+//! Puzzles and parsing helpers shared by the unit tests
+
+/// 37 clues with a unique solution that singles alone reach
+pub(crate) const SINGLES_PUZZLE: &str =
+    "....5.62.....675..7...23.419.8.35.7.64..1..58.5.78.2.647.19...5..934.....82.7....";
+
+/// 21 clues with a unique solution that logic stalls on at once
+pub(crate) const STALLED_PUZZLE: &str =
+    "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..";
+
+/// A complete, valid grid
+pub(crate) const SOLVED_GRID: &str =
+    "534678912672195348198342567859761423426853791713924856961537284287419635345286179";
+
+/// Parses an 81-character puzzle string ('.' or '0' for empty cells)
+pub(crate) fn parse(puzzle: &str) -> Vec<Option<u8>> {
+    puzzle
+        .chars()
+        .map(|c| c.to_digit(10).filter(|&d| d > 0).map(|d| d as u8))
+        .collect()
+}
+
+/// Parses a puzzle string into the JavaScript board layout (0 for empty)
+pub(crate) fn parse_js(puzzle: &str) -> Vec<u8> {
+    parse(puzzle).iter().map(|cell| cell.unwrap_or(0)).collect()
+}
@@ -0,0 +1,331 @@
+//! Validity-preserving board transforms and canonical forms
+//!
+//! Rotating, mirroring or relabeling the digits of a puzzle yields another
+//! valid puzzle with the same difficulty. This module applies those
+//! transforms and reduces a board to a canonical form so equivalent puzzles
+//! can be recognized.
+
+use std::collections::HashSet;
+
+use crate::grid::{coords_to_index, index_to_coords};
+use crate::types::{BOARD_SIZE, GRID_SIZE};
+
+/// The eight rotations and reflections of the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform {
+    /// Leaves the grid unchanged
+    Identity,
+    /// Quarter turn clockwise
+    Rotate90,
+    /// Half turn
+    Rotate180,
+    /// Quarter turn counter-clockwise
+    Rotate270,
+    /// Mirror across the middle row (top and bottom swap)
+    FlipHorizontal,
+    /// Mirror across the middle column (left and right swap)
+    FlipVertical,
+    /// Mirror across the main diagonal (rows and columns swap)
+    Transpose,
+    /// Mirror across the anti-diagonal
+    AntiTranspose,
+}
+
+impl Transform {
+    /// Every transform, starting with the identity
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::Transpose,
+        Transform::AntiTranspose,
+    ];
+
+    /// Gets where a cell ends up after the transform
+    ///
+    /// # Arguments
+    /// * `index` - The cell index before the transform (0-80)
+    ///
+    /// # Returns
+    /// The cell index after the transform
+    pub fn map_index(self, index: usize) -> usize {
+        let (row, col) = index_to_coords(index);
+        let last = GRID_SIZE - 1;
+        let (new_row, new_col) = match self {
+            Transform::Identity => (row, col),
+            Transform::Rotate90 => (col, last - row),
+            Transform::Rotate180 => (last - row, last - col),
+            Transform::Rotate270 => (last - col, row),
+            Transform::FlipHorizontal => (last - row, col),
+            Transform::FlipVertical => (row, last - col),
+            Transform::Transpose => (col, row),
+            Transform::AntiTranspose => (last - col, last - row),
+        };
+        coords_to_index(new_row, new_col)
+    }
+}
+
+/// Applies a geometric transform to a board
+///
+/// # Arguments
+/// * `board` - The board to transform
+/// * `transform` - The rotation or reflection to apply
+///
+/// # Returns
+/// The transformed board
+pub fn apply_transform(board: &[Option<u8>], transform: Transform) -> Vec<Option<u8>> {
+    let mut result = vec![None; BOARD_SIZE];
+    for (index, &cell) in board.iter().enumerate() {
+        result[transform.map_index(index)] = cell;
+    }
+    result
+}
+
+/// Relabels digits in order of first appearance
+///
+/// The first digit met in reading order becomes 1, the next new digit 2,
+/// and so on. Two boards that differ only by a digit permutation have the
+/// same normalized form.
+///
+/// # Arguments
+/// * `board` - The board to relabel
+///
+/// # Returns
+/// The relabeled board, or `None` if the board holds a value outside 1-9
+pub fn normalize_digits(board: &[Option<u8>]) -> Option<Vec<Option<u8>>> {
+    let mut mapping = [0u8; 10];
+    let mut next = 1;
+    board
+        .iter()
+        .map(|cell| match *cell {
+            Some(num @ 1..=9) => {
+                let slot = &mut mapping[num as usize];
+                if *slot == 0 {
+                    *slot = next;
+                    next += 1;
+                }
+                Some(Some(*slot))
+            }
+            Some(_) => None,
+            None => Some(None),
+        })
+        .collect()
+}
+
+/// Replaces every digit according to a permutation of 1-9
+///
+/// The solution is relabeled the same way, so the puzzle keeps its
+/// structure and difficulty exactly.
+///
+/// # Arguments
+/// * `board` - The board to relabel
+/// * `permutation` - Nine digits; digit `d` becomes `permutation[d - 1]`
+///
+/// # Returns
+/// The relabeled board, or `None` if `permutation` isn't a bijection of 1-9
+/// or the board holds a value outside 1-9
+pub fn relabel_digits(board: &[Option<u8>], permutation: &[u8]) -> Option<Vec<Option<u8>>> {
+    let mut seen = [false; 10];
+    if permutation.len() != GRID_SIZE {
+        return None;
+    }
+    for &digit in permutation {
+        if !(1..=9).contains(&digit) || seen[digit as usize] {
+            return None;
+        }
+        seen[digit as usize] = true;
+    }
+
+    board
+        .iter()
+        .map(|cell| match cell {
+            Some(num) => permutation
+                .get((*num as usize).checked_sub(1)?)
+                .map(|&new| Some(new)),
+            None => Some(None),
+        })
+        .collect()
+}
+
+/// Reduces a board to a canonical representative
+///
+/// Boards related by any of the eight [`Transform`]s and a digit relabeling
+/// share the same canonical form. Band, stack, row and column swaps are not
+/// taken into account.
+///
+/// # Arguments
+/// * `board` - The board to canonicalize (values 1-9)
+///
+/// # Returns
+/// The smallest normalized form across all transforms, or an empty board
+/// if the board holds a value outside 1-9
+pub fn canonical_form(board: &[Option<u8>]) -> Vec<Option<u8>> {
+    Transform::ALL
+        .iter()
+        .filter_map(|&transform| normalize_digits(&apply_transform(board, transform)))
+        .min()
+        .unwrap_or_default()
+}
+
+/// Computes a short fingerprint of a board's canonical form
+///
+/// # Arguments
+/// * `board` - The board to fingerprint (values 1-9)
+///
+/// # Returns
+/// A 16-character hex string, equal for equivalent boards
+pub fn fingerprint(board: &[Option<u8>]) -> String {
+    // FNV-1a, which is stable across platforms and releases
+    let hash = canonical_form(board)
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325u64, |hash, cell| {
+            (hash ^ cell.unwrap_or(0) as u64).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{:016x}", hash)
+}
+
+/// Remembers puzzles by fingerprint to spot repeats
+///
+/// Two puzzles count as the same when a rotation, reflection or digit
+/// relabeling turns one into the other (see [`canonical_form`]).
+#[derive(Debug, Clone, Default)]
+pub struct Deduplicator {
+    seen: HashSet<String>,
+}
+
+impl Deduplicator {
+    /// Creates a deduplicator that has seen nothing yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a puzzle
+    ///
+    /// # Arguments
+    /// * `board` - The puzzle to record (values 1-9)
+    ///
+    /// # Returns
+    /// `true` if no equivalent puzzle was recorded before
+    pub fn insert(&mut self, board: &[Option<u8>]) -> bool {
+        self.seen.insert(fingerprint(board))
+    }
+
+    /// Number of distinct puzzles recorded
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Whether nothing has been recorded yet
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
+
+/// Checks whether a non-identity transform maps the board onto itself
+///
+/// A relabeling of the digits is allowed alongside the transform. Puzzles
+/// with such automorphisms are usually considered lower quality.
+///
+/// # Arguments
+/// * `board` - The board to check (values 1-9)
+///
+/// # Returns
+/// `true` if some rotation or reflection plus relabeling leaves the board
+/// unchanged; `false` if the board holds a value outside 1-9
+pub fn has_nontrivial_automorphism(board: &[Option<u8>]) -> bool {
+    let Some(normalized) = normalize_digits(board) else {
+        return false;
+    };
+    Transform::ALL[1..].iter().any(|&transform| {
+        normalize_digits(&apply_transform(board, transform)).as_ref() == Some(&normalized)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{parse, STALLED_PUZZLE};
+
+    #[test]
+    fn test_transforms_are_permutations() {
+        for transform in Transform::ALL {
+            let mut seen = [false; BOARD_SIZE];
+            for index in 0..BOARD_SIZE {
+                seen[transform.map_index(index)] = true;
+            }
+            assert!(seen.iter().all(|&hit| hit), "{:?}", transform);
+        }
+        assert_eq!(Transform::Rotate90.map_index(0), 8);
+        assert_eq!(Transform::Rotate180.map_index(0), 80);
+    }
+
+    #[test]
+    fn test_canonical_form_ignores_transform_and_relabel() {
+        let board = parse(STALLED_PUZZLE);
+        let relabeled: Vec<Option<u8>> = apply_transform(&board, Transform::Rotate270)
+            .iter()
+            .map(|cell| cell.map(|num| 10 - num))
+            .collect();
+
+        assert_eq!(canonical_form(&board), canonical_form(&relabeled));
+        assert_eq!(fingerprint(&board), fingerprint(&relabeled));
+        assert_eq!(fingerprint(&board).len(), 16);
+    }
+
+    #[test]
+    fn test_relabel_digits() {
+        let board = parse(STALLED_PUZZLE);
+        let reversed = relabel_digits(&board, &[9, 8, 7, 6, 5, 4, 3, 2, 1]).unwrap();
+        assert_eq!(reversed[0], Some(2));
+        assert_eq!(reversed[1], None);
+        assert_eq!(canonical_form(&reversed), canonical_form(&board));
+        assert_eq!(
+            relabel_digits(&board, &[1, 2, 3, 4, 5, 6, 7, 8, 9]),
+            Some(board.clone())
+        );
+
+        // Repeated, missing or out-of-range digits are not a permutation
+        assert_eq!(relabel_digits(&board, &[1, 1, 3, 4, 5, 6, 7, 8, 9]), None);
+        assert_eq!(relabel_digits(&board, &[1, 2, 3, 4, 5, 6, 7, 8]), None);
+        assert_eq!(relabel_digits(&board, &[0, 2, 3, 4, 5, 6, 7, 8, 9]), None);
+    }
+
+    #[test]
+    fn test_deduplicator_spots_equivalent_puzzles() {
+        let board = parse(STALLED_PUZZLE);
+        let mut seen = Deduplicator::new();
+        assert!(seen.insert(&board));
+        assert!(!seen.insert(&apply_transform(&board, Transform::Transpose)));
+
+        let mut other = board.clone();
+        other[0] = None;
+        assert!(seen.insert(&other));
+        assert_eq!(seen.len(), 2);
+    }
+
+    #[test]
+    fn test_automorphism_detection() {
+        // Each clue's 180° partner holds 10 minus its digit
+        let mut symmetric = vec![None; BOARD_SIZE];
+        for (index, num) in [(0, 1), (10, 3), (20, 2), (31, 4), (40, 5)] {
+            symmetric[index] = Some(num);
+            symmetric[Transform::Rotate180.map_index(index)] = Some(10 - num);
+        }
+        assert!(has_nontrivial_automorphism(&symmetric));
+
+        let generic = parse(STALLED_PUZZLE);
+        assert!(!has_nontrivial_automorphism(&generic));
+    }
+
+    #[test]
+    fn test_out_of_range_values_are_rejected() {
+        let mut board = vec![None; BOARD_SIZE];
+        board[0] = Some(12);
+        assert_eq!(normalize_digits(&board), None);
+        assert!(canonical_form(&board).is_empty());
+        assert!(!has_nontrivial_automorphism(&board));
+    }
+}
@@ -19,11 +19,96 @@ pub struct ValidationResult {
     pub is_complete: bool,
 }
 
+/// Result of checking a finished grid against its puzzle
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CompletionCheck {
+    /// Whether the submission solves the puzzle
+    pub correct: bool,
+    /// Indices of cells that are empty or wrong, in index order
+    pub wrong_cells: Vec<usize>,
+    /// Whether the puzzle lacks a unique solution, so the submission was
+    /// only checked against the rules and the givens
+    pub consistency_only: bool,
+}
+
+/// Import verdict for one puzzle of a batch
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleVerdict {
+    /// Position of the puzzle in the batch
+    pub index: usize,
+    /// Whether the board has 81 cells, values 1-9 and no repeated clues
+    pub well_formed: bool,
+    /// Whether the puzzle has exactly one solution
+    pub unique: bool,
+    /// Whether the puzzle has at least one solution
+    pub solvable: bool,
+}
+
+/// The three kinds of unit a digit must not repeat in
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitKind {
+    Row,
+    Column,
+    Box,
+}
+
+/// A unit in which some digit appears more than once
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RuleViolation {
+    /// Whether the unit is a row, column or box
+    pub kind: UnitKind,
+    /// Which row, column or box (0-8, boxes in reading order)
+    pub unit_index: usize,
+    /// Indices of the cells holding the repeated digits
+    pub cells: Vec<usize>,
+}
+
+/// Errors describing malformed board input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputError {
+    /// The board doesn't have exactly 81 cells
+    InvalidLength { expected: usize, actual: usize },
+    /// A filled cell holds something other than 1-9
+    InvalidValue { index: usize, value: u8 },
+    /// Two clues repeat a digit within a row, column or box
+    ConflictingClues { kind: UnitKind, unit_index: usize },
+    /// Two imported givens hold the same digit in one unit (cell indices 0-80)
+    ConflictingGivens { first: usize, second: usize },
+}
+
+impl std::fmt::Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::InvalidLength { expected, actual } => {
+                write!(
+                    f,
+                    "Invalid board size: expected {}, got {}",
+                    expected, actual
+                )
+            }
+            InputError::InvalidValue { index, value } => {
+                write!(f, "Invalid value {} at cell {}: expected 1-9", value, index)
+            }
+            InputError::ConflictingClues { kind, unit_index } => {
+                let kind = match kind {
+                    UnitKind::Row => "row",
+                    UnitKind::Column => "column",
+                    UnitKind::Box => "box",
+                };
+                write!(f, "Conflicting clues in {} {}", kind, unit_index)
+            }
+            InputError::ConflictingGivens { first, second } => {
+                write!(f, "Conflicting givens at cells {} and {}", first, second)
+            }
+        }
+    }
+}
+
 /// Difficulty levels for Sudoku puzzles
 ///
 /// Represents the overall difficulty rating of a puzzle based on the
 /// techniques required to solve it.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum DifficultyLevel {
     /// Requires minimal techniques (mostly naked singles, very straightforward)
     VeryEasy,
@@ -37,6 +122,58 @@ pub enum DifficultyLevel {
     Expert,
 }
 
+impl DifficultyLevel {
+    /// Name used in analysis output
+    pub fn name(&self) -> &'static str {
+        match self {
+            DifficultyLevel::VeryEasy => "VeryEasy",
+            DifficultyLevel::Easy => "Easy",
+            DifficultyLevel::Medium => "Medium",
+            DifficultyLevel::Hard => "Hard",
+            DifficultyLevel::Expert => "Expert",
+        }
+    }
+
+    /// Number used by legacy front-ends, from 1 (VeryEasy) to 5 (Expert)
+    pub fn legacy_number(&self) -> u8 {
+        *self as u8 + 1
+    }
+
+    /// Converts a legacy 1-5 difficulty number back to a level
+    ///
+    /// # Returns
+    /// The matching level, or `None` outside 1-5
+    pub fn from_legacy_number(number: u8) -> Option<Self> {
+        match number {
+            1 => Some(DifficultyLevel::VeryEasy),
+            2 => Some(DifficultyLevel::Easy),
+            3 => Some(DifficultyLevel::Medium),
+            4 => Some(DifficultyLevel::Hard),
+            5 => Some(DifficultyLevel::Expert),
+            _ => None,
+        }
+    }
+}
+
+/// Symmetries a puzzle's clue pattern can have
+///
+/// Each mode pairs every cell with a partner cell; a clue pattern has the
+/// symmetry when every clue's partner is also a clue.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SymmetryMode {
+    /// No symmetry: every cell is its own partner
+    None,
+    /// 180° rotation about the center cell
+    Rotational,
+    /// Mirror across the middle row (top and bottom swap)
+    Horizontal,
+    /// Mirror across the middle column (left and right swap)
+    Vertical,
+    /// Mirror across the main diagonal (rows and columns swap)
+    Diagonal,
+}
+
 /// Enumeration of Sudoku solving techniques ordered by difficulty
 ///
 /// Each technique represents a logical method that humans use to solve Sudoku puzzles.
@@ -59,6 +196,12 @@ pub enum SolvingTechnique {
     XWing,
     /// Advanced: three cells in a row/column point to same box
     PointingTriples,
+    /// Advanced: a box's candidates on one row and column plus a conjugate pair
+    EmptyRectangle,
+    /// Advanced: X-Wing with extra candidates confined to one box
+    FinnedXWing,
+    /// Advanced: chain of cells sharing the same two candidates
+    RemotePairs,
     /// Expert: six cells form a complex elimination pattern
     Swordfish,
     /// Expert: color-based candidate elimination
@@ -67,16 +210,207 @@ pub enum SolvingTechnique {
     XYWing,
     /// Expert: chain of bi-value cells
     XYChain,
+    /// Expert: two almost locked sets linked by a restricted common candidate
+    AlmostLockedSet,
     /// Expert: chain of strong/weak links
     ForcingChain,
     /// Last resort: guessing and backtracking
     TrialAndError,
 }
 
+/*──────── TECHNIQUE GLOSSARY ────────*/
+
+impl SolvingTechnique {
+    /// Every technique, in difficulty order
+    pub const ALL: [SolvingTechnique; 18] = [
+        SolvingTechnique::NakedSingle,
+        SolvingTechnique::HiddenSingle,
+        SolvingTechnique::NakedPair,
+        SolvingTechnique::HiddenPair,
+        SolvingTechnique::BoxLineReduction,
+        SolvingTechnique::PointingPairs,
+        SolvingTechnique::XWing,
+        SolvingTechnique::PointingTriples,
+        SolvingTechnique::EmptyRectangle,
+        SolvingTechnique::FinnedXWing,
+        SolvingTechnique::RemotePairs,
+        SolvingTechnique::Swordfish,
+        SolvingTechnique::Coloring,
+        SolvingTechnique::XYWing,
+        SolvingTechnique::XYChain,
+        SolvingTechnique::AlmostLockedSet,
+        SolvingTechnique::ForcingChain,
+        SolvingTechnique::TrialAndError,
+    ];
+
+    /// Human-readable name used in hints and analysis output
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            SolvingTechnique::NakedSingle => "Naked Single",
+            SolvingTechnique::HiddenSingle => "Hidden Single",
+            SolvingTechnique::NakedPair => "Naked Pair",
+            SolvingTechnique::HiddenPair => "Hidden Pair",
+            SolvingTechnique::BoxLineReduction => "Box/Line Reduction",
+            SolvingTechnique::PointingPairs => "Pointing Pair",
+            SolvingTechnique::XWing => "X-Wing",
+            SolvingTechnique::PointingTriples => "Pointing Triples",
+            SolvingTechnique::EmptyRectangle => "Empty Rectangle",
+            SolvingTechnique::FinnedXWing => "Finned X-Wing",
+            SolvingTechnique::RemotePairs => "Remote Pairs",
+            SolvingTechnique::Swordfish => "Swordfish",
+            SolvingTechnique::Coloring => "Coloring",
+            SolvingTechnique::XYWing => "XY-Wing",
+            SolvingTechnique::XYChain => "XY-Chain",
+            SolvingTechnique::AlmostLockedSet => "Almost Locked Set",
+            SolvingTechnique::ForcingChain => "Forcing Chain",
+            SolvingTechnique::TrialAndError => "Trial and Error",
+        }
+    }
+
+    /// Position in the difficulty ordering, starting at 1 for naked singles
+    pub fn rank(&self) -> u8 {
+        self.clone() as u8 + 1
+    }
+
+    /// Score penalty for revealing a hint that relies on this technique
+    ///
+    /// Costs never decrease along the technique ordering, so a hint that
+    /// needs a harder technique always costs at least as much.
+    pub fn hint_cost(&self) -> u32 {
+        match self {
+            SolvingTechnique::NakedSingle => 1,
+            SolvingTechnique::HiddenSingle => 2,
+            SolvingTechnique::NakedPair => 5,
+            SolvingTechnique::HiddenPair => 6,
+            SolvingTechnique::BoxLineReduction => 8,
+            SolvingTechnique::PointingPairs => 8,
+            SolvingTechnique::XWing => 20,
+            SolvingTechnique::PointingTriples => 20,
+            SolvingTechnique::EmptyRectangle => 20,
+            SolvingTechnique::FinnedXWing => 25,
+            SolvingTechnique::RemotePairs => 25,
+            SolvingTechnique::Swordfish => 30,
+            SolvingTechnique::Coloring => 35,
+            SolvingTechnique::XYWing => 40,
+            SolvingTechnique::XYChain => 50,
+            SolvingTechnique::AlmostLockedSet => 55,
+            SolvingTechnique::ForcingChain => 60,
+            SolvingTechnique::TrialAndError => 100,
+        }
+    }
+
+    /// Typical seconds a human spends on one application of this technique
+    ///
+    /// Used by time estimates when no publisher-specific weights are given.
+    pub fn default_seconds(&self) -> f64 {
+        match self {
+            SolvingTechnique::NakedSingle => 5.0,
+            SolvingTechnique::HiddenSingle => 10.0,
+            SolvingTechnique::NakedPair => 30.0,
+            SolvingTechnique::HiddenPair => 40.0,
+            SolvingTechnique::BoxLineReduction => 30.0,
+            SolvingTechnique::PointingPairs => 30.0,
+            SolvingTechnique::XWing => 60.0,
+            SolvingTechnique::PointingTriples => 45.0,
+            SolvingTechnique::EmptyRectangle => 75.0,
+            SolvingTechnique::FinnedXWing => 90.0,
+            SolvingTechnique::RemotePairs => 90.0,
+            SolvingTechnique::Swordfish => 120.0,
+            SolvingTechnique::Coloring => 150.0,
+            SolvingTechnique::XYWing => 120.0,
+            SolvingTechnique::XYChain => 180.0,
+            SolvingTechnique::AlmostLockedSet => 210.0,
+            SolvingTechnique::ForcingChain => 240.0,
+            SolvingTechnique::TrialAndError => 300.0,
+        }
+    }
+}
+
+/// A set of solving techniques stored as bit flags
+///
+/// Bit `n` is set when the technique with rank `n + 1` is in the set.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TechniqueSet(u32);
+
+impl TechniqueSet {
+    /// Creates an empty set
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    /// Creates a set of every technique harder than `technique`
+    ///
+    /// # Arguments
+    /// * `technique` - The hardest technique left out of the set
+    pub fn harder_than(technique: SolvingTechnique) -> Self {
+        SolvingTechnique::ALL
+            .into_iter()
+            .filter(|t| *t > technique)
+            .collect()
+    }
+
+    /// Adds a technique to the set
+    pub fn insert(&mut self, technique: SolvingTechnique) {
+        self.0 |= Self::bit(&technique);
+    }
+
+    /// Checks whether a technique is in the set
+    pub fn contains(&self, technique: &SolvingTechnique) -> bool {
+        self.0 & Self::bit(technique) != 0
+    }
+
+    /// Checks whether the set has no techniques
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    fn bit(technique: &SolvingTechnique) -> u32 {
+        1 << (technique.rank() - 1)
+    }
+}
+
+impl FromIterator<SolvingTechnique> for TechniqueSet {
+    fn from_iter<I: IntoIterator<Item = SolvingTechnique>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for technique in iter {
+            set.insert(technique);
+        }
+        set
+    }
+}
+
+/// How far a difficulty analysis can be trusted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Confidence {
+    /// The human-style solver finished the puzzle with implemented techniques
+    Solved,
+    /// The solver stalled, so the rating is estimated from puzzle statistics
+    Heuristic,
+    /// The board has no empty cells, so there was nothing to rate. This,
+    /// not the level, marks a finished grid: the level stays at VeryEasy
+    /// only because every analysis carries one
+    NoPuzzle,
+    /// The givens admit no completion, so the level is only a placeholder
+    Unsolvable,
+}
+
+impl Confidence {
+    /// Name used in analysis output
+    pub fn name(&self) -> &'static str {
+        match self {
+            Confidence::Solved => "Solved",
+            Confidence::Heuristic => "Heuristic",
+            Confidence::NoPuzzle => "NoPuzzle",
+            Confidence::Unsolvable => "Unsolvable",
+        }
+    }
+}
+
 /// Comprehensive analysis of a puzzle's difficulty characteristics
 #[derive(Debug, Clone)]
 pub struct DifficultyAnalysis {
-    /// Overall difficulty level
+    /// Overall difficulty level, a placeholder unless `confidence` is
+    /// [`Confidence::Solved`] or [`Confidence::Heuristic`]
     pub level: DifficultyLevel,
     /// The most advanced technique needed to solve the puzzle
     pub hardest_technique: SolvingTechnique,
@@ -84,6 +418,14 @@ pub struct DifficultyAnalysis {
     pub technique_diversity: usize,
     /// Average number of candidates per empty cell (complexity metric)
     pub branching_factor: f64,
+    /// Branching factor averaged over every pass of the logical solve
+    pub average_branching_during_solve: f64,
+    /// Placements plus elimination passes on the logical solve path
+    pub step_count: u32,
+    /// Estimated human solving time, from per-technique time weights
+    pub estimated_seconds: f64,
+    /// Whether the rating comes from a full solve or an estimate
+    pub confidence: Confidence,
 }
 
 /// Cell candidates tracking using bit flags for efficient storage and operations
@@ -139,6 +481,23 @@ impl CandidateGrid {
         self.candidates[index] = 1 << (num - 1);
     }
 
+    /// Replaces the whole candidate set of a cell
+    ///
+    /// # Arguments
+    /// * `index` - The cell index (0-80)
+    /// * `mask` - The bit flags, in the layout returned by [`Self::get_mask`]
+    ///
+    /// # Panics
+    /// If `mask` has bits set above the ninth
+    pub fn set_candidates(&mut self, index: usize, mask: u16) {
+        assert!(
+            mask & !0b111111111 == 0,
+            "Candidate mask {:#b} uses bits beyond candidate 9",
+            mask
+        );
+        self.candidates[index] = mask;
+    }
+
     /// Gets all candidate numbers for a specific cell
     ///
     /// # Arguments
@@ -156,6 +515,17 @@ impl CandidateGrid {
         result
     }
 
+    /// Gets the raw candidate bits of a cell
+    ///
+    /// # Arguments
+    /// * `index` - The cell index (0-80)
+    ///
+    /// # Returns
+    /// The bit flags, where bit `k` set means `k + 1` is a candidate
+    pub fn get_mask(&self, index: usize) -> u16 {
+        self.candidates[index]
+    }
+
     /// Counts the number of candidates for a specific cell
     ///
     /// # Arguments
@@ -3,13 +3,116 @@
 //! This module provides functions for validating Sudoku boards,
 //! checking for conflicts, and solving puzzles using backtracking.
 
-use crate::grid::{index_to_coords, is_valid_placement};
-use crate::types::{ValidationResult, BOARD_SIZE};
+use crate::grid::{
+    box_cells, column_cells, index_to_coords, peers, row_cells, unit_indices, units,
+};
+use crate::types::{
+    CompletionCheck, InputError, PuzzleVerdict, RuleViolation, UnitKind, ValidationResult,
+    BOARD_SIZE, BOX_SIZE, GRID_SIZE,
+};
+
+/// Checks that a board has 81 cells and every filled cell holds 1-9
+///
+/// `Option<u8>` can carry `Some(0)` or values above 9 when a board is built
+/// directly rather than parsed, and neither is a meaningful clue.
+///
+/// # Arguments
+/// * `board` - The board to check
+///
+/// # Returns
+/// `Ok(())` if the board is well-formed, otherwise the first problem found
+pub fn check_board_values(board: &[Option<u8>]) -> Result<(), InputError> {
+    if board.len() != BOARD_SIZE {
+        return Err(InputError::InvalidLength {
+            expected: BOARD_SIZE,
+            actual: board.len(),
+        });
+    }
+
+    match board
+        .iter()
+        .enumerate()
+        .find(|(_, cell)| matches!(cell, Some(num) if !(1..=9).contains(num)))
+    {
+        Some((index, &Some(value))) => Err(InputError::InvalidValue { index, value }),
+        _ => Ok(()),
+    }
+}
+
+/// Checks that a board is a plausible puzzle before solving or analyzing it
+///
+/// On top of [`check_board_values`], the clues themselves must not repeat a
+/// digit in any unit; such a board has no solution, and finding that out by
+/// backtracking is wasted work.
+///
+/// # Arguments
+/// * `board` - The board to check
+///
+/// # Returns
+/// `Ok(())` if the board is well-formed, otherwise the first problem found
+pub fn check_well_formed(board: &[Option<u8>]) -> Result<(), InputError> {
+    check_board_values(board)?;
+    match first_rule_violation(board) {
+        Some(violation) => Err(InputError::ConflictingClues {
+            kind: violation.kind,
+            unit_index: violation.unit_index,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Parses an imported puzzle and checks its givens
+///
+/// Whitespace is ignored; every other character must be `1-9`, or `.` or
+/// `0` for an empty cell. The givens then go through the same conflict
+/// check as [`check_well_formed`], so a puzzle that can't have a solution
+/// is rejected here rather than failing to solve later.
+///
+/// # Arguments
+/// * `text` - The puzzle as 81 symbols
+///
+/// # Returns
+/// The board, or the first problem found; a repeated digit is reported as
+/// [`InputError::ConflictingGivens`] with the two cells holding it
+pub fn parse_puzzle(text: &str) -> Result<Vec<Option<u8>>, InputError> {
+    let symbols: Vec<u8> = text.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if symbols.len() != BOARD_SIZE {
+        return Err(InputError::InvalidLength {
+            expected: BOARD_SIZE,
+            actual: symbols.len(),
+        });
+    }
+
+    let board = symbols
+        .iter()
+        .enumerate()
+        .map(|(index, &symbol)| match symbol {
+            b'.' | b'0' => Ok(None),
+            b'1'..=b'9' => Ok(Some(symbol - b'0')),
+            _ => Err(InputError::InvalidValue {
+                index,
+                value: symbol,
+            }),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if let Some(violation) = first_rule_violation(&board) {
+        let first = violation.cells[0];
+        let second = violation.cells[1..]
+            .iter()
+            .copied()
+            .find(|&index| board[index] == board[first])
+            .unwrap_or(violation.cells[1]);
+        return Err(InputError::ConflictingGivens { first, second });
+    }
+    Ok(board)
+}
 
 /// Validates the current board state and identifies any rule violations
 ///
 /// Checks each filled cell to ensure it doesn't conflict with Sudoku rules
 /// in its row, column, or 3x3 box. Also determines if the board is complete.
+/// Cells holding a value outside 1-9 are always reported as invalid.
 ///
 /// # Arguments
 /// * `board` - The current board state with Some(num) for filled cells, None for empty
@@ -29,8 +132,8 @@ pub fn validate_board(board: &[Option<u8>]) -> ValidationResult {
                 is_complete = false;
             }
             Some(num) => {
-                // Validate that this number placement is legal
-                if !is_placement_valid_at_index(board, index, num) {
+                // Out-of-range values are never legal, otherwise check the placement
+                if !(1..=9).contains(&num) || !is_placement_valid_at_index(board, index, num) {
                     invalid_indices.push(index);
                 }
             }
@@ -43,10 +146,117 @@ pub fn validate_board(board: &[Option<u8>]) -> ValidationResult {
     }
 }
 
+/// Finds the first unit that repeats a digit
+///
+/// Rows are checked first, then columns, then boxes, so the result is
+/// stable for a given board.
+///
+/// # Arguments
+/// * `board` - The board to check
+///
+/// # Returns
+/// The first violated unit with its conflicting cells, or `None` if no digit repeats
+pub fn first_rule_violation(board: &[Option<u8>]) -> Option<RuleViolation> {
+    for (position, unit) in units().iter().enumerate() {
+        let kind = match position / GRID_SIZE {
+            0 => UnitKind::Row,
+            1 => UnitKind::Column,
+            _ => UnitKind::Box,
+        };
+        let unit_index = position % GRID_SIZE;
+        let cells: Vec<usize> = unit
+            .iter()
+            .copied()
+            .filter(|&index| {
+                board[index].is_some()
+                    && unit
+                        .iter()
+                        .any(|&other| other != index && board[other] == board[index])
+            })
+            .collect();
+        if !cells.is_empty() {
+            return Some(RuleViolation {
+                kind,
+                unit_index,
+                cells,
+            });
+        }
+    }
+    None
+}
+
+/// Counts rule violations across the board
+///
+/// Every filled cell adds one for each of its row, column and box that
+/// repeats its value, so a cell clashing in both its row and its box
+/// counts twice.
+///
+/// # Arguments
+/// * `board` - The board to score
+///
+/// # Returns
+/// The total number of cell-unit conflicts
+pub fn conflict_count(board: &[Option<u8>]) -> u32 {
+    (0..BOARD_SIZE)
+        .filter(|&index| board[index].is_some())
+        .map(|index| {
+            let (row, col, box_index) = unit_indices(index);
+            [row_cells(row), column_cells(col), box_cells(box_index)]
+                .iter()
+                .filter(|unit| {
+                    unit.iter()
+                        .any(|&other| other != index && board[other] == board[index])
+                })
+                .count() as u32
+        })
+        .sum()
+}
+
+/// Recomputes conflicts after a single cell has changed
+///
+/// Only the changed cell and its peers (same row, column and box) can gain or
+/// lose a conflict when one cell is edited, so every other entry of the
+/// previous invalid set is carried over untouched.
+///
+/// # Arguments
+/// * `board` - The board state after the edit
+/// * `index` - The index of the edited cell (0-80)
+/// * `previous_invalid` - Invalid indices from before the edit
+///
+/// # Returns
+/// The updated invalid indices in ascending order
+pub fn revalidate_cell(
+    board: &[Option<u8>],
+    index: usize,
+    previous_invalid: &[usize],
+) -> Vec<usize> {
+    let mut affected = peers(index).to_vec();
+    affected.push(index);
+    affected.sort_unstable();
+
+    let mut invalid_indices: Vec<usize> = previous_invalid
+        .iter()
+        .copied()
+        .filter(|i| affected.binary_search(i).is_err())
+        .collect();
+
+    for &cell in &affected {
+        if let Some(num) = board[cell] {
+            if !is_placement_valid_at_index(board, cell, num) {
+                invalid_indices.push(cell);
+            }
+        }
+    }
+
+    invalid_indices.sort_unstable();
+    invalid_indices.dedup();
+    invalid_indices
+}
+
 /// Checks if a number placement at a specific index is valid
 ///
-/// Temporarily removes the cell value and checks if placing the number
-/// would be valid according to Sudoku rules.
+/// Compares the number against the cell's peers only, so the cell's own
+/// value never counts as a conflict.
 ///
 /// # Arguments
 /// * `board` - The current board state
@@ -56,76 +266,493 @@ pub fn validate_board(board: &[Option<u8>]) -> ValidationResult {
 /// # Returns
 /// `true` if the placement is valid
 fn is_placement_valid_at_index(board: &[Option<u8>], index: usize, num: u8) -> bool {
-    let (row, col) = index_to_coords(index);
-
-    // Create a temporary board with this cell empty to test placement
-    let mut temp_board = board.to_vec();
-    temp_board[index] = None;
+    peers(index).iter().all(|&peer| board[peer] != Some(num))
+}
 
-    is_valid_placement(&temp_board, row, col, num)
+/// Checks whether any completion of the board exists
+///
+/// Catches givens that rule out every solution without two of them
+/// clashing directly, which [`check_well_formed`] can't see. Uniqueness
+/// isn't considered.
+///
+/// # Arguments
+/// * `board` - The board to check
+///
+/// # Returns
+/// `true` if backtracking finds at least one solution
+pub fn is_solvable_at_all(board: &[Option<u8>]) -> bool {
+    count_solutions(board, 1) > 0
 }
 
 /// Checks if a puzzle has a unique solution
 ///
-/// Uses backtracking to attempt to solve the puzzle. This is a simplified
-/// uniqueness check that verifies the puzzle is solvable.
-///
 /// # Arguments
 /// * `board` - The puzzle board to check
 ///
 /// # Returns
-/// `true` if the puzzle has a solution (uniqueness not fully verified)
-///
-/// # Note
-/// This is a simplified implementation. A full uniqueness check would
-/// need to count all possible solutions, which is computationally expensive.
+/// `true` if the puzzle has exactly one solution
 pub fn has_unique_solution(board: &[Option<u8>]) -> bool {
-    let mut test_board = board.to_vec();
-    solve_board(&mut test_board)
+    unique_solution(board).is_some()
+}
+
+/// Finds the solution of a puzzle if, and only if, it is unique
+///
+/// # Arguments
+/// * `board` - The puzzle board to solve
+///
+/// # Returns
+/// The solved board, or `None` if the puzzle has no solution or several
+pub fn unique_solution(board: &[Option<u8>]) -> Option<Vec<Option<u8>>> {
+    let mut solutions = enumerate_solutions(board, 2);
+    if solutions.len() == 1 {
+        solutions.pop()
+    } else {
+        None
+    }
+}
+
+/// Solves a puzzle with the player's entries locked in as extra givens
+///
+/// Entries are kept even if wrong, so a `None` result tells the player an
+/// earlier entry is a mistake. Givens win where an entry overlaps one.
+///
+/// # Arguments
+/// * `puzzle` - The puzzle with its givens
+/// * `entries` - The player's entries; other cells are `None`
+///
+/// # Returns
+/// A solution consistent with every entry, or `None` if none exists or
+/// either board is malformed
+pub fn solve_with_locked(puzzle: &[Option<u8>], entries: &[Option<u8>]) -> Option<Vec<Option<u8>>> {
+    if entries.len() != puzzle.len() {
+        return None;
+    }
+
+    let mut board: Vec<Option<u8>> = puzzle
+        .iter()
+        .zip(entries)
+        .map(|(&given, &entry)| given.or(entry))
+        .collect();
+    if check_well_formed(&board).is_err() || !solve_board(&mut board) {
+        return None;
+    }
+    Some(board)
+}
+
+/// Looks up the solution's value at one empty cell
+///
+/// Lets a client reveal a single cell without ever seeing the rest of the
+/// solution.
+///
+/// # Arguments
+/// * `board` - The puzzle board
+/// * `index` - The cell to reveal (0-80)
+///
+/// # Returns
+/// The correct digit, or `None` if the cell is out of range or already
+/// filled, or the puzzle doesn't have exactly one solution
+pub fn solution_value_at(board: &[Option<u8>], index: usize) -> Option<u8> {
+    if board.get(index)?.is_some() {
+        return None;
+    }
+    unique_solution(board)?[index]
+}
+
+/// Checks a filled-in grid against the puzzle it was meant to solve
+///
+/// A uniquely solvable puzzle has exactly one right answer, so any cell
+/// that differs from the solution is wrong even when the grid is otherwise
+/// consistent. Puzzles without a unique solution fall back to checking the
+/// rules and that the givens were kept.
+///
+/// # Arguments
+/// * `puzzle` - The puzzle with its givens
+/// * `submission` - The player's grid
+///
+/// # Returns
+/// A `CompletionCheck` listing empty and wrong cells, or the first
+/// [`check_board_values`] problem in either board
+pub fn check_completion(
+    puzzle: &[Option<u8>],
+    submission: &[Option<u8>],
+) -> Result<CompletionCheck, InputError> {
+    check_board_values(puzzle)?;
+    check_board_values(submission)?;
+
+    let (wrong_cells, consistency_only): (Vec<usize>, bool) = match unique_solution(puzzle) {
+        Some(solution) => (
+            (0..BOARD_SIZE)
+                .filter(|&index| submission[index] != solution[index])
+                .collect(),
+            false,
+        ),
+        None => {
+            let invalid = validate_board(submission).invalid_indices;
+            let wrong = (0..BOARD_SIZE)
+                .filter(|&index| {
+                    submission[index].is_none()
+                        || puzzle[index].is_some_and(|given| submission[index] != Some(given))
+                        || invalid.contains(&index)
+                })
+                .collect();
+            (wrong, true)
+        }
+    };
+
+    Ok(CompletionCheck {
+        correct: wrong_cells.is_empty(),
+        wrong_cells,
+        consistency_only,
+    })
+}
+
+/// Checks a batch of imported puzzles before accepting them
+///
+/// Malformed boards are neither solvable nor unique; the rest are searched
+/// for up to two solutions.
+///
+/// # Arguments
+/// * `boards` - The puzzles to check
+///
+/// # Returns
+/// One verdict per input, in order
+pub fn verify_batch(boards: &[Vec<Option<u8>>]) -> Vec<PuzzleVerdict> {
+    boards
+        .iter()
+        .enumerate()
+        .map(|(index, board)| {
+            let well_formed = check_well_formed(board).is_ok();
+            let solutions = if well_formed {
+                count_solutions(board, 2)
+            } else {
+                0
+            };
+            PuzzleVerdict {
+                index,
+                well_formed,
+                unique: solutions == 1,
+                solvable: solutions > 0,
+            }
+        })
+        .collect()
+}
+
+/// Counts the solutions of a puzzle, stopping once `limit` is reached
+///
+/// # Arguments
+/// * `board` - The puzzle board to count
+/// * `limit` - Stop searching after this many solutions
+///
+/// # Returns
+/// The number of solutions found, at most `limit`. Boards with bad values
+/// or conflicting clues have 0 solutions.
+pub fn count_solutions(board: &[Option<u8>], limit: usize) -> usize {
+    enumerate_solutions(board, limit).len()
+}
+
+/// Lists the solutions of a board, stopping once `limit` are found
+///
+/// Search order is fixed, so the same board always yields the same
+/// solutions in the same order. Keep `limit` small for sparse boards: an
+/// empty grid has billions of completions.
+///
+/// # Arguments
+/// * `board` - The board to complete
+/// * `limit` - Stop searching after this many solutions
+///
+/// # Returns
+/// Up to `limit` distinct complete grids. Boards with bad values or
+/// conflicting clues have none.
+pub fn enumerate_solutions(board: &[Option<u8>], limit: usize) -> Vec<Vec<Option<u8>>> {
+    if check_board_values(board).is_err() {
+        return Vec::new();
+    }
+    enumerate_completions(board, BOX_SIZE, limit)
+}
+
+/// Lists up to `limit` completions of a grid with `box_size`-wide boxes
+///
+/// Shared by 9x9 boards and [`crate::hexadoku`]; callers check cell values
+/// for their grid size first.
+///
+/// # Arguments
+/// * `board` - The board to complete, `box_size⁴` cells long
+/// * `box_size` - Box width: 3 for Sudoku, 4 for hexadoku
+/// * `limit` - Stop searching after this many solutions
+///
+/// # Returns
+/// Up to `limit` distinct complete grids, in a fixed order; none if two
+/// clues conflict
+pub(crate) fn enumerate_completions(
+    board: &[Option<u8>],
+    box_size: usize,
+    limit: usize,
+) -> Vec<Vec<Option<u8>>> {
+    let mut solutions = Vec::new();
+    if limit == 0 {
+        return solutions;
+    }
+    let Some(mut masks) = UnitMasks::from_board(board, box_size) else {
+        return solutions;
+    };
+
+    // Fast path for high-clue boards: if forced placements alone finish the
+    // grid, no choice was ever made, so the solution is unique
+    let mut work = board.to_vec();
+    if !fill_forced_cells(&mut work, &mut masks) {
+        return solutions;
+    }
+    if find_next_empty_cell(&work).is_none() {
+        solutions.push(work);
+        return solutions;
+    }
+
+    count_with_masks(&mut work, &mut masks, limit, &mut solutions);
+    solutions
+}
+
+/// Largest grid side [`UnitMasks`] can track
+const MAX_UNIT_SIZE: usize = 16;
+
+/// Bit flags of numbers already used in each row, column and box
+///
+/// Sized for any grid up to 16x16, so the same search serves 9x9 boards
+/// and hexadokus.
+pub(crate) struct UnitMasks {
+    box_size: usize,
+    size: usize,
+    rows: [u32; MAX_UNIT_SIZE],
+    cols: [u32; MAX_UNIT_SIZE],
+    boxes: [u32; MAX_UNIT_SIZE],
+}
+
+impl UnitMasks {
+    /// Builds the masks for a board of numbers 1 to `box_size²`
+    ///
+    /// # Returns
+    /// The masks, or `None` if a unit repeats a clue
+    pub(crate) fn from_board(board: &[Option<u8>], box_size: usize) -> Option<Self> {
+        let size = box_size * box_size;
+        let mut masks = Self {
+            box_size,
+            size,
+            rows: [0; MAX_UNIT_SIZE],
+            cols: [0; MAX_UNIT_SIZE],
+            boxes: [0; MAX_UNIT_SIZE],
+        };
+        for (index, cell) in board.iter().enumerate() {
+            if let Some(num) = *cell {
+                let bit = 1u32 << (num - 1);
+                if masks.allowed(index) & bit == 0 {
+                    return None;
+                }
+                masks.place(index, bit);
+            }
+        }
+        Some(masks)
+    }
+
+    /// Gets the row, column and box of a cell
+    fn units(&self, index: usize) -> (usize, usize, usize) {
+        let (row, col) = (index / self.size, index % self.size);
+        let box_index = (row / self.box_size) * self.box_size + col / self.box_size;
+        (row, col, box_index)
+    }
+
+    /// Bit flags of the numbers still legal in a cell
+    fn allowed(&self, index: usize) -> u32 {
+        let (row, col, box_index) = self.units(index);
+        !(self.rows[row] | self.cols[col] | self.boxes[box_index]) & ((1 << self.size) - 1)
+    }
+
+    /// Marks a number as used in the cell's units
+    fn place(&mut self, index: usize, bit: u32) {
+        let (row, col, box_index) = self.units(index);
+        self.rows[row] |= bit;
+        self.cols[col] |= bit;
+        self.boxes[box_index] |= bit;
+    }
+
+    /// Marks a number as free again in the cell's units
+    fn remove(&mut self, index: usize, bit: u32) {
+        let (row, col, box_index) = self.units(index);
+        self.rows[row] &= !bit;
+        self.cols[col] &= !bit;
+        self.boxes[box_index] &= !bit;
+    }
+}
+
+/// Fills cells with exactly one legal number until none are left
+///
+/// Forced placements never rule out a solution, so counting can continue
+/// from the filled board.
+///
+/// # Arguments
+/// * `board` - The board being filled in-place
+/// * `masks` - Bit flags of numbers already used per unit
+///
+/// # Returns
+/// `false` if some empty cell has no legal number left
+fn fill_forced_cells(board: &mut [Option<u8>], masks: &mut UnitMasks) -> bool {
+    while let Some((index, allowed)) = most_constrained_empty(board, masks) {
+        match allowed.count_ones() {
+            0 => return false,
+            1 => {
+                board[index] = Some(allowed.trailing_zeros() as u8 + 1);
+                masks.place(index, allowed);
+            }
+            _ => break,
+        }
+    }
+    true
 }
 
 /// Solves a Sudoku board using backtracking algorithm
 ///
 /// This is a complete backtracking solver that finds any valid solution
-/// to the given puzzle. It modifies the board in-place.
+/// to the given puzzle. It modifies the board in-place. Branching always
+/// happens on the most constrained empty cell, which keeps sparse,
+/// low-clue boards fast to solve. Uniquely solvable and unsolvable boards
+/// come out exactly as with a plain cell-by-cell search; on boards with
+/// several solutions the completion returned can differ from that one.
 ///
 /// # Arguments
 /// * `board` - Mutable reference to the board to solve
 ///
 /// # Returns
-/// `true` if a solution was found, `false` if unsolvable
+/// `true` if a solution was found, `false` if unsolvable, if two givens
+/// conflict or if the board fails [`check_board_values`]
 pub fn solve_board(board: &mut [Option<u8>]) -> bool {
-    // Find the next empty cell
-    let empty_cell_index = find_next_empty_cell(board);
+    check_board_values(board).is_ok() && solve_in_place(board, BOX_SIZE)
+}
+
+/// Solves a grid with `box_size`-wide boxes in place
+///
+/// Shared by 9x9 boards and [`crate::hexadoku`]; callers check cell values
+/// for their grid size first.
+///
+/// # Returns
+/// `true` if a solution was found; `false` leaves the board unchanged
+pub(crate) fn solve_in_place(board: &mut [Option<u8>], box_size: usize) -> bool {
+    match UnitMasks::from_board(board, box_size) {
+        Some(mut masks) => solve_with_masks(board, &mut masks),
+        None => false,
+    }
+}
+
+/// Backtracking search over unit bitmasks, branching on the most constrained cell
+///
+/// # Arguments
+/// * `board` - The board being filled in-place
+/// * `masks` - Bit flags of numbers already used per unit
+///
+/// # Returns
+/// `true` if the board was completed
+fn solve_with_masks(board: &mut [Option<u8>], masks: &mut UnitMasks) -> bool {
+    let Some((index, allowed)) = most_constrained_empty(board, masks) else {
+        // No empty cells means the board is complete
+        return true;
+    };
+
+    // Try each legal number in ascending order
+    for num in 1..=masks.size as u8 {
+        let bit = 1u32 << (num - 1);
+        if allowed & bit == 0 {
+            continue;
+        }
+
+        board[index] = Some(num);
+        masks.place(index, bit);
+
+        if solve_with_masks(board, masks) {
+            return true;
+        }
+
+        // Backtrack - remove the number and try next
+        board[index] = None;
+        masks.remove(index, bit);
+    }
+
+    // No valid number found for this position
+    false
+}
 
-    match empty_cell_index {
+/// Counting variant of [`solve_with_masks`] that explores every branch
+///
+/// The search keeps its own stack rather than recursing, with at most one
+/// frame per empty cell, so memory use stays bounded on any board.
+///
+/// # Arguments
+/// * `limit` - Stop once this many solutions have been found
+/// * `solutions` - Receives a copy of each solution found
+fn count_with_masks(
+    board: &mut [Option<u8>],
+    masks: &mut UnitMasks,
+    limit: usize,
+    solutions: &mut Vec<Vec<Option<u8>>>,
+) {
+    // Each frame is a branching cell and the numbers not yet tried in it
+    let mut stack: Vec<(usize, u32)> = Vec::with_capacity(board.len());
+    match most_constrained_empty(board, masks) {
+        Some(frame) => stack.push(frame),
         None => {
-            // No empty cells means the board is complete
-            true
+            solutions.push(board.to_vec());
+            return;
+        }
+    }
+
+    while let Some(&(index, untried)) = stack.last() {
+        // Take back the number the last branch placed here
+        if let Some(num) = board[index].take() {
+            masks.remove(index, 1u32 << (num - 1));
         }
-        Some(index) => {
-            let (row, col) = index_to_coords(index);
 
-            // Try each number 1-9 in this position
-            for num in 1..=9 {
-                if is_valid_placement(board, row, col, num) {
-                    // Place the number
-                    board[index] = Some(num);
+        if untried == 0 || solutions.len() >= limit {
+            stack.pop();
+            continue;
+        }
 
-                    // Recursively solve the rest
-                    if solve_board(board) {
-                        return true;
-                    }
+        // Try the smallest untried number next
+        let bit = untried & untried.wrapping_neg();
+        let top = stack.len() - 1;
+        stack[top].1 &= !bit;
+        board[index] = Some(bit.trailing_zeros() as u8 + 1);
+        masks.place(index, bit);
 
-                    // Backtrack - remove the number and try next
-                    board[index] = None;
-                }
-            }
+        match most_constrained_empty(board, masks) {
+            Some(frame) => stack.push(frame),
+            None => solutions.push(board.to_vec()),
+        }
+    }
+}
+
+/// Finds the empty cell with the fewest legal numbers
+///
+/// # Returns
+/// The cell index and the bit flags of its legal numbers, or `None` if the
+/// board is full
+fn most_constrained_empty(board: &[Option<u8>], masks: &UnitMasks) -> Option<(usize, u32)> {
+    let first_empty = find_next_empty_cell(board)?;
 
-            // No valid number found for this position
-            false
+    let mut best = (first_empty, 0u32);
+    let mut best_count = u32::MAX;
+    for (index, _) in board
+        .iter()
+        .enumerate()
+        .skip(first_empty)
+        .filter(|(_, cell)| cell.is_none())
+    {
+        let allowed = masks.allowed(index);
+        let count = allowed.count_ones();
+        if count < best_count {
+            best = (index, allowed);
+            best_count = count;
+            if count <= 1 {
+                break;
+            }
         }
     }
+    Some(best)
 }
 
 /// Finds the index of the next empty cell in the board
@@ -180,12 +807,205 @@ pub fn is_board_complete(board: &[Option<u8>]) -> bool {
 /// # Returns
 /// `true` if no Sudoku rules are violated
 pub fn is_board_valid(board: &[Option<u8>]) -> bool {
-    validate_board(board).invalid_indices.is_empty()
+    is_board_valid_fast(board)
+}
+
+/// Checks for rule violations, stopping at the first one found
+///
+/// Gives the same answer as an empty `validate_board(board).invalid_indices`
+/// without collecting every invalid cell: each unit is scanned once with a
+/// bitmask of the digits seen so far.
+///
+/// # Arguments
+/// * `board` - The board to validate
+///
+/// # Returns
+/// `true` if every filled cell holds 1-9 and no unit repeats a digit
+pub fn is_board_valid_fast(board: &[Option<u8>]) -> bool {
+    units().iter().all(|unit| {
+        let mut seen = 0u16;
+        unit.iter().all(|&index| match board[index] {
+            None => true,
+            Some(num) if (1..=9).contains(&num) => {
+                let bit = 1 << num;
+                let fresh = seen & bit == 0;
+                seen |= bit;
+                fresh
+            }
+            Some(_) => false,
+        })
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::grid::is_valid_placement;
+    use crate::test_fixtures::{parse, SINGLES_PUZZLE, SOLVED_GRID, STALLED_PUZZLE};
+
+    /// Plain backtracking in cell order, as `solve_board` worked before it
+    /// branched on the most constrained cell
+    fn reference_solve(board: &mut [Option<u8>]) -> bool {
+        let Some(index) = find_next_empty_cell(board) else {
+            return true;
+        };
+        let (row, col) = index_to_coords(index);
+        for num in 1..=9 {
+            if is_valid_placement(board, row, col, num) {
+                board[index] = Some(num);
+                if reference_solve(board) {
+                    return true;
+                }
+                board[index] = None;
+            }
+        }
+        false
+    }
+
+    #[test]
+    fn test_check_well_formed_rejects_duplicate_clues() {
+        let mut board = vec![None; BOARD_SIZE];
+        board[0] = Some(5);
+        board[40] = Some(5);
+        assert_eq!(check_well_formed(&board), Ok(()));
+
+        board[76] = Some(5);
+        let err = check_well_formed(&board).unwrap_err();
+        assert_eq!(
+            err,
+            InputError::ConflictingClues {
+                kind: UnitKind::Column,
+                unit_index: 4
+            }
+        );
+        assert_eq!(err.to_string(), "Conflicting clues in column 4");
+
+        board[76] = Some(10);
+        assert!(matches!(
+            check_well_formed(&board),
+            Err(InputError::InvalidValue { index: 76, .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_batch_flags() {
+        let unique = parse(SINGLES_PUZZLE);
+        let mut duplicate_clues = vec![None; BOARD_SIZE];
+        duplicate_clues[0] = Some(3);
+        duplicate_clues[1] = Some(3);
+        let boards = vec![
+            unique,
+            vec![None; BOARD_SIZE],
+            duplicate_clues,
+            vec![None; 80],
+        ];
+
+        let verdicts = verify_batch(&boards);
+        let flags: Vec<(usize, bool, bool, bool)> = verdicts
+            .iter()
+            .map(|v| (v.index, v.well_formed, v.unique, v.solvable))
+            .collect();
+        assert_eq!(
+            flags,
+            vec![
+                (0, true, true, true),
+                (1, true, false, true),
+                (2, false, false, false),
+                (3, false, false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_solve_with_locked_detects_wrong_entry() {
+        let puzzle = parse(SINGLES_PUZZLE);
+        let solution = unique_solution(&puzzle).unwrap();
+        let empty = (0..BOARD_SIZE).find(|&i| puzzle[i].is_none()).unwrap();
+
+        let mut entries = vec![None; BOARD_SIZE];
+        entries[empty] = solution[empty];
+        assert_eq!(solve_with_locked(&puzzle, &entries), Some(solution.clone()));
+
+        // A digit that breaks no rule yet, but isn't the solution's
+        let wrong = (1..=9)
+            .find(|&value| {
+                Some(value) != solution[empty] && is_placement_valid_at_index(&puzzle, empty, value)
+            })
+            .unwrap();
+        entries[empty] = Some(wrong);
+        assert_eq!(solve_with_locked(&puzzle, &entries), None);
+    }
+
+    #[test]
+    fn test_check_completion() {
+        let puzzle = parse(SINGLES_PUZZLE);
+        let solution = unique_solution(&puzzle).unwrap();
+        let result = check_completion(&puzzle, &solution).unwrap();
+        assert!(result.correct && result.wrong_cells.is_empty() && !result.consistency_only);
+
+        // Swapping two digits everywhere keeps the grid valid, but it is not the solution
+        let swapped: Vec<Option<u8>> = solution
+            .iter()
+            .map(|cell| {
+                cell.map(|num| match num {
+                    1 => 2,
+                    2 => 1,
+                    other => other,
+                })
+            })
+            .collect();
+        assert!(is_board_valid(&swapped) && is_board_complete(&swapped));
+        let result = check_completion(&puzzle, &swapped).unwrap();
+        assert!(!result.correct);
+        assert_eq!(result.wrong_cells.len(), 18);
+
+        // Without a unique solution any valid grid that keeps the givens passes
+        let empty = vec![None; BOARD_SIZE];
+        let result = check_completion(&empty, &swapped).unwrap();
+        assert!(result.correct && result.consistency_only);
+
+        let mut clashing = swapped.clone();
+        clashing[0] = clashing[1];
+        let result = check_completion(&empty, &clashing).unwrap();
+        assert!(!result.correct && result.wrong_cells.contains(&0));
+
+        // Short or out-of-range boards are rejected rather than indexed
+        assert_eq!(
+            check_completion(&puzzle, &swapped[..80]),
+            Err(InputError::InvalidLength {
+                expected: BOARD_SIZE,
+                actual: 80
+            })
+        );
+        let mut out_of_range = swapped.clone();
+        out_of_range[5] = Some(10);
+        assert!(check_completion(&out_of_range, &swapped).is_err());
+    }
+
+    #[test]
+    fn test_fast_validity_agrees_with_validate_board() {
+        let puzzle = parse(SINGLES_PUZZLE);
+        let solution = unique_solution(&puzzle).unwrap();
+
+        let mut boards = vec![vec![None; BOARD_SIZE], puzzle.clone(), solution.clone()];
+        for (index, value) in [(0, 5), (1, 2), (40, 9), (80, 3)] {
+            let mut clashing = solution.clone();
+            clashing[index] = Some(value);
+            boards.push(clashing);
+        }
+        let mut out_of_range = puzzle.clone();
+        out_of_range[0] = Some(10);
+        boards.push(out_of_range);
+
+        for board in &boards {
+            assert_eq!(
+                is_board_valid_fast(board),
+                validate_board(board).invalid_indices.is_empty()
+            );
+        }
+        assert!(is_board_valid_fast(&solution));
+        assert!(!is_board_valid_fast(&boards[3]));
+    }
 
     #[test]
     fn test_validate_empty_board() {
@@ -195,6 +1015,81 @@ mod tests {
         assert!(!result.is_complete);
     }
 
+    #[test]
+    fn test_solvable_at_all_sees_indirect_contradictions() {
+        // Row 0 holds 1-7, and 9 is already in columns 7 and 8, so both
+        // remaining cells of the row can only take 8
+        let mut board = vec![None; BOARD_SIZE];
+        for (col, cell) in board.iter_mut().take(7).enumerate() {
+            *cell = Some(col as u8 + 1);
+        }
+        board[5 * GRID_SIZE + 7] = Some(9);
+        board[6 * GRID_SIZE + 8] = Some(9);
+
+        assert_eq!(check_well_formed(&board), Ok(()));
+        assert!(!is_solvable_at_all(&board));
+
+        board[6 * GRID_SIZE + 8] = None;
+        assert!(is_solvable_at_all(&board));
+        assert!(!has_unique_solution(&board));
+    }
+
+    #[test]
+    fn test_parse_puzzle_reports_conflicting_givens() {
+        let text = SINGLES_PUZZLE;
+        let board = parse_puzzle(text).unwrap();
+        assert_eq!(board[4], Some(5));
+        assert!(has_unique_solution(&board));
+
+        // A second 6 in the first row, which already has one at cell 6
+        let duplicate = format!("6{}", &text[1..]);
+        let err = parse_puzzle(&duplicate).unwrap_err();
+        assert_eq!(
+            err,
+            InputError::ConflictingGivens {
+                first: 0,
+                second: 6
+            }
+        );
+        assert_eq!(err.to_string(), "Conflicting givens at cells 0 and 6");
+
+        assert!(matches!(
+            parse_puzzle(&text[1..]),
+            Err(InputError::InvalidLength { actual: 80, .. })
+        ));
+    }
+
+    #[test]
+    fn test_out_of_range_values_rejected() {
+        let mut board = vec![None; BOARD_SIZE];
+        board[3] = Some(0);
+
+        assert_eq!(
+            check_board_values(&board),
+            Err(InputError::InvalidValue { index: 3, value: 0 })
+        );
+        assert_eq!(validate_board(&board).invalid_indices, vec![3]);
+        assert!(!solve_board(&mut board.clone()));
+
+        board[3] = Some(10);
+        assert_eq!(
+            check_board_values(&board),
+            Err(InputError::InvalidValue {
+                index: 3,
+                value: 10
+            })
+        );
+        assert!(!is_board_valid(&board));
+
+        assert_eq!(
+            check_board_values(&board[..80]),
+            Err(InputError::InvalidLength {
+                expected: 81,
+                actual: 80
+            })
+        );
+    }
+
     #[test]
     fn test_count_functions() {
         let mut board = vec![None; BOARD_SIZE];
@@ -206,6 +1101,218 @@ mod tests {
         assert!(!is_board_complete(&board));
     }
 
+    #[test]
+    fn test_revalidate_cell_only_touches_peers() {
+        let mut board = vec![None; BOARD_SIZE];
+        // Conflict far away from the edit: two 7s in the bottom row
+        board[72] = Some(7);
+        board[80] = Some(7);
+        board[0] = Some(5);
+        let before = validate_board(&board).invalid_indices;
+        assert_eq!(before, vec![72, 80]);
+
+        // Introduce a row conflict with cell 0
+        board[4] = Some(5);
+        let after = revalidate_cell(&board, 4, &before);
+        assert_eq!(after, vec![0, 4, 72, 80]);
+        assert_eq!(after, validate_board(&board).invalid_indices);
+
+        // Clearing the edit removes only the conflicts it caused
+        board[4] = None;
+        let cleared = revalidate_cell(&board, 4, &after);
+        assert_eq!(cleared, vec![72, 80]);
+    }
+
+    #[test]
+    fn test_solve_board_matches_reference_search() {
+        let puzzles = [
+            SINGLES_PUZZLE,
+            STALLED_PUZZLE,
+            ".5.4....7....83.51..32.596442.8.1..9.........5..3.4.166187.24..24.56....3....9.2.",
+            // Medium with a 1 added in the corner: no direct conflict, no solution
+            "1...5.62.....675..7...23.419.8.35.7.64..1..58.5.78.2.647.19...5..934.....82.7....",
+        ];
+        for puzzle in puzzles {
+            let board = parse_puzzle(puzzle).unwrap();
+            let (mut expected, mut actual) = (board.clone(), board.clone());
+            assert_eq!(solve_board(&mut actual), reference_solve(&mut expected));
+            assert_eq!(actual, expected, "{}", puzzle);
+        }
+
+        // Several solutions: both searches complete the givens, maybe differently
+        let mut sparse = vec![None; BOARD_SIZE];
+        sparse[40] = Some(5);
+        let mut solved = sparse.clone();
+        assert!(solve_board(&mut solved));
+        assert!(is_board_complete(&solved) && is_board_valid(&solved));
+        assert_eq!(solved[40], Some(5));
+    }
+
+    #[test]
+    fn test_enumerate_solutions_order_is_fixed() {
+        // Expected lists were recorded from the recursive search this replaced
+        let cases: [(&str, usize, &[&str]); 3] = [
+            (
+                "534..8912672195348198342567859..1423426853791713924856961537284287419635345286179",
+                10,
+                &[
+                    "534678912672195348198342567859761423426853791713924856961537284287419635345286179",
+                    "534768912672195348198342567859671423426853791713924856961537284287419635345286179",
+                ],
+            ),
+            (
+                ".......................3.419.8.35.7.64..1..58.5.78.2.647.19...5..934.....82.7....",
+                5,
+                &[
+                    "734851962291467583865923741928635174647219358153784296476192835519348627382576419",
+                    "234951687891467523765823941928635174647219358153784296476192835519348762382576419",
+                    "234961587891457623765823941928635174647219358153784296476192835519348762382576419",
+                    "834951627291467583765823941928635174647219358153784296476192835519348762382576419",
+                    "834951762291467583765823941928635174647219358153784296476192835519348627382576419",
+                ],
+            ),
+            (
+                "........................................5........................................",
+                3,
+                &[
+                    "236415789589627134147839256752341968968752341413968572624173895395284617871596423",
+                    "236415789589627134147839256752341968968752413314968527895173642671284395423596871",
+                    "236415789589627134147839256752341968968752413413968527624173895395284671871596342",
+                ],
+            ),
+        ];
+
+        for (puzzle, limit, expected) in cases {
+            let expected: Vec<Vec<Option<u8>>> = expected
+                .iter()
+                .map(|grid| parse_puzzle(grid).unwrap())
+                .collect();
+            assert_eq!(
+                enumerate_solutions(&parse_puzzle(puzzle).unwrap(), limit),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_enumerate_solutions_lists_both_completions() {
+        let solution = parse(SOLVED_GRID);
+        // 6 and 7 can swap across rows 0 and 3 in columns 3 and 4
+        let mut board = solution.clone();
+        for index in [3, 4, 30, 31] {
+            board[index] = None;
+        }
+
+        let solutions = enumerate_solutions(&board, 10);
+        assert_eq!(solutions.len(), 2);
+        assert_ne!(solutions[0], solutions[1]);
+        assert!(solutions.contains(&solution));
+        for grid in &solutions {
+            assert!(is_board_complete(grid) && is_board_valid(grid));
+        }
+
+        assert_eq!(enumerate_solutions(&board, 1).len(), 1);
+        assert!(enumerate_solutions(&board, 0).is_empty());
+    }
+
+    #[test]
+    fn test_count_solutions() {
+        let solution = parse(SOLVED_GRID);
+        let mut puzzle = solution.clone();
+        for index in (0..BOARD_SIZE).step_by(4) {
+            puzzle[index] = None;
+        }
+        assert_eq!(count_solutions(&puzzle, 2), 1);
+        assert_eq!(unique_solution(&puzzle), Some(solution.clone()));
+        assert!(has_unique_solution(&puzzle));
+
+        // An empty board has many solutions; counting stops at the limit
+        let empty = vec![None; BOARD_SIZE];
+        assert_eq!(count_solutions(&empty, 5), 5);
+        assert!(!has_unique_solution(&empty));
+        assert_eq!(unique_solution(&empty), None);
+
+        // Conflicting clues have no solutions
+        let mut conflicting = empty.clone();
+        conflicting[0] = Some(1);
+        conflicting[1] = Some(1);
+        assert_eq!(count_solutions(&conflicting, 2), 0);
+    }
+
+    #[test]
+    fn test_forced_cells_fast_path() {
+        let solution = parse(SOLVED_GRID);
+        let mut high_clue = solution.clone();
+        for index in (0..BOARD_SIZE).step_by(4) {
+            high_clue[index] = None;
+        }
+
+        // Forced placements alone finish a high-clue board
+        let mut masks = UnitMasks::from_board(&high_clue, BOX_SIZE).unwrap();
+        let mut work = high_clue.clone();
+        assert!(fill_forced_cells(&mut work, &mut masks));
+        assert_eq!(work, solution);
+        assert_eq!(unique_solution(&high_clue), Some(solution));
+
+        // A sparse board needs the branching search
+        let empty = vec![None; BOARD_SIZE];
+        let mut masks = UnitMasks::from_board(&empty, BOX_SIZE).unwrap();
+        let mut work = empty.clone();
+        assert!(fill_forced_cells(&mut work, &mut masks));
+        assert_eq!(work, empty);
+    }
+
+    #[test]
+    fn test_first_rule_violation() {
+        let solution = parse(SOLVED_GRID);
+        assert_eq!(first_rule_violation(&solution), None);
+
+        // Swapping two digits between rows 1 and 2 breaks row 1 first
+        let mut wrong = solution.clone();
+        wrong[9] = Some(1);
+        wrong[18] = Some(6);
+        assert_eq!(
+            first_rule_violation(&wrong),
+            Some(RuleViolation {
+                kind: UnitKind::Row,
+                unit_index: 1,
+                cells: vec![9, 12],
+            })
+        );
+    }
+
+    #[test]
+    fn test_conflict_count() {
+        let mut board = vec![None; BOARD_SIZE];
+        assert_eq!(conflict_count(&board), 0);
+
+        // Two 5s sharing row 0 and box 0: each cell conflicts twice
+        board[0] = Some(5);
+        board[1] = Some(5);
+        assert_eq!(conflict_count(&board), 4);
+
+        // A third 5 in column 0 adds a column conflict for it and cell 0
+        board[36] = Some(5);
+        assert_eq!(conflict_count(&board), 6);
+    }
+
+    #[test]
+    fn test_solution_value_at() {
+        let solution = parse(SOLVED_GRID);
+        let mut puzzle = solution.clone();
+        for index in (0..BOARD_SIZE).step_by(4) {
+            puzzle[index] = None;
+        }
+
+        assert_eq!(solution_value_at(&puzzle, 0), Some(5));
+        assert_eq!(solution_value_at(&puzzle, 40), solution[40]);
+        // Givens and out-of-range cells reveal nothing
+        assert_eq!(solution_value_at(&puzzle, 1), None);
+        assert_eq!(solution_value_at(&puzzle, BOARD_SIZE), None);
+        // Neither does an ambiguous puzzle
+        assert_eq!(solution_value_at(&[None; BOARD_SIZE], 0), None);
+    }
+
     #[test]
     fn test_find_next_empty_cell() {
         let mut board = vec![Some(1); BOARD_SIZE];
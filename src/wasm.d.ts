@@ -10,6 +10,7 @@ declare module "/assets/sudoku_wasm.js" {
 	): Promise<void>;
 	export function createBoard(): Uint8Array;
 	export function createGameWithSeed(difficulty: number, seed: bigint): any;
+	export function createGameWithAnalysisSeed(difficulty: number, seed: bigint): any;
 	export function validateBoard(board: any): ValidationResult;
 	export function solveBoard(board: any): Uint8Array;
 }
@@ -18,6 +19,7 @@ declare module "./pkg/sudoku_wasm.js" {
 	export default function init(): Promise<void>;
 	export function createBoard(): Uint8Array;
 	export function createGameWithSeed(difficulty: number, seed: bigint): any;
+	export function createGameWithAnalysisSeed(difficulty: number, seed: bigint): any;
 	export function validateBoard(board: any): ValidationResult;
 	export function solveBoard(board: any): Uint8Array;
 }
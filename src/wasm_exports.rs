@@ -3,19 +3,66 @@
 //! This module provides the public interface that JavaScript can call
 //! to interact with the Sudoku solver and generator.
 
+use std::collections::HashMap;
+
 use js_sys::Array;
 use rand::rngs::SmallRng;
 use rand::seq::SliceRandom;
-use rand::SeedableRng;
 use wasm_bindgen::prelude::*;
 use web_sys::console;
 
-use crate::difficulty::analyze_difficulty;
-use crate::generator::{GeneratorConfig, PuzzleGenerator};
-use crate::solver::HumanStyleSolver;
-use crate::types::{DifficultyLevel, SolvingTechnique, BOARD_SIZE};
+use crate::delta::{self, BoardDelta};
+use crate::difficulty::{
+    analyze_batch as internal_analyze_batch, analyze_difficulty, analyze_difficulty_easiest,
+    analyze_difficulty_weighted, analyze_difficulty_with_thresholds,
+    clue_removal_impact as internal_clue_removal_impact, DifficultyThresholds,
+};
+use crate::generator::{
+    calibration_report as internal_calibration_report,
+    clue_balance_score as internal_clue_balance_score,
+    complete_to_puzzle as internal_complete_to_puzzle, detect_symmetry as internal_detect_symmetry,
+    generate_distinct_puzzles, generate_for_curriculum as internal_generate_for_curriculum,
+    generate_for_time_band as internal_generate_for_time_band,
+    generate_requiring_technique as internal_generate_requiring_technique, minimal_puzzle,
+    nth_solution_grid as internal_nth_solution_grid,
+    regenerate_similar as internal_regenerate_similar,
+    strip_redundant_to_difficulty as internal_strip_redundant_to_difficulty, ConfigError,
+    Constraint, GeneratorConfig, PuzzleGenerator,
+};
+use crate::grid::{
+    box_cells, column_cells, crosshatch as internal_crosshatch, row_cells, unit_indices,
+};
+use crate::hexadoku::{self, HEX_BOARD_SIZE};
+use crate::rng::make_rng;
+use crate::solver::{
+    all_immediate_placements as internal_all_immediate_placements,
+    annotated_solution as internal_annotated_solution, candidate_masks, count_required_guesses,
+    explain_hint, explain_step, find_fish, find_hint,
+    first_move_region as internal_first_move_region,
+    implemented_techniques as internal_implemented_techniques,
+    solve_path_length as internal_solve_path_length, solve_trace as internal_solve_trace,
+    solve_until_stuck as internal_solve_until_stuck,
+    technique_milestones as internal_technique_milestones,
+    trivially_forced_cells as internal_trivially_forced_cells, HumanStyleSolver,
+    IMPLEMENTED_TECHNIQUES,
+};
+use crate::transform::{
+    fingerprint, has_nontrivial_automorphism as internal_has_nontrivial_automorphism,
+    relabel_digits as internal_relabel_digits,
+};
+use crate::types::{
+    DifficultyAnalysis, DifficultyLevel, InputError, SolvingTechnique, UnitKind, BOARD_SIZE,
+    BOX_SIZE, GRID_SIZE,
+};
 use crate::validator::{
-    has_unique_solution, solve_board, validate_board as internal_validate_board,
+    check_board_values, check_completion, check_well_formed,
+    conflict_count as internal_conflict_count, count_solutions,
+    enumerate_solutions as internal_enumerate_solutions,
+    first_rule_violation as internal_first_rule_violation, has_unique_solution,
+    is_board_valid_fast, is_solvable_at_all as internal_is_solvable_at_all, parse_puzzle,
+    revalidate_cell as internal_revalidate_cell, solution_value_at as internal_solution_value_at,
+    solve_board, solve_with_locked as internal_solve_with_locked, unique_solution,
+    validate_board as internal_validate_board, verify_batch as internal_verify_batch,
 };
 
 /// JavaScript-compatible representation of a Sudoku board
@@ -48,13 +95,114 @@ fn from_js_board(js_board: &[u8]) -> Vec<Option<u8>> {
         .collect()
 }
 
+/// Convert an `undefined`-style JavaScript array to internal format
+///
+/// Legacy exports pass boards as arrays holding numbers for filled cells
+/// and `undefined` (or `null`) for empty ones.
+///
+/// # Arguments
+/// * `arr` - The JavaScript array of 81 cells
+///
+/// # Returns
+/// Internal board format, or the first length or value problem found
+pub fn board_from_js_array(arr: &Array) -> Result<Vec<Option<u8>>, InputError> {
+    board_from_cells(
+        arr.iter()
+            .map(|cell| {
+                if cell.is_undefined() || cell.is_null() {
+                    None
+                } else {
+                    Some(cell.as_f64().unwrap_or(f64::NAN))
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Convert an internal board to an `undefined`-style JavaScript array
+///
+/// # Arguments
+/// * `board` - Internal board with Option<u8> values
+///
+/// # Returns
+/// A JavaScript array with numbers for filled cells and `undefined` for empty ones
+pub fn board_to_js_array(board: &[Option<u8>]) -> Array {
+    board
+        .iter()
+        .map(|cell| match cell {
+            Some(num) => JsValue::from(*num),
+            None => JsValue::undefined(),
+        })
+        .collect()
+}
+
+/// Validate the cells of an `undefined`-style array, `None` standing for empty
+fn board_from_cells(cells: Vec<Option<f64>>) -> Result<Vec<Option<u8>>, InputError> {
+    if cells.len() != BOARD_SIZE {
+        return Err(InputError::InvalidLength {
+            expected: BOARD_SIZE,
+            actual: cells.len(),
+        });
+    }
+
+    cells
+        .into_iter()
+        .enumerate()
+        .map(|(index, cell)| match cell {
+            None => Ok(None),
+            Some(num) if num.fract() == 0.0 && (1.0..=9.0).contains(&num) => Ok(Some(num as u8)),
+            Some(num) => Err(InputError::InvalidValue {
+                index,
+                value: num as u8,
+            }),
+        })
+        .collect()
+}
+
+/// Serialize a value into a plain JavaScript value
+fn to_js_value<T: serde::Serialize>(value: &T) -> JsValue {
+    serde_wasm_bindgen::to_value(value).unwrap_or(JsValue::NULL)
+}
+
+/// Build a `{ error: message }` object for JavaScript callers
+fn js_error(message: &str) -> JsValue {
+    let js_result = js_sys::Object::new();
+    js_sys::Reflect::set(&js_result, &"error".into(), &JsValue::from(message)).unwrap();
+    js_result.into()
+}
+
+/// Look up a technique by its display name, e.g. `"X-Wing"`
+fn technique_from_name(name: &str) -> Option<SolvingTechnique> {
+    SolvingTechnique::ALL
+        .into_iter()
+        .find(|technique| technique.display_name() == name)
+}
+
+/// Convert a JavaScript difficulty number to a difficulty level
+///
+/// # Arguments
+/// * `difficulty` - Difficulty level (0=Easy, 1=Medium, 2=Hard, 3=Expert)
+///
+/// # Returns
+/// The matching level, or `None` for unknown numbers
+fn difficulty_from_u8(difficulty: u8) -> Option<DifficultyLevel> {
+    match difficulty {
+        0 => Some(DifficultyLevel::Easy),
+        1 => Some(DifficultyLevel::Medium),
+        2 => Some(DifficultyLevel::Hard),
+        3 => Some(DifficultyLevel::Expert),
+        _ => None,
+    }
+}
+
 /// Generate a new Sudoku puzzle with the specified difficulty
 ///
 /// # Arguments
 /// * `difficulty` - Difficulty level (0=Easy, 1=Medium, 2=Hard, 3=Expert)
 ///
 /// # Returns
-/// A new puzzle as a flat array of 81 numbers (0 for empty cells)
+/// A new puzzle as a flat array of 81 numbers (0 for empty cells), or an
+/// all-empty board if generation fails
 ///
 /// # JavaScript Example
 /// ```javascript
@@ -65,20 +213,14 @@ fn from_js_board(js_board: &[u8]) -> Vec<Option<u8>> {
 pub fn generate_puzzle(difficulty: u8) -> Vec<u8> {
     console::log_1(&format!("Generating puzzle with difficulty level {}", difficulty).into());
 
-    let difficulty_level = match difficulty {
-        0 => DifficultyLevel::Easy,
-        1 => DifficultyLevel::Medium,
-        2 => DifficultyLevel::Hard,
-        3 => DifficultyLevel::Expert,
-        _ => {
-            console::log_1(&"Invalid difficulty level, using Medium".into());
-            DifficultyLevel::Medium
-        }
-    };
+    let difficulty_level = difficulty_from_u8(difficulty).unwrap_or_else(|| {
+        console::log_1(&"Invalid difficulty level, using Medium".into());
+        DifficultyLevel::Medium
+    });
 
     let generator = PuzzleGenerator::with_difficulty(difficulty_level);
 
-    match generator.generate() {
+    match generator.generate_with_relaxation() {
         Some(puzzle) => {
             let js_board = to_js_board(&puzzle);
             console::log_1(
@@ -97,36 +239,210 @@ pub fn generate_puzzle(difficulty: u8) -> Vec<u8> {
     }
 }
 
+/// A generated puzzle with the level it analyzes as
+#[derive(serde::Serialize)]
+struct LeveledPuzzleRecord {
+    puzzle: Vec<u8>,
+    level: &'static str,
+    level_numeric: u8,
+}
+
+/// Generate a puzzle and report the level it actually analyzes as
+///
+/// Puzzles whose analyzed level misses the requested one are regenerated a
+/// few times; if none matches, the closest is returned, so check `level`
+/// rather than assuming the request was met.
+///
+/// # Arguments
+/// * `difficulty` - Difficulty level (0=Easy, 1=Medium, 2=Hard, 3=Expert)
+/// * `seed` - Seed for reproducible generation
+///
+/// # Returns
+/// `{ puzzle, level, level_numeric }`, or `{ error }` if generation failed
+///
+/// # JavaScript Example
+/// ```javascript
+/// const result = generate_puzzle_on_level(2, 42n);
+/// if (result.level !== "Hard") console.log(`Got a ${result.level} puzzle instead`);
+/// ```
+#[wasm_bindgen]
+pub fn generate_puzzle_on_level(difficulty: u8, seed: u64) -> JsValue {
+    let difficulty_level = difficulty_from_u8(difficulty).unwrap_or(DifficultyLevel::Medium);
+    match PuzzleGenerator::with_difficulty(difficulty_level).generate_on_level_with_seed(seed) {
+        Some((puzzle, level)) => to_js_value(&LeveledPuzzleRecord {
+            puzzle: to_js_board(&puzzle),
+            level: level.name(),
+            level_numeric: level.legacy_number(),
+        }),
+        None => js_error("Puzzle generation failed"),
+    }
+}
+
+/// Two puzzles for head-to-head play, as returned by [`generate_mirror_pair`]
+#[derive(serde::Serialize)]
+struct MirrorPairRecord {
+    first: Vec<u8>,
+    second: Vec<u8>,
+    level: &'static str,
+}
+
+/// Generate two differently looking puzzles with identical difficulty
+///
+/// The second puzzle is a rotated or reflected copy of the first with its
+/// digits relabeled, checked to have the same analyzed level and solve
+/// path, so neither player in a head-to-head match has an advantage.
+///
+/// # Arguments
+/// * `difficulty` - Difficulty level (0=Easy, 1=Medium, 2=Hard, 3=Expert)
+/// * `seed` - Seed for reproducible generation; share it so both players
+///   get the same pair
+///
+/// # Returns
+/// `{ first, second, level }`, or `{ error }` if generation failed
+///
+/// # JavaScript Example
+/// ```javascript
+/// const pair = generate_mirror_pair(2, dailySeed);
+/// loadPuzzle(isHost ? pair.first : pair.second);
+/// ```
+#[wasm_bindgen]
+pub fn generate_mirror_pair(difficulty: u8, seed: u64) -> JsValue {
+    let difficulty_level = difficulty_from_u8(difficulty).unwrap_or(DifficultyLevel::Medium);
+    match PuzzleGenerator::with_difficulty(difficulty_level).generate_mirror_pair_with_seed(seed) {
+        Some(pair) => to_js_value(&MirrorPairRecord {
+            first: to_js_board(&pair.first),
+            second: to_js_board(&pair.second),
+            level: analyze_difficulty(&pair.first).level.name(),
+        }),
+        None => js_error("Puzzle generation failed"),
+    }
+}
+
+/// A stateful source of puzzles that generates one at a time
+///
+/// Lets long batches be produced incrementally instead of blocking on the
+/// whole batch at once.
+///
+/// # JavaScript Example
+/// ```javascript
+/// const stream = new PuzzleStream(2, 1234n);
+/// const first = stream.next();
+/// const second = stream.next(); // undefined if generation failed
+/// ```
+#[wasm_bindgen]
+pub struct PuzzleStream {
+    generator: PuzzleGenerator,
+    rng: SmallRng,
+}
+
+#[wasm_bindgen]
+impl PuzzleStream {
+    /// Create a stream of puzzles
+    ///
+    /// # Arguments
+    /// * `difficulty` - Difficulty level (0=Easy, 1=Medium, 2=Hard, 3=Expert)
+    /// * `seed` - Seed for a reproducible sequence of puzzles
+    #[wasm_bindgen(constructor)]
+    pub fn new(difficulty: u8, seed: u64) -> PuzzleStream {
+        let difficulty_level = difficulty_from_u8(difficulty).unwrap_or(DifficultyLevel::Medium);
+        PuzzleStream {
+            generator: PuzzleGenerator::with_difficulty(difficulty_level),
+            rng: make_rng(Some(seed)),
+        }
+    }
+
+    /// Generate the next puzzle in the stream
+    ///
+    /// # Returns
+    /// A flat array of 81 numbers, or `undefined` if generation failed
+    #[wasm_bindgen(js_name = next)]
+    pub fn next_puzzle(&mut self) -> Option<Vec<u8>> {
+        self.generator
+            .generate_with_relaxation_rng(&mut self.rng)
+            .map(|puzzle| to_js_board(&puzzle))
+    }
+}
+
+/// Generate a batch of puzzles with no repeats
+///
+/// Puzzles that are rotations, reflections or digit relabelings of one
+/// already in the batch are skipped and generation retried.
+///
+/// # Arguments
+/// * `difficulty` - Difficulty level (0=Easy, 1=Medium, 2=Hard, 3=Expert)
+/// * `count` - Number of puzzles wanted
+/// * `seed` - Seed for a reproducible batch
+///
+/// # Returns
+/// An array of up to `count` puzzles, each a flat array of 81 numbers;
+/// shorter if generation kept failing or repeating
+///
+/// # JavaScript Example
+/// ```javascript
+/// const pack = generate_puzzle_batch(0, 20, 2024n);
+/// console.log(`Got ${pack.length} distinct puzzles`);
+/// ```
+#[wasm_bindgen]
+pub fn generate_puzzle_batch(difficulty: u8, count: u32, seed: u64) -> JsValue {
+    let difficulty_level = difficulty_from_u8(difficulty).unwrap_or(DifficultyLevel::Medium);
+    let puzzles: Vec<Vec<u8>> = generate_distinct_puzzles(difficulty_level, count as usize, seed)
+        .iter()
+        .map(|puzzle| to_js_board(puzzle))
+        .collect();
+    to_js_value(&puzzles)
+}
+
 /// Generate a puzzle with custom configuration
 ///
 /// # Arguments
 /// * `difficulty` - Target difficulty level (0-3)
-/// * `min_clues` - Minimum number of clues
-/// * `max_clues` - Maximum number of clues
+/// * `min_clues` - Minimum number of clues (at least 17)
+/// * `max_clues` - Maximum number of clues (at most 80)
 /// * `prefer_symmetry` - Whether to prefer symmetric patterns
 ///
 /// # Returns
-/// A new puzzle as a flat array of 81 numbers
+/// A new puzzle as a flat array of 81 numbers, or `{ error }` if
+/// `min_clues` is below 17, `max_clues` is above 80, `min_clues` is above
+/// `max_clues`, or generation failed. Open puzzles for guided
+/// play, with 46 clues or more, skip the branching factor limits.
+///
+/// # JavaScript Example
+/// ```javascript
+/// const puzzle = generate_custom_puzzle(1, 30, 35, true);
+/// if (puzzle.error) showWarning(puzzle.error);
+/// ```
 #[wasm_bindgen]
 pub fn generate_custom_puzzle(
     difficulty: u8,
     min_clues: usize,
     max_clues: usize,
     prefer_symmetry: bool,
-) -> Vec<u8> {
-    let difficulty_level = match difficulty {
-        0 => DifficultyLevel::Easy,
-        1 => DifficultyLevel::Medium,
-        2 => DifficultyLevel::Hard,
-        3 => DifficultyLevel::Expert,
-        _ => DifficultyLevel::Medium,
+) -> JsValue {
+    let generator = match custom_generator(difficulty, min_clues, max_clues, prefer_symmetry) {
+        Ok(generator) => generator,
+        Err(err) => return js_error(&err.to_string()),
     };
 
+    match generator.generate() {
+        Some(puzzle) => to_js_value(&to_js_board(&puzzle)),
+        None => js_error("Custom puzzle generation failed"),
+    }
+}
+
+/// Build the generator behind [`generate_custom_puzzle`]
+fn custom_generator(
+    difficulty: u8,
+    min_clues: usize,
+    max_clues: usize,
+    prefer_symmetry: bool,
+) -> Result<PuzzleGenerator, ConfigError> {
+    let difficulty_level = difficulty_from_u8(difficulty).unwrap_or(DifficultyLevel::Medium);
+
     let config = GeneratorConfig {
         target_difficulty: difficulty_level,
         max_attempts: 1000,
-        min_clues: min_clues.max(17), // Ensure minimum is at least 17
-        max_clues: max_clues.min(50), // Ensure maximum is reasonable
+        min_clues,
+        max_clues,
         prefer_symmetry,
 
         // Use default branching factor settings for custom generation
@@ -134,297 +450,2621 @@ pub fn generate_custom_puzzle(
         max_branching_factor: 4.0,
         target_branching_factor: 2.5,
         branching_factor_tolerance: 0.5,
+        ..GeneratorConfig::default()
     };
 
-    let generator = PuzzleGenerator::new(config);
+    PuzzleGenerator::try_new(config)
+}
 
-    match generator.generate() {
+/// One constraint's tally as returned to JavaScript by [`diagnose_custom_puzzle`]
+#[derive(serde::Serialize)]
+struct ConstraintFailureRecord {
+    constraint: &'static str,
+    failures: u32,
+}
+
+/// The outcome of [`diagnose_custom_puzzle`]
+#[derive(serde::Serialize)]
+struct DiagnosticsRecord {
+    puzzle: Option<Vec<u8>>,
+    attempts: u32,
+    failures: Vec<ConstraintFailureRecord>,
+    bottleneck: Option<&'static str>,
+}
+
+/// Generate a custom puzzle, reporting which setting blocked generation
+///
+/// Takes the same settings as [`generate_custom_puzzle`] plus a seed. When
+/// no puzzle is found, every failed attempt is charged to the constraint it
+/// ran into, so settings screens can say which knob to loosen.
+///
+/// # Arguments
+/// * `difficulty` - Target difficulty level (0-3)
+/// * `min_clues` - Minimum number of clues (at least 17)
+/// * `max_clues` - Maximum number of clues
+/// * `prefer_symmetry` - Whether to prefer symmetric patterns
+/// * `seed` - Seed for reproducible generation
+///
+/// # Returns
+/// `{ puzzle, attempts, failures, bottleneck }`. On success `puzzle` is a
+/// flat array of 81 numbers and the rest is empty; on failure `puzzle` is
+/// `null`, `failures` lists `{ constraint, failures }` for each constraint
+/// that failed, and `bottleneck` names the most frequent one. A
+/// clue range rejected by [`generate_custom_puzzle`] returns `{ error }`
+/// instead.
+///
+/// # JavaScript Example
+/// ```javascript
+/// const result = diagnose_custom_puzzle(3, 45, 50, true, 42);
+/// if (!result.puzzle) {
+///     showWarning(`Try loosening: ${result.bottleneck}`); // "clue_count"
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn diagnose_custom_puzzle(
+    difficulty: u8,
+    min_clues: usize,
+    max_clues: usize,
+    prefer_symmetry: bool,
+    seed: u64,
+) -> JsValue {
+    let generator = match custom_generator(difficulty, min_clues, max_clues, prefer_symmetry) {
+        Ok(generator) => generator,
+        Err(err) => return js_error(&err.to_string()),
+    };
+
+    let record = match generator.generate_with_diagnostics(seed) {
+        Ok(puzzle) => DiagnosticsRecord {
+            puzzle: Some(to_js_board(&puzzle)),
+            attempts: 0,
+            failures: Vec::new(),
+            bottleneck: None,
+        },
+        Err(failure) => DiagnosticsRecord {
+            puzzle: None,
+            attempts: failure.attempts,
+            failures: Constraint::ALL
+                .into_iter()
+                .filter(|&constraint| failure.failures(constraint) > 0)
+                .map(|constraint| ConstraintFailureRecord {
+                    constraint: constraint.name(),
+                    failures: failure.failures(constraint),
+                })
+                .collect(),
+            bottleneck: failure.bottleneck().map(|constraint| constraint.name()),
+        },
+    };
+    to_js_value(&record)
+}
+
+/// Serialize a difficulty preset's generator settings to JSON
+///
+/// The JSON lists every field of the generator config, so settings screens
+/// can start from a preset, edit it and hand it back to
+/// [`generate_with_config_json`].
+///
+/// # Arguments
+/// * `difficulty` - Difficulty level (0=Easy, 1=Medium, 2=Hard, 3=Expert)
+///
+/// # Returns
+/// The preset as a JSON string (Medium for unknown levels)
+///
+/// # JavaScript Example
+/// ```javascript
+/// const config = JSON.parse(config_to_json(2));
+/// config.prefer_symmetry = false;
+/// const puzzle = generate_with_config_json(JSON.stringify(config), 42);
+/// ```
+#[wasm_bindgen]
+pub fn config_to_json(difficulty: u8) -> String {
+    let level = difficulty_from_u8(difficulty).unwrap_or(DifficultyLevel::Medium);
+    serde_json::to_string(&GeneratorConfig::for_difficulty(level)).unwrap_or_default()
+}
+
+/// Parse and validate generator settings from JSON
+///
+/// Missing fields take their default values.
+///
+/// # Arguments
+/// * `json` - A config as produced by [`config_to_json`]
+///
+/// # Returns
+/// The full config as an object, or `{ error }` if the JSON is malformed or
+/// the settings are invalid
+///
+/// # JavaScript Example
+/// ```javascript
+/// const config = config_from_json('{"target_difficulty":"Hard","min_clues":24}');
+/// if (config.error) showWarning(config.error);
+/// ```
+#[wasm_bindgen]
+pub fn config_from_json(json: String) -> JsValue {
+    match parse_config_json(&json) {
+        Ok(config) => to_js_value(&config),
+        Err(message) => js_error(&message),
+    }
+}
+
+/// Generate a puzzle from generator settings given as JSON
+///
+/// # Arguments
+/// * `json` - A config as produced by [`config_to_json`]
+/// * `seed` - Seed for reproducible generation
+///
+/// # Returns
+/// A puzzle as a flat array of 81 numbers, or all zeros if the config is
+/// invalid or generation fails
+///
+/// # JavaScript Example
+/// ```javascript
+/// const puzzle = generate_with_config_json(savedConfig, 42);
+/// ```
+#[wasm_bindgen]
+pub fn generate_with_config_json(json: String, seed: u64) -> Vec<u8> {
+    let config = match parse_config_json(&json) {
+        Ok(config) => config,
+        Err(message) => {
+            console::log_1(&message.into());
+            return vec![0; BOARD_SIZE];
+        }
+    };
+
+    match PuzzleGenerator::new(config).generate_with_seed(seed) {
         Some(puzzle) => to_js_board(&puzzle),
         None => {
-            console::log_1(&"Custom puzzle generation failed".into());
+            console::log_1(&"Puzzle generation failed".into());
             vec![0; BOARD_SIZE]
         }
     }
 }
 
-/// Validate a Sudoku board for correctness
+/// Deserialize a generator config and check it can be used
+fn parse_config_json(json: &str) -> Result<GeneratorConfig, String> {
+    let config: GeneratorConfig =
+        serde_json::from_str(json).map_err(|err| format!("Invalid config JSON: {}", err))?;
+    config.validate().map_err(|err| err.to_string())?;
+    Ok(config)
+}
+
+/// Generate a new puzzle that feels like an existing one
 ///
-/// Checks if the current state of the board violates any Sudoku rules.
-/// Does not require the board to be complete.
+/// The new puzzle targets the source's difficulty level and branching
+/// factor, for "give me another like this" buttons.
 ///
 /// # Arguments
-/// * `board` - The board to validate (flat array of 81 numbers)
+/// * `board` - The puzzle to imitate (flat array of 81 numbers)
+/// * `seed` - Seed for reproducible generation
 ///
 /// # Returns
-/// `true` if the board state is valid, `false` if there are conflicts
+/// A new puzzle as a flat array of 81 numbers, or an empty board if the
+/// input is malformed or no similar puzzle was found
 ///
 /// # JavaScript Example
 /// ```javascript
-/// const isValid = validate_board(currentBoard);
-/// if (!isValid) {
-///     console.log("There are conflicts in the current board!");
-/// }
+/// const next = regenerate_similar(currentPuzzle, Date.now());
 /// ```
 #[wasm_bindgen]
-pub fn validate_board(board: Vec<u8>) -> bool {
-    if board.len() != BOARD_SIZE {
-        console::log_1(
-            &format!(
-                "Invalid board size: expected {}, got {}",
-                BOARD_SIZE,
-                board.len()
-            )
-            .into(),
-        );
-        return false;
+pub fn regenerate_similar(board: Vec<u8>, seed: u64) -> Vec<u8> {
+    let internal_board = from_js_board(&board);
+    if check_board_values(&internal_board).is_err() {
+        return vec![0; BOARD_SIZE];
     }
 
-    let internal_board = from_js_board(&board);
-    internal_validate_board(&internal_board)
-        .invalid_indices
-        .is_empty()
+    match internal_regenerate_similar(&internal_board, seed) {
+        Some(puzzle) => to_js_board(&puzzle),
+        None => {
+            console::log_1(&"No similar puzzle found, returning empty board".into());
+            vec![0; BOARD_SIZE]
+        }
+    }
 }
 
-/// Check if a puzzle has a unique solution
+/// A puzzle with its estimated solving time, as returned by [`generate_for_time_band`]
+#[derive(serde::Serialize)]
+struct TimedPuzzleRecord {
+    puzzle: Vec<u8>,
+    estimated_seconds: f64,
+}
+
+/// Generate a puzzle expected to take a given amount of time
 ///
-/// This is important for puzzle quality - good Sudoku puzzles should have
-/// exactly one solution.
+/// Candidates of every difficulty are generated until one's estimated
+/// solving time (see [`estimate_solve_seconds`]) falls in the band.
+///
+/// # Arguments
+/// * `min_seconds` - Shortest acceptable estimate
+/// * `max_seconds` - Longest acceptable estimate
+/// * `seed` - Seed for reproducible generation
+///
+/// # Returns
+/// `{ puzzle, estimated_seconds }` with the puzzle as a flat array of 81
+/// numbers, or `{ error }` if the band is empty or no puzzle landed in it
+///
+/// # JavaScript Example
+/// ```javascript
+/// // "Quick play": about three minutes
+/// const { puzzle, estimated_seconds } = generate_for_time_band(150, 210, Date.now());
+/// ```
+#[wasm_bindgen]
+pub fn generate_for_time_band(min_seconds: u32, max_seconds: u32, seed: u64) -> JsValue {
+    if min_seconds > max_seconds {
+        return js_error("min_seconds must not exceed max_seconds");
+    }
+
+    match internal_generate_for_time_band(min_seconds as f64, max_seconds as f64, seed) {
+        Some((puzzle, estimated_seconds)) => to_js_value(&TimedPuzzleRecord {
+            puzzle: to_js_board(&puzzle),
+            estimated_seconds,
+        }),
+        None => js_error("No puzzle found in the requested time band"),
+    }
+}
+
+/// One level of the report returned by [`calibration_report`]
+#[derive(serde::Serialize)]
+struct CalibrationRecord {
+    level: &'static str,
+    samples: u32,
+    agreement: f64,
+    branching_factor_mean: f64,
+    branching_factor_stddev: f64,
+}
+
+/// Check that generated puzzles still grade as the level they target
+///
+/// A health check downstream apps can run in CI after upgrading the
+/// engine. It generates `samples_per_level` puzzles per level, so keep the
+/// count small in interactive use.
+///
+/// # Arguments
+/// * `samples_per_level` - Puzzles to generate per level
+/// * `seed` - Seed for reproducible generation
+///
+/// # Returns
+/// One `{ level, samples, agreement, branching_factor_mean,
+/// branching_factor_stddev }` per level, easiest first. `agreement` is the
+/// fraction of samples that analyze back to `level`.
+///
+/// # JavaScript Example
+/// ```javascript
+/// for (const entry of calibration_report(20, 1)) {
+///     assert(entry.agreement > 0.8, `${entry.level} drifted`);
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn calibration_report(samples_per_level: u32, seed: u64) -> JsValue {
+    let report: Vec<CalibrationRecord> = internal_calibration_report(samples_per_level, seed)
+        .into_iter()
+        .map(|entry| CalibrationRecord {
+            level: entry.level.name(),
+            samples: entry.samples,
+            agreement: entry.agreement,
+            branching_factor_mean: entry.branching_factor_mean,
+            branching_factor_stddev: entry.branching_factor_stddev,
+        })
+        .collect();
+    to_js_value(&report)
+}
+
+/// List the techniques the solver can genuinely detect
+///
+/// Other techniques may still appear in difficulty ratings, but only as
+/// heuristic estimates; the solver never finds them on the board.
+///
+/// # Returns
+/// Technique display names, in difficulty order
+///
+/// # JavaScript Example
+/// ```javascript
+/// const detectable = implemented_techniques();
+/// if (!detectable.includes(analysis.hardest_technique)) markAsEstimate();
+/// ```
+#[wasm_bindgen]
+pub fn implemented_techniques() -> Vec<String> {
+    internal_implemented_techniques()
+}
+
+/// Generate a puzzle for one step of a technique curriculum
+///
+/// The puzzle's hardest required technique is exactly `max_technique`, so
+/// a lesson can introduce one new technique at a time.
+///
+/// # Arguments
+/// * `max_technique` - Display name of the technique, e.g. `"Pointing Pair"`
+/// * `seed` - Seed for reproducible generation
+///
+/// # Returns
+/// The puzzle as a flat array of 81 numbers, or `{ error }` if the name is
+/// unknown, the solver doesn't implement the technique yet, or no puzzle
+/// was found
+///
+/// # JavaScript Example
+/// ```javascript
+/// const lesson = generate_for_curriculum("Box/Line Reduction", 42);
+/// if (!lesson.error) loadPuzzle(lesson);
+/// ```
+#[wasm_bindgen]
+pub fn generate_for_curriculum(max_technique: String, seed: u64) -> JsValue {
+    let Some(technique) = technique_from_name(&max_technique) else {
+        return js_error(&format!("Unknown technique: {}", max_technique));
+    };
+    if !IMPLEMENTED_TECHNIQUES.contains(&technique) {
+        return js_error(&format!("{} is not implemented yet", max_technique));
+    }
+
+    match internal_generate_for_curriculum(technique, seed) {
+        Some(puzzle) => to_js_value(&to_js_board(&puzzle)),
+        None => js_error("No puzzle needing this technique was found"),
+    }
+}
+
+/// Generate a puzzle that logic can't solve without one technique
+///
+/// Stronger than [`generate_for_curriculum`]: with the technique disabled,
+/// the solver stalls even though every other technique, harder ones
+/// included, is still available.
+///
+/// # Arguments
+/// * `technique` - Display name of the technique, e.g. `"Empty Rectangle"`
+/// * `seed` - Seed for reproducible generation
+///
+/// # Returns
+/// The puzzle as a flat array of 81 numbers, or `{ error }` if the name is
+/// unknown, the technique is a naked single or not implemented yet, or no
+/// puzzle was found
+///
+/// # JavaScript Example
+/// ```javascript
+/// const study = generate_requiring_technique("Pointing Pair", 7);
+/// if (!study.error) recordSample(study);
+/// ```
+#[wasm_bindgen]
+pub fn generate_requiring_technique(technique: String, seed: u64) -> JsValue {
+    let Some(required) = technique_from_name(&technique) else {
+        return js_error(&format!("Unknown technique: {}", technique));
+    };
+    if required == SolvingTechnique::NakedSingle {
+        return js_error("Naked singles can't be disabled");
+    }
+    if !IMPLEMENTED_TECHNIQUES.contains(&required) {
+        return js_error(&format!("{} is not implemented yet", technique));
+    }
+
+    match internal_generate_requiring_technique(required, seed) {
+        Some(puzzle) => to_js_value(&to_js_board(&puzzle)),
+        None => js_error("No puzzle requiring this technique was found"),
+    }
+}
+
+/// Count the clues left after carving a solution grid as far as possible
+///
+/// Clues are removed greedily in a seeded random order while the puzzle
+/// stays unique. Greedy carving isn't optimal, so the count is an upper
+/// bound on the smallest puzzle for this grid; other seeds may go lower.
+///
+/// # Arguments
+/// * `solution` - A complete, valid grid (flat array of 81 numbers)
+/// * `seed` - Seed for the removal order
+///
+/// # Returns
+/// The number of clues in the irreducible puzzle, or 0 if `solution` isn't
+/// a complete, valid grid
+///
+/// # JavaScript Example
+/// ```javascript
+/// const best = Math.min(...seeds.map(seed => minimal_clue_count(grid, seed)));
+/// ```
+#[wasm_bindgen]
+pub fn minimal_clue_count(solution: Vec<u8>, seed: u64) -> u32 {
+    if solution.len() != BOARD_SIZE {
+        return 0;
+    }
+
+    match minimal_puzzle(&from_js_board(&solution), seed) {
+        Some(puzzle) => puzzle.iter().filter(|c| c.is_some()).count() as u32,
+        None => {
+            console::log_1(&"Solution is not a complete, valid grid".into());
+            0
+        }
+    }
+}
+
+/// Turn an author's sketched clues into a full puzzle of a target difficulty
+///
+/// The sketch is solved, then the solution is carved down to the target
+/// difficulty without ever removing one of the sketched clues.
+///
+/// # Arguments
+/// * `partial` - The sketched clues (flat array of 81 numbers, 0 for empty)
+/// * `difficulty` - Target difficulty (0=Easy, 1=Medium, 2=Hard, 3=Expert)
+/// * `seed` - Seed for reproducible generation
+///
+/// # Returns
+/// The puzzle as a flat array of 81 numbers, or `{ error }` if the sketch
+/// has no solution or several, or no puzzle on target was found
+///
+/// # JavaScript Example
+/// ```javascript
+/// const result = complete_to_puzzle(sketch, 1, 42);
+/// if (result.error) showMessage(result.error);
+/// else loadPuzzle(result);
+/// ```
+#[wasm_bindgen]
+pub fn complete_to_puzzle(partial: Vec<u8>, difficulty: u8, seed: u64) -> JsValue {
+    if partial.len() != BOARD_SIZE {
+        return js_error("Invalid board size");
+    }
+    let Some(level) = difficulty_from_u8(difficulty) else {
+        return js_error("Invalid difficulty level");
+    };
+    let internal_partial = from_js_board(&partial);
+    if let Err(err) = check_board_values(&internal_partial) {
+        return js_error(&err.to_string());
+    }
+
+    match count_solutions(&internal_partial, 2) {
+        0 => return js_error("Partial grid has no solution"),
+        1 => {}
+        _ => return js_error("Partial grid has more than one solution"),
+    }
+
+    match internal_complete_to_puzzle(&internal_partial, level, seed) {
+        Some(puzzle) => to_js_value(&to_js_board(&puzzle)),
+        None => js_error("No puzzle of the target difficulty contains these clues"),
+    }
+}
+
+/// An over-determined puzzle after [`strip_redundant_to_difficulty`]
+#[derive(serde::Serialize)]
+struct StrippedRecord {
+    puzzle: Vec<u8>,
+    level: &'static str,
+    clue_count: usize,
+}
+
+/// Remove extra givens from an imported puzzle until it reaches its intended difficulty
+///
+/// Givens whose removal keeps the solution unique are dropped in a seeded
+/// order until the puzzle analyzes at `target` or harder.
+///
+/// # Arguments
+/// * `board` - The imported puzzle (flat array of 81 numbers)
+/// * `target` - Intended difficulty (0=Easy, 1=Medium, 2=Hard, 3=Expert)
+/// * `seed` - Seed for the removal order
+///
+/// # Returns
+/// `{ puzzle, level, clue_count }` with the level actually reached, which
+/// may fall short of `target` if the givens ran out, or `{ error }` if the
+/// board is malformed or not uniquely solvable
+///
+/// # JavaScript Example
+/// ```javascript
+/// const repaired = strip_redundant_to_difficulty(imported, 1, 7);
+/// if (!repaired.error) loadPuzzle(repaired.puzzle);
+/// ```
+#[wasm_bindgen]
+pub fn strip_redundant_to_difficulty(board: Vec<u8>, target: u8, seed: u64) -> JsValue {
+    let Some(level) = difficulty_from_u8(target) else {
+        return js_error("Invalid difficulty level");
+    };
+    let internal_board = from_js_board(&board);
+    if let Err(err) = check_well_formed(&internal_board) {
+        return js_error(&err.to_string());
+    }
+
+    match internal_strip_redundant_to_difficulty(&internal_board, level, seed) {
+        Some((puzzle, reached)) => to_js_value(&StrippedRecord {
+            clue_count: puzzle.iter().filter(|c| c.is_some()).count(),
+            puzzle: to_js_board(&puzzle),
+            level: reached.name(),
+        }),
+        None => js_error("Puzzle does not have a unique solution"),
+    }
+}
+
+/// Generate the clue layout of a typical puzzle without its digits
+///
+/// Lets UIs preview where givens would sit for a difficulty and symmetry
+/// setting before a real puzzle is filled in.
+///
+/// # Arguments
+/// * `difficulty` - Difficulty level (0=Easy, 1=Medium, 2=Hard, 3=Expert)
+/// * `seed` - Seed for deterministic generation
+///
+/// # Returns
+/// A flat array of 81 numbers: 1 where a given would be, 0 elsewhere
+///
+/// # JavaScript Example
+/// ```javascript
+/// const mask = generate_clue_pattern(1, 1234n);
+/// mask.forEach((given, i) => cells[i].classList.toggle("given", given === 1));
+/// ```
+#[wasm_bindgen]
+pub fn generate_clue_pattern(difficulty: u8, seed: u64) -> Vec<u8> {
+    let difficulty_level = difficulty_from_u8(difficulty).unwrap_or(DifficultyLevel::Medium);
+
+    match crate::generator::generate_clue_pattern(difficulty_level, seed) {
+        Some(pattern) => pattern.iter().map(|&given| given as u8).collect(),
+        None => {
+            console::log_1(&"Clue pattern generation failed".into());
+            vec![0; BOARD_SIZE]
+        }
+    }
+}
+
+/// Validate a Sudoku board for correctness
+///
+/// Checks if the current state of the board violates any Sudoku rules.
+/// Does not require the board to be complete.
+///
+/// # Arguments
+/// * `board` - The board to validate (flat array of 81 numbers)
+///
+/// # Returns
+/// `true` if the board state is valid, `false` if there are conflicts
+///
+/// # JavaScript Example
+/// ```javascript
+/// const isValid = validate_board(currentBoard);
+/// if (!isValid) {
+///     console.log("There are conflicts in the current board!");
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn validate_board(board: Vec<u8>) -> bool {
+    if board.len() != BOARD_SIZE {
+        console::log_1(
+            &format!(
+                "Invalid board size: expected {}, got {}",
+                BOARD_SIZE,
+                board.len()
+            )
+            .into(),
+        );
+        return false;
+    }
+
+    is_board_valid_fast(&from_js_board(&board))
+}
+
+/// Count rule violations for mistake tracking
+///
+/// Each filled cell counts once per row, column or box in which its value
+/// repeats, so a cell clashing in both its row and its box counts twice.
+///
+/// # Arguments
+/// * `board` - The board to score (flat array of 81 numbers)
+///
+/// # Returns
+/// The total number of conflicts, or `0` for a malformed board
+///
+/// # JavaScript Example
+/// ```javascript
+/// mistakes += conflict_count(board);
+/// ```
+#[wasm_bindgen]
+pub fn conflict_count(board: Vec<u8>) -> u32 {
+    if board.len() != BOARD_SIZE {
+        return 0;
+    }
+
+    internal_conflict_count(&from_js_board(&board))
+}
+
+/// Find the first row, column or box that repeats a digit
+///
+/// Friendlier than a flat list of invalid cells when a player fills the grid
+/// incorrectly: the UI can point at one offending unit.
+///
+/// # Arguments
+/// * `board` - The board to check (flat array of 81 numbers)
+///
+/// # Returns
+/// `{ kind: "Row" | "Column" | "Box", unit_index, cells }` for the first
+/// conflicting unit, `null` if none, or `{ error }` for a malformed board
+///
+/// # JavaScript Example
+/// ```javascript
+/// const violation = first_rule_violation(board);
+/// if (violation) highlightUnit(violation.kind, violation.unit_index, violation.cells);
+/// ```
+#[wasm_bindgen]
+pub fn first_rule_violation(board: Vec<u8>) -> JsValue {
+    let internal_board = from_js_board(&board);
+    if let Err(err) = check_board_values(&internal_board) {
+        return js_error(&err.to_string());
+    }
+
+    match internal_first_rule_violation(&internal_board) {
+        Some(violation) => to_js_value(&violation),
+        None => JsValue::NULL,
+    }
+}
+
+/// Recompute conflicts after the player edits a single cell
+///
+/// Only the edited cell and its peers are re-checked, so conflict highlights
+/// elsewhere on the board stay stable.
+///
+/// # Arguments
+/// * `board` - The board after the edit (flat array of 81 numbers)
+/// * `index` - The index of the edited cell (0-80)
+/// * `previous_invalid` - Array of invalid indices from before the edit
+///
+/// # Returns
+/// The updated array of invalid indices, or `{ error }` on bad input
+///
+/// # JavaScript Example
+/// ```javascript
+/// invalid = revalidate_cell(board, 40, invalid);
+/// ```
+#[wasm_bindgen]
+pub fn revalidate_cell(board: Vec<u8>, index: usize, previous_invalid: JsValue) -> JsValue {
+    if board.len() != BOARD_SIZE || index >= BOARD_SIZE {
+        return js_error("Invalid board size or cell index");
+    }
+
+    let previous: Vec<usize> = match serde_wasm_bindgen::from_value(previous_invalid) {
+        Ok(previous) => previous,
+        Err(_) => return js_error("previous_invalid must be an array of indices"),
+    };
+
+    let internal_board = from_js_board(&board);
+    to_js_value(&internal_revalidate_cell(&internal_board, index, &previous))
+}
+
+/// Apply a single-cell delta to a board
+///
+/// Lets front-ends keep a compact undo/redo stack of `{ index, before, after }`
+/// entries instead of full board snapshots.
+///
+/// # Arguments
+/// * `board` - The current board (flat array of 81 numbers)
+/// * `index` - The cell that changes (0-80)
+/// * `before` - The value the cell must currently hold (0 for empty)
+/// * `after` - The new value (0 for empty)
+///
+/// # Returns
+/// The updated board, or `{ error }` if the cell doesn't hold `before`
+///
+/// # JavaScript Example
+/// ```javascript
+/// board = apply_delta(board, 40, 0, 5);
+/// undoStack.push({ index: 40, before: 0, after: 5 });
+/// ```
+#[wasm_bindgen]
+pub fn apply_delta(board: Vec<u8>, index: usize, before: u8, after: u8) -> JsValue {
+    update_with_delta(board, index, before, after, delta::apply_delta)
+}
+
+/// Revert a single-cell delta previously applied with `apply_delta`
+///
+/// # Arguments
+/// * `board` - The current board (flat array of 81 numbers)
+/// * `index` - The cell that changed (0-80)
+/// * `before` - The value the cell held before the change (0 for empty)
+/// * `after` - The value the cell holds now (0 for empty)
+///
+/// # Returns
+/// The restored board, or `{ error }` if the cell doesn't hold `after`
+///
+/// # JavaScript Example
+/// ```javascript
+/// const { index, before, after } = undoStack.pop();
+/// board = revert_delta(board, index, before, after);
+/// ```
+#[wasm_bindgen]
+pub fn revert_delta(board: Vec<u8>, index: usize, before: u8, after: u8) -> JsValue {
+    update_with_delta(board, index, before, after, delta::revert_delta)
+}
+
+/// Restart a game by clearing every player entry
+///
+/// # Arguments
+/// * `puzzle` - The original puzzle (flat array of 81 numbers)
+/// * `current` - The board being played (flat array of 81 numbers)
+///
+/// # Returns
+/// The board with only the givens left, or `{ error }` if either board has
+/// the wrong size or `current` has a different value in any given cell
+///
+/// # JavaScript Example
+/// ```javascript
+/// const reset = reset_to_givens(puzzle, board);
+/// if (!reset.error) board = reset;
+/// ```
+#[wasm_bindgen]
+pub fn reset_to_givens(puzzle: Vec<u8>, current: Vec<u8>) -> JsValue {
+    if puzzle.len() != BOARD_SIZE {
+        return js_error("Invalid board size");
+    }
+
+    match delta::reset_to_givens(&from_js_board(&puzzle), &from_js_board(&current)) {
+        Ok(reset) => to_js_value(&to_js_board(&reset)),
+        Err(err) => js_error(&err.to_string()),
+    }
+}
+
+/// Shared conversion for `apply_delta` and `revert_delta`
+fn update_with_delta(
+    board: Vec<u8>,
+    index: usize,
+    before: u8,
+    after: u8,
+    update: fn(&mut [Option<u8>], &BoardDelta) -> Result<(), delta::DeltaError>,
+) -> JsValue {
+    if board.len() != BOARD_SIZE {
+        return js_error("Invalid board size");
+    }
+
+    let mut internal_board = from_js_board(&board);
+    let delta = BoardDelta {
+        index,
+        before: from_js_board(&[before])[0],
+        after: from_js_board(&[after])[0],
+    };
+
+    match update(&mut internal_board, &delta) {
+        Ok(()) => to_js_value(&to_js_board(&internal_board)),
+        Err(err) => js_error(&err.to_string()),
+    }
+}
+
+/// Detect the symmetries of a puzzle's clue pattern
+///
+/// # Arguments
+/// * `board` - The puzzle to classify (flat array of 81 numbers)
+///
+/// # Returns
+/// An array drawn from `"rotational"`, `"horizontal"`, `"vertical"` and
+/// `"diagonal"`, or `["none"]`; `{ error }` for a malformed board
+///
+/// # JavaScript Example
+/// ```javascript
+/// const symmetries = detect_symmetry(puzzle);
+/// if (symmetries.includes("rotational")) showBadge("Classic symmetry");
+/// ```
+#[wasm_bindgen]
+pub fn detect_symmetry(board: Vec<u8>) -> JsValue {
+    if board.len() != BOARD_SIZE {
+        return js_error("Invalid board size");
+    }
+
+    to_js_value(&internal_detect_symmetry(&from_js_board(&board)))
+}
+
+/// Measure how evenly a puzzle's clues are spread across the boxes
+///
+/// # Arguments
+/// * `board` - The puzzle to measure (flat array of 81 numbers)
+///
+/// # Returns
+/// The variance of per-box clue counts over the squared mean: 0 for a
+/// perfectly even layout, up to 8 with every clue in one box; `NaN` for a
+/// malformed board
+///
+/// # JavaScript Example
+/// ```javascript
+/// if (clue_balance_score(puzzle) > 0.3) console.log("Clues look lopsided");
+/// ```
+#[wasm_bindgen]
+pub fn clue_balance_score(board: Vec<u8>) -> f64 {
+    if board.len() != BOARD_SIZE {
+        return f64::NAN;
+    }
+
+    internal_clue_balance_score(&from_js_board(&board))
+}
+
+/// Check if a rotation or reflection maps a puzzle onto itself
+///
+/// Digits may be relabeled alongside the transform. Such puzzles are
+/// usually considered lower quality.
+///
+/// # Arguments
+/// * `board` - The puzzle to check (flat array of 81 numbers)
+///
+/// # Returns
+/// `true` if the puzzle has a non-trivial automorphism; `false` for a
+/// malformed board or one holding values outside 0-9
+///
+/// # JavaScript Example
+/// ```javascript
+/// if (has_nontrivial_automorphism(puzzle)) {
+///     console.log("Puzzle is self-similar");
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn has_nontrivial_automorphism(board: Vec<u8>) -> bool {
+    if board.len() != BOARD_SIZE {
+        return false;
+    }
+    let internal_board = from_js_board(&board);
+    if check_board_values(&internal_board).is_err() {
+        return false;
+    }
+
+    internal_has_nontrivial_automorphism(&internal_board)
+}
+
+/// Relabel a puzzle's digits with a fixed permutation
+///
+/// A cheap way to freshen a puzzle: the layout, solution structure and
+/// difficulty stay exactly the same.
+///
+/// # Arguments
+/// * `board` - The puzzle to relabel (flat array of 81 numbers)
+/// * `permutation` - Nine digits; digit `d` becomes `permutation[d - 1]`
+///
+/// # Returns
+/// The relabeled puzzle as a flat array of 81 numbers, or `{ error }` if
+/// the board is malformed or `permutation` isn't a bijection of 1-9
+///
+/// # JavaScript Example
+/// ```javascript
+/// const fresh = relabel_digits(puzzle, [3, 1, 4, 9, 5, 2, 6, 8, 7]);
+/// ```
+#[wasm_bindgen]
+pub fn relabel_digits(board: Vec<u8>, permutation: Vec<u8>) -> JsValue {
+    let internal_board = from_js_board(&board);
+    if let Err(err) = check_board_values(&internal_board) {
+        return js_error(&err.to_string());
+    }
+
+    match internal_relabel_digits(&internal_board, &permutation) {
+        Some(relabeled) => to_js_value(&to_js_board(&relabeled)),
+        None => js_error("Permutation must contain each digit 1-9 exactly once"),
+    }
+}
+
+/// Check if a puzzle has a unique solution
+///
+/// This is important for puzzle quality - good Sudoku puzzles should have
+/// exactly one solution.
+///
+/// # Arguments
+/// * `board` - The puzzle to check (flat array of 81 numbers)
+///
+/// # Returns
+/// `true` if the puzzle has exactly one solution
+///
+/// # JavaScript Example
+/// ```javascript
+/// const hasUniqueSolution = check_unique_solution(puzzle);
+/// if (!hasUniqueSolution) {
+///     console.log("This puzzle has multiple solutions or no solution!");
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn check_unique_solution(board: Vec<u8>) -> bool {
+    if board.len() != BOARD_SIZE {
+        return false;
+    }
+
+    let internal_board = from_js_board(&board);
+    has_unique_solution(&internal_board)
+}
+
+/// Most solutions [`enumerate_solutions`] returns, whatever `max` asks for
+const SOLUTION_LIST_LIMIT: u32 = 1_000;
+
+/// List the solutions of an under-constrained board
+///
+/// # Arguments
+/// * `board` - The board to complete (flat array of 81 numbers)
+/// * `max` - Stop after this many solutions; capped at 1000, since sparse
+///   boards have far too many to list
+///
+/// # Returns
+/// An array of up to `max` complete grids, each a flat array of 81
+/// numbers; empty if the board is malformed or has no solution
+///
+/// # JavaScript Example
+/// ```javascript
+/// const solutions = enumerate_solutions(board, 10);
+/// console.log(`This board has ${solutions.length} solutions`);
+/// ```
+#[wasm_bindgen]
+pub fn enumerate_solutions(board: Vec<u8>, max: u32) -> JsValue {
+    let solutions: Vec<Vec<u8>> = if board.len() == BOARD_SIZE {
+        let limit = max.min(SOLUTION_LIST_LIMIT) as usize;
+        internal_enumerate_solutions(&from_js_board(&board), limit)
+            .iter()
+            .map(|solution| to_js_board(solution))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    to_js_value(&solutions)
+}
+
+/// Check whether a board can be completed at all
+///
+/// Unlike [`is_well_formed_puzzle`], this also catches givens that rule out
+/// every solution without two of them clashing directly. Unlike
+/// [`check_unique_solution`], several solutions are fine.
+///
+/// # Arguments
+/// * `board` - The board to check (flat array of 81 numbers)
+///
+/// # Returns
+/// `true` if at least one solution exists
+///
+/// # JavaScript Example
+/// ```javascript
+/// if (!is_solvable_at_all(imported)) {
+///     console.log("These givens can't lead to any solution");
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn is_solvable_at_all(board: Vec<u8>) -> bool {
+    if board.len() != BOARD_SIZE {
+        return false;
+    }
+
+    internal_is_solvable_at_all(&from_js_board(&board))
+}
+
+/// The outcome of [`is_well_formed_puzzle`]
+#[derive(serde::Serialize)]
+struct WellFormedRecord {
+    well_formed: bool,
+    reason: String,
+}
+
+/// Check that 81 numbers make a plausible puzzle
+///
+/// The board must have 81 cells, hold only 0-9, and its clues must not
+/// repeat a digit in any row, column or box. [`solve_puzzle`] and
+/// [`analyze_puzzle_difficulty`] run the same check before doing any work.
+///
+/// # Arguments
+/// * `board` - The puzzle to check (flat array of 81 numbers)
+///
+/// # Returns
+/// `{ well_formed, reason }`, where `reason` is empty for a well-formed board
+///
+/// # JavaScript Example
+/// ```javascript
+/// const check = is_well_formed_puzzle(imported);
+/// if (!check.well_formed) showError(check.reason);
+/// ```
+#[wasm_bindgen]
+pub fn is_well_formed_puzzle(board: Vec<u8>) -> JsValue {
+    let reason = match check_well_formed(&from_js_board(&board)) {
+        Ok(()) => String::new(),
+        Err(err) => err.to_string(),
+    };
+    to_js_value(&WellFormedRecord {
+        well_formed: reason.is_empty(),
+        reason,
+    })
+}
+
+/// Import a puzzle from text
+///
+/// Accepts the usual one-line format: 81 symbols, `1-9` for givens and `.`
+/// or `0` for empty cells, with whitespace ignored. The givens get the same
+/// check as [`is_well_formed_puzzle`], so a puzzle repeating a digit in a
+/// unit is rejected on import instead of turning out unsolvable later.
+///
+/// # Arguments
+/// * `text` - The puzzle text
+///
+/// # Returns
+/// The board as 81 numbers (0 for empty), or `{ error }`, e.g.
+/// `"Conflicting givens at cells 0 and 6"`
+///
+/// # JavaScript Example
+/// ```javascript
+/// const board = import_puzzle(pastedText);
+/// if (board.error) showError(board.error);
+/// ```
+#[wasm_bindgen]
+pub fn import_puzzle(text: String) -> JsValue {
+    match parse_puzzle(&text) {
+        Ok(board) => to_js_value(&to_js_board(&board)),
+        Err(err) => js_error(&err.to_string()),
+    }
+}
+
+/// Check that a board belongs to a given puzzle
+///
+/// A board in progress holds the puzzle's givens plus the player's fills,
+/// and fills can't be told apart from givens, so the puzzle side decides
+/// which cells count: every given must appear at the same cell with the
+/// same value on the other board. Extra filled cells are ignored.
+///
+/// # Arguments
+/// * `a` - The first board (flat array of 81 numbers, 0 for empty)
+/// * `b` - The second board
+/// * `a_is_puzzle` - `true` if `a` holds the givens, `false` if `b` does
+///
+/// # Returns
+/// `true` if the other board keeps every given; `false` otherwise or if
+/// either board doesn't have 81 cells
+///
+/// # JavaScript Example
+/// ```javascript
+/// if (!same_givens(expectedPuzzle, clientBoard, true)) {
+///     rejectSync("Board doesn't belong to this puzzle");
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn same_givens(a: Vec<u8>, b: Vec<u8>, a_is_puzzle: bool) -> bool {
+    let (puzzle, progress) = if a_is_puzzle { (a, b) } else { (b, a) };
+    if puzzle.len() != BOARD_SIZE || progress.len() != BOARD_SIZE {
+        return false;
+    }
+
+    puzzle
+        .iter()
+        .zip(&progress)
+        .all(|(&given, &cell)| given == 0 || given == cell)
+}
+
+/// Solve a Sudoku puzzle completely
+///
+/// Uses backtracking to find a complete solution to the puzzle.
+/// Returns the original board if no solution exists.
+///
+/// An all-empty board is not a puzzle: any complete grid would "solve" it,
+/// so it comes back empty rather than filled with an arbitrary grid. Use
+/// [`nth_solution_grid`] when any complete grid will do.
+///
+/// # Arguments
+/// * `board` - The puzzle to solve (flat array of 81 numbers)
+///
+/// # Returns
+/// The solved board, or the original board if it is empty, unsolvable or
+/// not well-formed (see [`is_well_formed_puzzle`]). Failure is not
+/// reported otherwise: a result that still contains a 0 was not solved,
+/// and a complete input comes back unchanged whether or not it is valid.
+/// Callers that need to tell these cases apart should use
+/// [`solve_puzzle_strict`], which names the reason.
+///
+/// # JavaScript Example
+/// ```javascript
+/// const solution = solve_puzzle(puzzle);
+/// if (!solution.includes(0)) {
+///     console.log("Found solution!");
+/// } else {
+///     console.log("No solution exists");
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn solve_puzzle(board: Vec<u8>) -> Vec<u8> {
+    if board.len() != BOARD_SIZE {
+        console::log_1(&"Invalid board size for solving".into());
+        return board;
+    }
+
+    let mut internal_board = from_js_board(&board);
+    if let Err(err) = check_well_formed(&internal_board) {
+        console::log_1(&err.to_string().into());
+        return board;
+    }
+
+    // Nothing to solve; not an error, so nothing is logged
+    if internal_board.iter().all(|cell| cell.is_none()) {
+        return board;
+    }
+
+    if solve_board(&mut internal_board) {
+        to_js_board(&internal_board)
+    } else {
+        console::log_1(&"No solution found for the given puzzle".into());
+        board // Return original if unsolvable
+    }
+}
+
+/// The outcome of [`solve_puzzle_strict`]
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct StrictSolveRecord {
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    board: Option<Vec<u8>>,
+}
+
+/// Solve a puzzle, saying exactly why when it can't be solved
+///
+/// Unlike [`solve_puzzle`], failures are never passed off as the input.
+///
+/// # Arguments
+/// * `board` - The puzzle to solve (flat array of 81 numbers)
+///
+/// # Returns
+/// `{ status, board }` where `status` is one of:
+/// * `"ok"` - the puzzle has exactly one solution, given in `board`
+/// * `"multiple_solutions"` - `board` holds one of several solutions
+/// * `"no_solution"` - no grid completes the puzzle; `board` is omitted
+/// * `"malformed"` - the board has the wrong length, values outside 0-9 or
+///   repeated clues (see [`is_well_formed_puzzle`]); `board` is omitted
+///
+/// # JavaScript Example
+/// ```javascript
+/// const result = solve_puzzle_strict(puzzle);
+/// if (result.status === "ok") showSolution(result.board);
+/// else showWarning(result.status);
+/// ```
+#[wasm_bindgen]
+pub fn solve_puzzle_strict(board: Vec<u8>) -> JsValue {
+    to_js_value(&strict_solve(&board))
+}
+
+/// Solve a board for [`solve_puzzle_strict`]
+fn strict_solve(board: &[u8]) -> StrictSolveRecord {
+    let outcome = |status, board| StrictSolveRecord { status, board };
+    if board.len() != BOARD_SIZE {
+        return outcome("malformed", None);
+    }
+    let internal_board = from_js_board(board);
+    if check_well_formed(&internal_board).is_err() {
+        return outcome("malformed", None);
+    }
+
+    let mut solution = internal_board.clone();
+    if !solve_board(&mut solution) {
+        return outcome("no_solution", None);
+    }
+    let status = if count_solutions(&internal_board, 2) > 1 {
+        "multiple_solutions"
+    } else {
+        "ok"
+    };
+    outcome(status, Some(to_js_board(&solution)))
+}
+
+/// Reveal the correct digit for a single cell
+///
+/// Only the one value is returned, so competitive modes can offer a
+/// "peek one cell" assist without sending the whole solution to the client.
+///
+/// # Arguments
+/// * `puzzle` - The puzzle (flat array of 81 numbers)
+/// * `index` - The empty cell to reveal (0-80)
+///
+/// # Returns
+/// The digit (1-9), or `{ error }` if the cell is a given or the puzzle
+/// doesn't have exactly one solution
+///
+/// # JavaScript Example
+/// ```javascript
+/// const digit = solution_value_at(puzzle, selected);
+/// if (typeof digit === "number") flashCell(selected, digit);
+/// ```
+#[wasm_bindgen]
+pub fn solution_value_at(puzzle: Vec<u8>, index: usize) -> JsValue {
+    if puzzle.len() != BOARD_SIZE || index >= BOARD_SIZE {
+        return js_error("Invalid board size or cell index");
+    }
+    if puzzle[index] != 0 {
+        return js_error("Cell is a given");
+    }
+
+    match internal_solution_value_at(&from_js_board(&puzzle), index) {
+        Some(value) => to_js_value(&value),
+        None => js_error("Puzzle does not have a unique solution"),
+    }
+}
+
+/// Check whether a finished grid is the puzzle's solution
+///
+/// A valid but different complete grid counts as wrong when the puzzle is
+/// uniquely solvable. For puzzles with several solutions only the rules and
+/// the givens are checked, and `consistency_only` is set.
+///
+/// # Arguments
+/// * `puzzle` - The puzzle (flat array of 81 numbers)
+/// * `submission` - The player's grid (flat array of 81 numbers)
+///
+/// # Returns
+/// `{ correct, wrong_cells, consistency_only }`, or `{ error }` for a
+/// malformed board. Empty cells are listed as wrong.
+///
+/// # JavaScript Example
+/// ```javascript
+/// const result = is_correct_completion(puzzle, playerGrid);
+/// if (result.correct) celebrate();
+/// else result.wrong_cells.forEach(markWrong);
+/// ```
+#[wasm_bindgen]
+pub fn is_correct_completion(puzzle: Vec<u8>, submission: Vec<u8>) -> JsValue {
+    let internal_puzzle = from_js_board(&puzzle);
+    let internal_submission = from_js_board(&submission);
+    match check_completion(&internal_puzzle, &internal_submission) {
+        Ok(check) => to_js_value(&check),
+        Err(err) => js_error(&err.to_string()),
+    }
+}
+
+/// The outcome of [`solve_with_locked`]
+#[derive(serde::Serialize)]
+struct LockedSolveRecord {
+    solvable: bool,
+    solution: Option<Vec<u8>>,
+}
+
+/// Solve a puzzle treating the player's entries as fixed
+///
+/// Entries are locked in like extra givens, right or wrong, which powers
+/// "you've made an error somewhere" warnings without revealing where.
+///
+/// # Arguments
+/// * `puzzle` - The puzzle (flat array of 81 numbers)
+/// * `user` - The player's entries (flat array of 81 numbers, 0 = empty)
+///
+/// # Returns
+/// `{ solvable, solution }`, where `solution` is a flat array of 81 numbers
+/// or `null` if the entries rule out every solution; `{ error }` for a
+/// malformed board
+///
+/// # JavaScript Example
+/// ```javascript
+/// if (!solve_with_locked(puzzle, entries).solvable) {
+///     showBanner("You've made an error somewhere");
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn solve_with_locked(puzzle: Vec<u8>, user: Vec<u8>) -> JsValue {
+    let internal_puzzle = from_js_board(&puzzle);
+    let internal_user = from_js_board(&user);
+    if let Err(err) =
+        check_board_values(&internal_puzzle).and_then(|()| check_board_values(&internal_user))
+    {
+        return js_error(&err.to_string());
+    }
+
+    let solution = internal_solve_with_locked(&internal_puzzle, &internal_user);
+    to_js_value(&LockedSolveRecord {
+        solvable: solution.is_some(),
+        solution: solution.map(|board| to_js_board(&board)),
+    })
+}
+
+/// Count the guesses a solver needs when logic alone is not enough
+///
+/// Logical techniques are applied first; each time they stall, one guess is
+/// made on the most constrained cell. The search is capped to stay bounded.
+///
+/// # Arguments
+/// * `board` - The puzzle to grade (flat array of 81 numbers)
+///
+/// # Returns
+/// The minimum number of nested guesses (0 = solvable by logic), or
+/// `4294967295` if the puzzle is invalid or unsolvable within the cap
+///
+/// # JavaScript Example
+/// ```javascript
+/// const fair = max_guesses_required(puzzle) === 0;
+/// ```
+#[wasm_bindgen]
+pub fn max_guesses_required(board: Vec<u8>) -> u32 {
+    if board.len() != BOARD_SIZE {
+        return u32::MAX;
+    }
+
+    let internal_board = from_js_board(&board);
+    count_required_guesses(&internal_board).unwrap_or(u32::MAX)
+}
+
+/// Count the logical steps needed to solve a puzzle
+///
+/// Puzzles of the same tier often differ greatly in length, which affects
+/// how hard they feel.
+///
+/// # Arguments
+/// * `board` - The puzzle to measure (flat array of 81 numbers)
+///
+/// # Returns
+/// Placements plus elimination passes until solved or stalled; `0` for a
+/// malformed board
+///
+/// # JavaScript Example
+/// ```javascript
+/// const steps = solve_path_length(puzzle);
+/// console.log(`About ${steps} moves to finish`);
+/// ```
+#[wasm_bindgen]
+pub fn solve_path_length(board: Vec<u8>) -> u32 {
+    if board.len() != BOARD_SIZE {
+        return 0;
+    }
+
+    internal_solve_path_length(&from_js_board(&board))
+}
+
+/// Analyze the difficulty of a puzzle
+///
+/// Returns detailed information about what techniques are required
+/// to solve the puzzle and estimates the overall difficulty.
+///
+/// # Arguments
+/// * `board` - The puzzle to analyze (flat array of 81 numbers)
+///
+/// # Returns
+/// A JSON string containing difficulty analysis, with the level both as a
+/// name (`level`) and as the legacy 1-5 number (`level_numeric`), or
+/// `{"error": ...}` if the board is not well-formed (see
+/// [`is_well_formed_puzzle`]). `confidence` is `"Solved"` when the rating
+/// comes from a full logical solve, `"Heuristic"` when it was estimated,
+/// `"NoPuzzle"` when the board has no empty cells to rate and
+/// `"Unsolvable"` when the givens allow no completion at all. In the last
+/// two cases `level` is a placeholder and shouldn't be shown as a rating
+///
+/// # JavaScript Example
+/// ```javascript
+/// const analysis = JSON.parse(analyze_puzzle_difficulty(puzzle));
+/// console.log(`Difficulty: ${analysis.level}, Techniques: ${analysis.techniques}`);
+/// ```
+#[wasm_bindgen]
+pub fn analyze_puzzle_difficulty(board: Vec<u8>) -> String {
+    if board.len() != BOARD_SIZE {
+        return r#"{"error": "Invalid board size"}"#.to_string();
+    }
+
+    let internal_board = from_js_board(&board);
+    if let Err(err) = check_well_formed(&internal_board) {
+        return format!(r#"{{"error": "{}"}}"#, err);
+    }
+    difficulty_json(&analyze_difficulty(&internal_board))
+}
+
+/// Analyze a puzzle's difficulty along its easiest solve path
+///
+/// Unlike [`analyze_puzzle_difficulty`], a puzzle whose solve uses only
+/// singles is graded by that solve, with no clue-count estimate on top.
+/// Other puzzles grade the same either way.
+///
+/// # Arguments
+/// * `board` - The puzzle to analyze (flat array of 81 numbers)
+///
+/// # Returns
+/// The same JSON as [`analyze_puzzle_difficulty`]
+///
+/// # JavaScript Example
+/// ```javascript
+/// const official = JSON.parse(analyze_puzzle_easiest_path(puzzle));
+/// console.log(`Graded ${official.level} by ${official.hardest_technique}`);
+/// ```
+#[wasm_bindgen]
+pub fn analyze_puzzle_easiest_path(board: Vec<u8>) -> String {
+    if board.len() != BOARD_SIZE {
+        return r#"{"error": "Invalid board size"}"#.to_string();
+    }
+
+    let internal_board = from_js_board(&board);
+    if let Err(err) = check_well_formed(&internal_board) {
+        return format!(r#"{{"error": "{}"}}"#, err);
+    }
+    difficulty_json(&analyze_difficulty_easiest(&internal_board))
+}
+
+/// Analyze a puzzle's difficulty with custom branching factor bands
+///
+/// Lets apps tune how readily puzzles rate as harder for their audience.
+///
+/// # Arguments
+/// * `board` - The puzzle to analyze (flat array of 81 numbers)
+/// * `thresholds_json` - `{ very_easy, easy, medium, hard }`, the highest
+///   branching factor for each level; missing fields keep their defaults
+///   (1.7, 2.2, 3.8 and 6.0)
+///
+/// # Returns
+/// The same JSON as [`analyze_puzzle_difficulty`], or `{"error": ...}` if
+/// the thresholds JSON is malformed or its bands aren't finite and
+/// non-decreasing from `very_easy` to `hard`
+///
+/// # JavaScript Example
+/// ```javascript
+/// const analysis = JSON.parse(analyze_puzzle_with_thresholds(puzzle, '{"easy":2.5}'));
+/// console.log(`Rated ${analysis.level} for casual players`);
+/// ```
+#[wasm_bindgen]
+pub fn analyze_puzzle_with_thresholds(board: Vec<u8>, thresholds_json: String) -> String {
+    if board.len() != BOARD_SIZE {
+        return r#"{"error": "Invalid board size"}"#.to_string();
+    }
+    let thresholds: DifficultyThresholds = match serde_json::from_str(&thresholds_json) {
+        Ok(thresholds) => thresholds,
+        Err(err) => {
+            return serde_json::json!({ "error": format!("Invalid thresholds JSON: {}", err) })
+                .to_string();
+        }
+    };
+
+    let internal_board = from_js_board(&board);
+    if let Err(err) = check_well_formed(&internal_board) {
+        return format!(r#"{{"error": "{}"}}"#, err);
+    }
+    match analyze_difficulty_with_thresholds(&internal_board, &thresholds) {
+        Ok(analysis) => difficulty_json(&analysis),
+        Err(err) => serde_json::json!({ "error": err.to_string() }).to_string(),
+    }
+}
+
+/// Build the JSON returned by [`analyze_puzzle_difficulty`]
+fn difficulty_json(analysis: &DifficultyAnalysis) -> String {
+    // Convert to JSON manually for simplicity
+    let level_str = analysis.level.name();
+    let technique_str = analysis.hardest_technique.display_name();
+
+    // Contradictory boards report an infinite branching factor, which JSON can't hold
+    let finite_or_null = |value: f64| {
+        if value.is_finite() {
+            format!("{:.2}", value)
+        } else {
+            "null".to_string()
+        }
+    };
+
+    format!(
+        r#"{{"level": "{}", "level_numeric": {}, "hardest_technique": "{}", "technique_diversity": {}, "branching_factor": {}, "average_branching_during_solve": {}, "step_count": {}, "estimated_seconds": {:.1}, "confidence": "{}"}}"#,
+        level_str,
+        analysis.level.legacy_number(),
+        technique_str,
+        analysis.technique_diversity,
+        finite_or_null(analysis.branching_factor),
+        finite_or_null(analysis.average_branching_during_solve),
+        analysis.step_count,
+        analysis.estimated_seconds,
+        analysis.confidence.name()
+    )
+}
+
+/// Most board states a [`PuzzleEditor`] remembers analyses for
+const EDITOR_CACHE_LIMIT: usize = 256;
+
+/// What a [`PuzzleEditor`] knows about one analyzed board
+#[derive(Clone)]
+struct EditorEntry {
+    analysis: String,
+    level: Option<DifficultyLevel>,
+    solution: Option<Vec<Option<u8>>>,
+}
+
+/// A puzzle being edited, with its difficulty kept up to date
+///
+/// Every board state's analysis is cached, so toggling a clue on and off
+/// again, or undoing back to an earlier layout, reuses the earlier result
+/// instead of solving again. Adding a clue that agrees with the solution
+/// keeps the puzzle unique and can only make it easier, so the last level
+/// stays an upper bound and the full analysis waits until
+/// [`PuzzleEditor::current_analysis`] asks for it.
+///
+/// # JavaScript Example
+/// ```javascript
+/// const editor = new PuzzleEditor(puzzle);
+/// editor.set_cell(40, 0); // remove the center clue
+/// const analysis = JSON.parse(editor.current_analysis());
+/// ```
+#[wasm_bindgen]
+pub struct PuzzleEditor {
+    board: Vec<Option<u8>>,
+    // `None` while clues that agree with the solution await analysis
+    analysis: Option<String>,
+    // The level and unique solution of the last analyzed board
+    level: Option<DifficultyLevel>,
+    solution: Option<Vec<Option<u8>>>,
+    cache: HashMap<Vec<Option<u8>>, EditorEntry>,
+    analyses_run: usize,
+}
+
+#[wasm_bindgen]
+impl PuzzleEditor {
+    /// Start editing a puzzle
+    ///
+    /// # Arguments
+    /// * `board` - The starting puzzle (flat array of 81 numbers); a
+    ///   malformed board starts the editor empty
+    #[wasm_bindgen(constructor)]
+    pub fn new(board: Vec<u8>) -> PuzzleEditor {
+        if board.len() != BOARD_SIZE {
+            return PuzzleEditor::from_board(vec![None; BOARD_SIZE]);
+        }
+        PuzzleEditor::from_board(from_js_board(&board))
+    }
+
+    /// Place or clear one clue and refresh the analysis
+    ///
+    /// # Arguments
+    /// * `index` - The cell to change (0-80)
+    /// * `value` - The clue (1-9), or 0 to clear the cell
+    ///
+    /// # Returns
+    /// `false` if the index or value is out of range; the board is unchanged
+    pub fn set_cell(&mut self, index: usize, value: u8) -> bool {
+        if index >= BOARD_SIZE || value > 9 {
+            return false;
+        }
+
+        let cell = if value == 0 { None } else { Some(value) };
+        if self.board[index] == cell {
+            return true;
+        }
+        let agrees = self.board[index].is_none()
+            && self
+                .solution
+                .as_ref()
+                .is_some_and(|solution| solution[index] == cell);
+        self.board[index] = cell;
+
+        if agrees && !self.cache.contains_key(&self.board) {
+            self.analysis = None;
+        } else {
+            self.refresh();
+        }
+        true
+    }
+
+    /// Get the analysis of the current board, running it if clues were
+    /// added since the last one
+    ///
+    /// # Returns
+    /// A JSON string in the format of [`analyze_puzzle_difficulty`]
+    pub fn current_analysis(&mut self) -> String {
+        if self.analysis.is_none() {
+            self.refresh();
+        }
+        self.analysis.clone().unwrap_or_default()
+    }
+
+    /// Get the hardest level the current board can have without analyzing it
+    ///
+    /// # Returns
+    /// The level name of the last analysis, which added clues can't raise,
+    /// or `undefined` if the last analyzed board was malformed
+    pub fn level_bound(&self) -> Option<String> {
+        self.level.map(|level| level.name().to_string())
+    }
+
+    /// Get the current board
+    ///
+    /// # Returns
+    /// A flat array of 81 numbers (0 for empty cells)
+    pub fn board(&self) -> Vec<u8> {
+        to_js_board(&self.board)
+    }
+}
+
+impl PuzzleEditor {
+    /// Start editing an internal board
+    fn from_board(board: Vec<Option<u8>>) -> PuzzleEditor {
+        let mut editor = PuzzleEditor {
+            board,
+            analysis: None,
+            level: None,
+            solution: None,
+            cache: HashMap::new(),
+            analyses_run: 0,
+        };
+        editor.refresh();
+        editor
+    }
+
+    /// Bring the analysis in line with the board, reusing cached results
+    fn refresh(&mut self) {
+        let entry = match self.cache.get(&self.board) {
+            Some(entry) => entry.clone(),
+            None => {
+                let entry = self.analyze();
+                if self.cache.len() >= EDITOR_CACHE_LIMIT {
+                    self.cache.clear();
+                }
+                self.cache.insert(self.board.clone(), entry.clone());
+                entry
+            }
+        };
+        self.analysis = Some(entry.analysis);
+        self.level = entry.level;
+        self.solution = entry.solution;
+    }
+
+    /// Run the full analysis of the current board
+    fn analyze(&mut self) -> EditorEntry {
+        self.analyses_run += 1;
+        if let Err(err) = check_well_formed(&self.board) {
+            return EditorEntry {
+                analysis: format!(r#"{{"error": "{}"}}"#, err),
+                level: None,
+                solution: None,
+            };
+        }
+        let analysis = analyze_difficulty(&self.board);
+        EditorEntry {
+            analysis: difficulty_json(&analysis),
+            level: Some(analysis.level),
+            solution: unique_solution(&self.board),
+        }
+    }
+}
+
+/// A stored puzzle as produced by [`export_puzzle_bundle`]
+#[derive(serde::Serialize)]
+struct PuzzleBundle {
+    puzzle: String,
+    analysis: serde_json::Value,
+    solution: String,
+    fingerprint: String,
+}
+
+/// Export a puzzle with its analysis, solution and fingerprint
+///
+/// The result is a self-describing record that a database can store as is.
+///
+/// # Arguments
+/// * `board` - The puzzle to export (flat array of 81 numbers)
+///
+/// # Returns
+/// A JSON string `{ puzzle, analysis, solution, fingerprint }`. `puzzle`
+/// and `solution` are 81-character strings in reading order with `.` for
+/// empty cells, `analysis` is the object from
+/// [`analyze_puzzle_difficulty`] and `fingerprint` is shared by rotated,
+/// reflected and relabeled copies of the puzzle. `{"error": ...}` if the
+/// board is malformed or doesn't have exactly one solution.
+///
+/// # JavaScript Example
+/// ```javascript
+/// const bundle = export_puzzle_bundle(puzzle);
+/// await db.put("puzzles", JSON.parse(bundle));
+/// ```
+#[wasm_bindgen]
+pub fn export_puzzle_bundle(board: Vec<u8>) -> String {
+    if board.len() != BOARD_SIZE {
+        return r#"{"error": "Invalid board size"}"#.to_string();
+    }
+
+    let internal_board = from_js_board(&board);
+    if let Err(err) = check_well_formed(&internal_board) {
+        return format!(r#"{{"error": "{}"}}"#, err);
+    }
+    let Some(solution) = unique_solution(&internal_board) else {
+        return r#"{"error": "Puzzle does not have exactly one solution"}"#.to_string();
+    };
+
+    let bundle = PuzzleBundle {
+        puzzle: puzzle_line(&internal_board),
+        analysis: serde_json::from_str(&difficulty_json(&analyze_difficulty(&internal_board)))
+            .unwrap_or_default(),
+        solution: puzzle_line(&solution),
+        fingerprint: fingerprint(&internal_board),
+    };
+    serde_json::to_string(&bundle).unwrap_or_default()
+}
+
+/// Write a board as one line of 81 characters, `.` for empty cells
+fn puzzle_line(board: &[Option<u8>]) -> String {
+    board
+        .iter()
+        .map(|cell| match cell {
+            Some(num) => (b'0' + num) as char,
+            None => '.',
+        })
+        .collect()
+}
+
+/// Describe a board as plain text for bug reports
+///
+/// # Arguments
+/// * `board` - The board to describe (flat array of 81 numbers)
+///
+/// # Returns
+/// Nine grid rows with `|` and `-` box separators (`.` for empty cells),
+/// followed by a line with the clue count, analyzed level and solution
+/// count (`2+` for several). A malformed board is still printed, with the
+/// problem in place of the level; a board of the wrong size is described
+/// in one line.
+///
+/// # JavaScript Example
+/// ```javascript
+/// const report = "```\n" + describe_board(board) + "\n```";
+/// navigator.clipboard.writeText(report);
+/// ```
+#[wasm_bindgen]
+pub fn describe_board(board: Vec<u8>) -> String {
+    if board.len() != BOARD_SIZE {
+        return format!("Invalid board size: {} cells", board.len());
+    }
+
+    let mut lines = Vec::new();
+    for (row, cells) in board.chunks(GRID_SIZE).enumerate() {
+        if row > 0 && row % BOX_SIZE == 0 {
+            lines.push("------+-------+------".to_string());
+        }
+        let boxes: Vec<String> = cells
+            .chunks(BOX_SIZE)
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|&value| match value {
+                        0 => ".".to_string(),
+                        _ => value.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect();
+        lines.push(boxes.join(" | "));
+    }
+
+    let internal_board = from_js_board(&board);
+    let clues = internal_board.iter().filter(|cell| cell.is_some()).count();
+    let level = match check_well_formed(&internal_board) {
+        Ok(()) => analyze_difficulty(&internal_board).level.name().to_string(),
+        Err(err) => format!("invalid ({})", err),
+    };
+    let solutions = match count_solutions(&internal_board, 2) {
+        2 => "2+".to_string(),
+        count => count.to_string(),
+    };
+    lines.push(format!(
+        "Clues: {}, Level: {}, Solutions: {}",
+        clues, level, solutions
+    ));
+    lines.join("\n")
+}
+
+/// Estimate how long a human would take to solve a puzzle
+///
+/// Each technique application on the solve path costs a number of seconds.
+/// Publishers with their own timing data can pass per-technique weights;
+/// techniques left out use built-in defaults.
+///
+/// # Arguments
+/// * `board` - The puzzle to time (flat array of 81 numbers)
+/// * `weights` - `undefined`, or an object mapping technique names (as in
+///   `hardest_technique`) to seconds per application
+///
+/// # Returns
+/// The estimate in seconds, or `{ error }` for a malformed board or an
+/// unknown technique name
+///
+/// # JavaScript Example
+/// ```javascript
+/// const seconds = estimate_solve_seconds(puzzle, { "Hidden Single": 8, "X-Wing": 90 });
+/// label.textContent = `About ${Math.round(seconds / 60)} min`;
+/// ```
+#[wasm_bindgen]
+pub fn estimate_solve_seconds(board: Vec<u8>, weights: JsValue) -> JsValue {
+    let internal_board = from_js_board(&board);
+    if let Err(err) = check_well_formed(&internal_board) {
+        return js_error(&err.to_string());
+    }
+
+    let named: HashMap<String, f64> = if weights.is_undefined() || weights.is_null() {
+        HashMap::new()
+    } else {
+        match serde_wasm_bindgen::from_value(weights) {
+            Ok(named) => named,
+            Err(_) => return js_error("weights must map technique names to seconds"),
+        }
+    };
+    let mut time_weights = Vec::new();
+    for (name, seconds) in named {
+        match technique_from_name(&name) {
+            Some(technique) => time_weights.push((technique, seconds)),
+            None => return js_error(&format!("Unknown technique: {}", name)),
+        }
+    }
+
+    let analysis = analyze_difficulty_weighted(&internal_board, Some(&time_weights));
+    to_js_value(&analysis.estimated_seconds)
+}
+
+/// The effect of removing one given, as returned by [`clue_removal_impact`]
+#[derive(serde::Serialize)]
+struct ClueImpactRecord {
+    index: usize,
+    still_unique: bool,
+    resulting_level: &'static str,
+}
+
+/// Report what removing each given would do to a puzzle
+///
+/// Runs a uniqueness check and a full difficulty analysis per given, so it
+/// suits puzzle editors rather than real-time use.
+///
+/// # Arguments
+/// * `board` - The puzzle being edited (flat array of 81 numbers)
+///
+/// # Returns
+/// An array of `{ index, still_unique, resulting_level }`, one per given,
+/// or `{ error }` for a malformed board
+///
+/// # JavaScript Example
+/// ```javascript
+/// const safe = clue_removal_impact(puzzle)
+///     .filter(c => c.still_unique && c.resulting_level === currentLevel);
+/// ```
+#[wasm_bindgen]
+pub fn clue_removal_impact(board: Vec<u8>) -> JsValue {
+    let internal_board = from_js_board(&board);
+    if let Err(err) = check_board_values(&internal_board) {
+        return js_error(&err.to_string());
+    }
+
+    let impacts: Vec<ClueImpactRecord> = internal_clue_removal_impact(&internal_board)
+        .into_iter()
+        .map(|impact| ClueImpactRecord {
+            index: impact.index,
+            still_unique: impact.still_unique,
+            resulting_level: impact.resulting_level.name(),
+        })
+        .collect();
+    to_js_value(&impacts)
+}
+
+/// An analysis record as returned to JavaScript by [`analyze_batch`]
+#[derive(serde::Serialize)]
+struct BatchRecord {
+    level: &'static str,
+    hardest_technique: &'static str,
+    score: u32,
+    branching_factor: f64,
+    clue_count: usize,
+    solution_count: usize,
+}
+
+/// Analyze the difficulty of many puzzles in one call
+///
+/// Avoids per-puzzle call overhead when analyzing large collections. Each
+/// record is flat, so the result can be written straight out as CSV rows.
+///
+/// # Arguments
+/// * `boards` - An array of puzzles, each a flat array of 81 numbers
+///
+/// # Returns
+/// One entry per input: `{ level, hardest_technique, score, branching_factor,
+/// clue_count, solution_count }` (solution counts stop at 2), or `{ error }`
+/// for a malformed board
+///
+/// # JavaScript Example
+/// ```javascript
+/// const rows = analyze_batch(puzzles);
+/// const csv = rows.map(r => [r.level, r.hardest_technique, r.score].join(",")).join("\n");
+/// ```
+#[wasm_bindgen]
+pub fn analyze_batch(boards: JsValue) -> JsValue {
+    let boards: Vec<Vec<u8>> = match serde_wasm_bindgen::from_value(boards) {
+        Ok(boards) => boards,
+        Err(_) => return js_error("boards must be an array of number arrays"),
+    };
+
+    let internal_boards: Vec<Vec<Option<u8>>> =
+        boards.iter().map(|board| from_js_board(board)).collect();
+    let results = Array::new();
+    for result in internal_analyze_batch(&internal_boards) {
+        let entry = match result {
+            Ok(record) => to_js_value(&BatchRecord {
+                level: record.level.name(),
+                hardest_technique: record.hardest_technique.display_name(),
+                score: record.score,
+                branching_factor: record.branching_factor,
+                clue_count: record.clue_count,
+                solution_count: record.solution_count,
+            }),
+            Err(error) => js_error(&error.to_string()),
+        };
+        results.push(&entry);
+    }
+    results.into()
+}
+
+/// Check a whole file of imported puzzles in one call
+///
+/// The gate before accepting a third-party puzzle pack: every board is
+/// checked for well-formedness (see [`is_well_formed_puzzle`]) and for a
+/// unique solution.
+///
+/// # Arguments
+/// * `boards` - An array of puzzles, each a flat array of 81 numbers
+///
+/// # Returns
+/// One `{ index, well_formed, unique, solvable }` per input, or `{ error }`
+/// if `boards` isn't an array of number arrays
+///
+/// # JavaScript Example
+/// ```javascript
+/// const rejected = verify_batch(pack.puzzles).filter(v => !v.unique);
+/// if (rejected.length > 0) {
+///     console.warn(`Puzzles ${rejected.map(v => v.index).join(", ")} are not valid`);
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn verify_batch(boards: JsValue) -> JsValue {
+    let boards: Vec<Vec<u8>> = match serde_wasm_bindgen::from_value(boards) {
+        Ok(boards) => boards,
+        Err(_) => return js_error("boards must be an array of number arrays"),
+    };
+
+    let internal_boards: Vec<Vec<Option<u8>>> =
+        boards.iter().map(|board| from_js_board(board)).collect();
+    to_js_value(&internal_verify_batch(&internal_boards))
+}
+
+/// Solve a puzzle step by step using human-style techniques
+///
+/// Returns information about what techniques were used and the
+/// intermediate steps taken during solving.
+///
+/// # Arguments  
+/// * `board` - The puzzle to solve (flat array of 81 numbers)
+///
+/// # Returns
+/// A JSON string containing the solving steps and techniques used
+///
+/// # JavaScript Example
+/// ```javascript
+/// const result = JSON.parse(solve_with_techniques(puzzle));
+/// console.log(`Used techniques: ${result.techniques.join(', ')}`);
+/// console.log(`Solved: ${result.solved}`);
+/// ```
+#[wasm_bindgen]
+pub fn solve_with_techniques(board: Vec<u8>) -> String {
+    if board.len() != BOARD_SIZE {
+        return r#"{"error": "Invalid board size"}"#.to_string();
+    }
+
+    let internal_board = from_js_board(&board);
+    let mut solver = HumanStyleSolver::new(&internal_board);
+
+    let solved = solver.solve_with_techniques();
+    let techniques_used = solver.get_techniques_used();
+    let final_board = solver.get_board();
+    let branching_factor = solver.calculate_branching_factor();
+
+    // Convert techniques to strings
+    let technique_names: Vec<&str> = techniques_used.iter().map(|t| t.display_name()).collect();
+
+    let techniques_json = technique_names
+        .iter()
+        .map(|&t| format!(r#""{}""#, t))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let board_json = to_js_board(final_board)
+        .iter()
+        .map(|&n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        r#"{{"solved": {}, "techniques": [{}], "board": [{}], "branching_factor": {:.2}}}"#,
+        solved, techniques_json, board_json, branching_factor
+    )
+}
+
+/// Get a hint for the next move in a puzzle
+///
+/// Analyzes the current board state and suggests the next logical move
+/// that a human solver might make.
+///
+/// # Arguments
+/// * `board` - The current puzzle state (flat array of 81 numbers)
+///
+/// # Returns
+/// A JSON string with hint information (cell index, number, technique used)
+///
+/// # JavaScript Example
+/// ```javascript
+/// const hint = JSON.parse(get_hint(currentBoard));
+/// if (hint.cell !== -1) {
+///     console.log(`Try placing ${hint.number} at position ${hint.cell}`);
+///     console.log(`Technique: ${hint.technique}`);
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn get_hint(board: Vec<u8>) -> String {
+    if board.len() != BOARD_SIZE {
+        return r#"{"error": "Invalid board size"}"#.to_string();
+    }
+
+    let internal_board = from_js_board(&board);
+    let mut solver = HumanStyleSolver::new(&internal_board);
+
+    // Try to make one step of progress
+    let original_board = solver.get_board().to_vec();
+
+    // Attempt one round of basic techniques
+    if solver.apply_basic_techniques() {
+        // Find what changed
+        let new_board = solver.get_board();
+        for (index, (&old, &new)) in original_board.iter().zip(new_board.iter()).enumerate() {
+            if let (true, Some(num)) = (old != new, new) {
+                return format!(
+                    r#"{{"cell": {}, "number": {}, "technique": "Basic solving technique"}}"#,
+                    index, num
+                );
+            }
+        }
+    }
+
+    // No immediate hint available
+    r#"{"cell": -1, "number": 0, "technique": "No immediate hint available"}"#.to_string()
+}
+
+/// A hint as returned to JavaScript by [`hint_with_cost`]
+#[derive(serde::Serialize)]
+struct CostedHint {
+    cell: usize,
+    number: u8,
+    technique: &'static str,
+    cost: u32,
+    explanation: String,
+    unit_kind: Option<UnitKind>,
+    unit_index: Option<usize>,
+}
+
+/// A fish as returned to JavaScript by [`fish_pattern`]
+#[derive(serde::Serialize)]
+struct FishRecord {
+    base_kind: UnitKind,
+    base_lines: Vec<usize>,
+    cover_lines: Vec<usize>,
+    eliminations: Vec<usize>,
+}
+
+/// Find an X-Wing, Swordfish or Jellyfish for one digit, for drawing it
+///
+/// The board is not changed. Candidates come from the clues alone, and
+/// only a fish that removes at least one candidate is reported. X-Wings
+/// and Swordfish are the patterns the solver applies; Jellyfish are for
+/// display only.
+///
+/// # Arguments
+/// * `board` - The current board state (flat array of 81 numbers)
+/// * `digit` - The digit the fish is made of (1-9)
+/// * `size` - 2 for an X-Wing, 3 for a Swordfish, 4 for a Jellyfish
+///
+/// # Returns
+/// `{ base_kind: "Row" | "Column", base_lines, cover_lines, eliminations }`
+/// with lines numbered 0-8 and eliminations as cell indices, `null` if
+/// there is no such fish, or `{ error }` for a malformed board, digit or
+/// size
+///
+/// # JavaScript Example
+/// ```javascript
+/// const fish = fish_pattern(board, 4, 2);
+/// if (fish && !fish.error) drawFish(fish.base_kind, fish.base_lines, fish.cover_lines);
+/// ```
+#[wasm_bindgen]
+pub fn fish_pattern(board: Vec<u8>, digit: u8, size: usize) -> JsValue {
+    if board.len() != BOARD_SIZE {
+        return js_error("Invalid board size");
+    }
+    if !(1..=9).contains(&digit) {
+        return js_error(&format!("Invalid digit: {}", digit));
+    }
+    if !(2..=4).contains(&size) {
+        return js_error(&format!("Fish size must be 2-4, got {}", size));
+    }
+    let internal_board = from_js_board(&board);
+    if let Err(err) = check_well_formed(&internal_board) {
+        return js_error(&err.to_string());
+    }
+
+    match find_fish(&internal_board, digit, size) {
+        Some(fish) => to_js_value(&FishRecord {
+            base_kind: fish.base_kind,
+            base_lines: fish.base_lines,
+            cover_lines: fish.cover_lines,
+            eliminations: fish.eliminations,
+        }),
+        None => JsValue::NULL,
+    }
+}
+
+/// A stuck point as returned to JavaScript by [`solve_until_stuck`]
+#[derive(serde::Serialize)]
+struct StuckReport {
+    board: Vec<u8>,
+    empty_cells: Vec<usize>,
+    branch_cell: Option<usize>,
+    branch_candidates: Vec<u8>,
+}
+
+/// Solve a puzzle with logic only, stopping where a guess would be needed
+///
+/// Shows how far logical techniques can take a solver before it must
+/// guess.
+///
+/// # Arguments
+/// * `board` - The puzzle to solve (flat array of 81 numbers)
+///
+/// # Returns
+/// `{ board, empty_cells, branch_cell, branch_candidates }`, where
+/// `branch_cell` is `null` if logic solved the puzzle; `{ error }` for a
+/// malformed board
+///
+/// # JavaScript Example
+/// ```javascript
+/// const stuck = solve_until_stuck(puzzle);
+/// if (stuck.branch_cell !== null) {
+///     console.log(`Guess at ${stuck.branch_cell}: ${stuck.branch_candidates}`);
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn solve_until_stuck(board: Vec<u8>) -> JsValue {
+    let internal_board = from_js_board(&board);
+    if let Err(error) = check_board_values(&internal_board) {
+        return js_error(&error.to_string());
+    }
+
+    let stuck = internal_solve_until_stuck(&internal_board);
+    let (branch_cell, branch_candidates) = match stuck.branch {
+        Some((cell, candidates)) => (Some(cell), candidates),
+        None => (None, Vec::new()),
+    };
+    to_js_value(&StuckReport {
+        board: to_js_board(&stuck.board),
+        empty_cells: stuck.empty_cells,
+        branch_cell,
+        branch_candidates,
+    })
+}
+
+/// A solve step as returned to JavaScript by [`solve_trace`]
+#[derive(serde::Serialize)]
+struct TraceStep {
+    cell: usize,
+    number: u8,
+    technique: &'static str,
+    rank: u8,
+    branching_factor: f64,
+    explanation: String,
+    unit_kind: Option<UnitKind>,
+    unit_index: Option<usize>,
+}
+
+/// Solve a puzzle step by step, reporting the difficulty of each placement
+///
+/// Each step carries the technique's difficulty rank and the branching
+/// factor at that point, so the difficulty curve of a solve can be plotted.
+///
+/// # Arguments
+/// * `board` - The puzzle to solve (flat array of 81 numbers)
+///
+/// # Returns
+/// An array of `{ cell, number, technique, rank, branching_factor,
+/// explanation, unit_kind, unit_index }`, or `{ error }` for a malformed
+/// board. The unit fields are set as in [`hint_with_cost`]. The trace ends
+/// early if logic stalls.
+///
+/// # JavaScript Example
+/// ```javascript
+/// const steps = solve_trace(puzzle);
+/// plot(steps.map(step => step.rank), steps.map(step => step.branching_factor));
+/// ```
+#[wasm_bindgen]
+pub fn solve_trace(board: Vec<u8>) -> JsValue {
+    if board.len() != BOARD_SIZE {
+        return js_error("Invalid board size");
+    }
+
+    let steps: Vec<TraceStep> = internal_solve_trace(&from_js_board(&board))
+        .into_iter()
+        .map(|step| TraceStep {
+            cell: step.index,
+            number: step.value,
+            technique: step.technique.display_name(),
+            rank: step.rank,
+            branching_factor: step.branching_factor,
+            explanation: explain_step(&step),
+            unit_kind: step.unit.map(|(kind, _)| kind),
+            unit_index: step.unit.map(|(_, index)| index),
+        })
+        .collect();
+    to_js_value(&steps)
+}
+
+/// A solved cell as returned to JavaScript by [`annotated_solution`]
+#[derive(serde::Serialize)]
+struct AnnotatedCell {
+    cell: usize,
+    number: u8,
+    technique: &'static str,
+}
+
+/// Solve a puzzle, noting how each empty cell was deduced
+///
+/// Data for a post-game review that walks through the whole solution.
+///
+/// # Arguments
+/// * `puzzle` - The puzzle to solve (flat array of 81 numbers)
+///
+/// # Returns
+/// An array of `{ cell, number, technique }` with one entry per empty cell
+/// in index order. `technique` names the technique that placed the number,
+/// or is `"guess"` where logic stalled and backtracking filled the cell.
+/// `{ error }` if the puzzle is malformed or has no solution.
+///
+/// # JavaScript Example
+/// ```javascript
+/// for (const { cell, number, technique } of annotated_solution(puzzle)) {
+///     reviewGrid.annotate(cell, number, technique);
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn annotated_solution(puzzle: Vec<u8>) -> JsValue {
+    if puzzle.len() != BOARD_SIZE {
+        return js_error("Invalid board size");
+    }
+    let internal_board = from_js_board(&puzzle);
+    if let Err(err) = check_well_formed(&internal_board) {
+        return js_error(&err.to_string());
+    }
+
+    let Some(annotations) = internal_annotated_solution(&internal_board) else {
+        return js_error("Puzzle has no solution");
+    };
+    let cells: Vec<AnnotatedCell> = annotations
+        .into_iter()
+        .map(|annotation| AnnotatedCell {
+            cell: annotation.index,
+            number: annotation.value,
+            technique: match annotation.technique {
+                SolvingTechnique::TrialAndError => "guess",
+                technique => technique.display_name(),
+            },
+        })
+        .collect();
+    to_js_value(&cells)
+}
+
+/// A technique checkpoint as returned to JavaScript by [`technique_milestones`]
+#[derive(serde::Serialize)]
+struct MilestoneRecord {
+    fraction_solved: f64,
+    technique: &'static str,
+    rank: u8,
+}
+
+/// Report how far each technique tier carries a solve
+///
+/// For tutorial progressions such as "singles get you 40% of the way, then
+/// you need pairs".
+///
+/// # Arguments
+/// * `board` - The puzzle to analyze (flat array of 81 numbers)
+///
+/// # Returns
+/// An array of `{ fraction_solved, technique, rank }` in solving order, or
+/// `{ error }` for a malformed board. A last `"Trial and Error"` entry marks
+/// where logic stalls.
+///
+/// # JavaScript Example
+/// ```javascript
+/// for (const m of technique_milestones(puzzle)) {
+///     console.log(`${Math.round(m.fraction_solved * 100)}%: ${m.technique}`);
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn technique_milestones(board: Vec<u8>) -> JsValue {
+    if board.len() != BOARD_SIZE {
+        return js_error("Invalid board size");
+    }
+
+    let milestones: Vec<MilestoneRecord> = internal_technique_milestones(&from_js_board(&board))
+        .into_iter()
+        .map(|milestone| MilestoneRecord {
+            fraction_solved: milestone.fraction_solved,
+            technique: milestone.technique.display_name(),
+            rank: milestone.technique.rank(),
+        })
+        .collect();
+    to_js_value(&milestones)
+}
+
+/// A placement as returned to JavaScript by [`all_immediate_placements`]
+#[derive(serde::Serialize)]
+struct PlacementRecord {
+    index: usize,
+    value: u8,
+    technique: &'static str,
+}
+
+/// List every naked and hidden single available right now
+///
+/// Nothing is applied, so the front-end can animate all obvious cells
+/// filling together. Singles that only appear after these are placed are
+/// not included.
+///
+/// # Arguments
+/// * `board` - The current puzzle state (flat array of 81 numbers)
+///
+/// # Returns
+/// An array of `{ index, value, technique }` in cell order, or `{ error }`
+/// if the board is not well-formed
+///
+/// # JavaScript Example
+/// ```javascript
+/// for (const p of all_immediate_placements(board)) {
+///     animateFill(p.index, p.value);
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn all_immediate_placements(board: Vec<u8>) -> JsValue {
+    let internal_board = from_js_board(&board);
+    if let Err(err) = check_well_formed(&internal_board) {
+        return js_error(&err.to_string());
+    }
+
+    let placements: Vec<PlacementRecord> = internal_all_immediate_placements(&internal_board)
+        .into_iter()
+        .map(|hint| PlacementRecord {
+            index: hint.index,
+            value: hint.value,
+            technique: hint.technique.display_name(),
+        })
+        .collect();
+    to_js_value(&placements)
+}
+
+/// A forced cell as returned to JavaScript by [`trivially_forced_cells`]
+#[derive(serde::Serialize)]
+struct ForcedCellRecord {
+    index: usize,
+    value: u8,
+    unit_kind: UnitKind,
+    unit_index: usize,
+}
+
+/// List cells that are the last gap in their row, column or box
+///
+/// The gentlest hint category for beginners: the missing digit can be read
+/// straight off a unit that already has 8 of its 9 digits.
+///
+/// # Arguments
+/// * `board` - The current puzzle state (flat array of 81 numbers)
+///
+/// # Returns
+/// An array of `{ index, value, unit_kind, unit_index }` in cell order,
+/// where `unit_kind` is `"Row"`, `"Column"` or `"Box"`, or `{ error }` if
+/// the board is not well-formed
+///
+/// # JavaScript Example
+/// ```javascript
+/// const [first] = trivially_forced_cells(board);
+/// if (first) say(`Only ${first.value} is missing from this ${first.unit_kind.toLowerCase()}`);
+/// ```
+#[wasm_bindgen]
+pub fn trivially_forced_cells(board: Vec<u8>) -> JsValue {
+    let internal_board = from_js_board(&board);
+    if let Err(err) = check_well_formed(&internal_board) {
+        return js_error(&err.to_string());
+    }
+
+    let forced: Vec<ForcedCellRecord> = internal_trivially_forced_cells(&internal_board)
+        .into_iter()
+        .filter_map(|hint| {
+            let (unit_kind, unit_index) = hint.unit?;
+            Some(ForcedCellRecord {
+                index: hint.index,
+                value: hint.value,
+                unit_kind,
+                unit_index,
+            })
+        })
+        .collect();
+    to_js_value(&forced)
+}
+
+/// Get every cell's candidates as raw bitmasks
+///
+/// A compact form of the candidate grid for large transfers, such as
+/// syncing solver state over the network.
+///
+/// # Arguments
+/// * `board` - The current puzzle state (flat array of 81 numbers)
+///
+/// # Returns
+/// 81 masks where bit `k` set means `k + 1` is a candidate (filled cells
+/// are 0), or an empty array if the board is not well-formed
+///
+/// # JavaScript Example
+/// ```javascript
+/// const masks = get_candidate_masks(board);
+/// const marks = [...Array(9).keys()].filter(k => masks[cell] & (1 << k)).map(k => k + 1);
+/// ```
+#[wasm_bindgen]
+pub fn get_candidate_masks(board: Vec<u8>) -> Vec<u16> {
+    let internal_board = from_js_board(&board);
+    if let Err(err) = check_well_formed(&internal_board) {
+        console::log_1(&err.to_string().into());
+        return Vec::new();
+    }
+
+    candidate_masks(&internal_board)
+}
+
+/// Get a hint along with the cost of the technique behind it
+///
+/// Games can charge more for hints that rely on harder techniques, with
+/// guessing being the most expensive.
 ///
 /// # Arguments
-/// * `board` - The puzzle to check (flat array of 81 numbers)
+/// * `board` - The current puzzle state (flat array of 81 numbers)
 ///
 /// # Returns
-/// `true` if the puzzle has exactly one solution
+/// `{ cell, number, technique, cost, explanation, unit_kind, unit_index }`,
+/// or `{ error }` if no hint exists. `explanation` is a sentence describing
+/// the reasoning. For hidden singles, `unit_kind` (`"Row"`, `"Column"` or
+/// `"Box"`) and `unit_index` (0-8) name the unit where the number had one
+/// spot left; otherwise both are `null`.
 ///
 /// # JavaScript Example
 /// ```javascript
-/// const hasUniqueSolution = check_unique_solution(puzzle);
-/// if (!hasUniqueSolution) {
-///     console.log("This puzzle has multiple solutions or no solution!");
+/// const hint = hint_with_cost(currentBoard);
+/// if (!hint.error) {
+///     score -= hint.cost;
+///     tooltip.textContent = hint.explanation;
 /// }
 /// ```
 #[wasm_bindgen]
-pub fn check_unique_solution(board: Vec<u8>) -> bool {
+pub fn hint_with_cost(board: Vec<u8>) -> JsValue {
     if board.len() != BOARD_SIZE {
-        return false;
+        return js_error("Invalid board size");
     }
 
-    let internal_board = from_js_board(&board);
-    has_unique_solution(&internal_board)
+    match find_hint(&from_js_board(&board)) {
+        Some(hint) => to_js_value(&CostedHint {
+            cell: hint.index,
+            number: hint.value,
+            technique: hint.technique.display_name(),
+            cost: hint.technique.hint_cost(),
+            explanation: explain_hint(&hint),
+            unit_kind: hint.unit.map(|(kind, _)| kind),
+            unit_index: hint.unit.map(|(_, index)| index),
+        }),
+        None => js_error("No hint available"),
+    }
 }
 
-/// Solve a Sudoku puzzle completely
+/// A region to highlight, as returned by [`first_move_region`]
+#[derive(serde::Serialize)]
+struct RegionRecord {
+    kind: UnitKind,
+    unit_index: usize,
+    cells: Vec<usize>,
+}
+
+/// Find the row, column or box where the next deduction is made
 ///
-/// Uses backtracking to find a complete solution to the puzzle.
-/// Returns the original board if no solution exists.
+/// A softer hint than [`hint_with_cost`]: tutorials can circle the region
+/// first and reveal the cell only if the player is still stuck.
 ///
 /// # Arguments
-/// * `board` - The puzzle to solve (flat array of 81 numbers)
+/// * `board` - The current puzzle state (flat array of 81 numbers)
 ///
 /// # Returns
-/// The solved board, or the original board if unsolvable
+/// `{ kind: "Row" | "Column" | "Box", unit_index, cells }`, `null` if the
+/// board is full or unsolvable, or `{ error }` for a malformed board
 ///
 /// # JavaScript Example
 /// ```javascript
-/// const solution = solve_puzzle(puzzle);
-/// if (solution.every((cell, i) => cell === puzzle[i] || puzzle[i] === 0)) {
-///     console.log("Found solution!");
-/// } else {
-///     console.log("No solution exists");
-/// }
+/// const region = first_move_region(board);
+/// if (region && !region.error) circleCells(region.cells);
 /// ```
 #[wasm_bindgen]
-pub fn solve_puzzle(board: Vec<u8>) -> Vec<u8> {
+pub fn first_move_region(board: Vec<u8>) -> JsValue {
     if board.len() != BOARD_SIZE {
-        console::log_1(&"Invalid board size for solving".into());
-        return board;
+        return js_error("Invalid board size");
+    }
+    let internal_board = from_js_board(&board);
+    if let Err(err) = check_board_values(&internal_board) {
+        return js_error(&err.to_string());
     }
 
-    let mut internal_board = from_js_board(&board);
-
-    if solve_board(&mut internal_board) {
-        to_js_board(&internal_board)
-    } else {
-        console::log_1(&"No solution found for the given puzzle".into());
-        board // Return original if unsolvable
+    match internal_first_move_region(&internal_board) {
+        Some((kind, unit_index)) => {
+            let cells = match kind {
+                UnitKind::Row => row_cells(unit_index),
+                UnitKind::Column => column_cells(unit_index),
+                UnitKind::Box => box_cells(unit_index),
+            };
+            to_js_value(&RegionRecord {
+                kind,
+                unit_index,
+                cells: cells.to_vec(),
+            })
+        }
+        None => JsValue::NULL,
     }
 }
 
-/// Analyze the difficulty of a puzzle
-///
-/// Returns detailed information about what techniques are required
-/// to solve the puzzle and estimates the overall difficulty.
+/// Get the row, column and box a cell belongs to
 ///
 /// # Arguments
-/// * `board` - The puzzle to analyze (flat array of 81 numbers)
+/// * `index` - The cell index (0-80)
 ///
 /// # Returns
-/// A JSON string containing difficulty analysis
+/// `[row, col, box]` (each 0-8), or an empty array for an out-of-range index
 ///
 /// # JavaScript Example
 /// ```javascript
-/// const analysis = JSON.parse(analyze_puzzle_difficulty(puzzle));
-/// console.log(`Difficulty: ${analysis.level}, Techniques: ${analysis.techniques}`);
+/// const [row, col, box] = cell_units(40); // [4, 4, 4]
 /// ```
 #[wasm_bindgen]
-pub fn analyze_puzzle_difficulty(board: Vec<u8>) -> String {
-    if board.len() != BOARD_SIZE {
-        return r#"{"error": "Invalid board size"}"#.to_string();
+pub fn cell_units(index: usize) -> Vec<u8> {
+    if index >= BOARD_SIZE {
+        return Vec::new();
     }
 
-    let internal_board = from_js_board(&board);
-    let analysis = analyze_difficulty(&internal_board);
-
-    // Convert to JSON manually for simplicity
-    let level_str = match analysis.level {
-        DifficultyLevel::VeryEasy => "VeryEasy",
-        DifficultyLevel::Easy => "Easy",
-        DifficultyLevel::Medium => "Medium",
-        DifficultyLevel::Hard => "Hard",
-        DifficultyLevel::Expert => "Expert",
-    };
-
-    let technique_str = match analysis.hardest_technique {
-        SolvingTechnique::NakedSingle => "Naked Single",
-        SolvingTechnique::HiddenSingle => "Hidden Single",
-        SolvingTechnique::NakedPair => "Naked Pair",
-        SolvingTechnique::HiddenPair => "Hidden Pair",
-        SolvingTechnique::BoxLineReduction => "Box/Line Reduction",
-        SolvingTechnique::PointingPairs => "Pointing Pair",
-        SolvingTechnique::XWing => "X-Wing",
-        SolvingTechnique::PointingTriples => "Pointing Triples",
-        SolvingTechnique::Swordfish => "Swordfish",
-        SolvingTechnique::Coloring => "Coloring",
-        SolvingTechnique::XYWing => "XY-Wing",
-        SolvingTechnique::XYChain => "XY-Chain",
-        SolvingTechnique::ForcingChain => "Forcing Chain",
-        SolvingTechnique::TrialAndError => "Trial and Error",
-    };
-
-    format!(
-        r#"{{"level": "{}", "hardest_technique": "{}", "technique_diversity": {}, "branching_factor": {:.2}}}"#,
-        level_str, technique_str, analysis.technique_diversity, analysis.branching_factor
-    )
+    let (row, col, box_index) = unit_indices(index);
+    vec![row as u8, col as u8, box_index as u8]
 }
 
-/// Solve a puzzle step by step using human-style techniques
+/// Cross-hatch a number to show where it can still go in each box
 ///
-/// Returns information about what techniques were used and the
-/// intermediate steps taken during solving.
+/// Each placed copy of `digit` rules out its row and column, which is how
+/// beginners scan for placements. Tutorials can shade the blocked lines and
+/// highlight the cells that remain.
 ///
-/// # Arguments  
-/// * `board` - The puzzle to solve (flat array of 81 numbers)
+/// # Arguments
+/// * `board` - The current puzzle state (flat array of 81 numbers)
+/// * `digit` - The number to cross-hatch (1-9)
 ///
 /// # Returns
-/// A JSON string containing the solving steps and techniques used
+/// Nine arrays of cell indices, one per box, or `{ error }` for a malformed
+/// board or digit
 ///
 /// # JavaScript Example
 /// ```javascript
-/// const result = JSON.parse(solve_with_techniques(puzzle));
-/// console.log(`Used techniques: ${result.techniques.join(', ')}`);
-/// console.log(`Solved: ${result.solved}`);
+/// const boxes = crosshatch(board, 5);
+/// boxes.forEach(cells => { if (cells.length === 1) highlight(cells[0]); });
 /// ```
 #[wasm_bindgen]
-pub fn solve_with_techniques(board: Vec<u8>) -> String {
-    if board.len() != BOARD_SIZE {
-        return r#"{"error": "Invalid board size"}"#.to_string();
+pub fn crosshatch(board: Vec<u8>, digit: u8) -> JsValue {
+    if !(1..=9).contains(&digit) {
+        return js_error("Digit must be between 1 and 9");
     }
-
     let internal_board = from_js_board(&board);
-    let mut solver = HumanStyleSolver::new(&internal_board);
-
-    let solved = solver.solve_with_techniques();
-    let techniques_used = solver.get_techniques_used();
-    let final_board = solver.get_board();
-    let branching_factor = solver.calculate_branching_factor();
-
-    // Convert techniques to strings
-    let technique_names: Vec<&str> = techniques_used
-        .iter()
-        .map(|t| match t {
-            SolvingTechnique::NakedSingle => "Naked Single",
-            SolvingTechnique::HiddenSingle => "Hidden Single",
-            SolvingTechnique::NakedPair => "Naked Pair",
-            SolvingTechnique::HiddenPair => "Hidden Pair",
-            SolvingTechnique::BoxLineReduction => "Box/Line Reduction",
-            SolvingTechnique::PointingPairs => "Pointing Pair",
-            SolvingTechnique::XWing => "X-Wing",
-            SolvingTechnique::PointingTriples => "Pointing Triples",
-            SolvingTechnique::Swordfish => "Swordfish",
-            SolvingTechnique::Coloring => "Coloring",
-            SolvingTechnique::XYWing => "XY-Wing",
-            SolvingTechnique::XYChain => "XY-Chain",
-            SolvingTechnique::ForcingChain => "Forcing Chain",
-            SolvingTechnique::TrialAndError => "Trial and Error",
-        })
-        .collect();
-
-    let techniques_json = technique_names
-        .iter()
-        .map(|&t| format!(r#""{}""#, t))
-        .collect::<Vec<_>>()
-        .join(",");
-
-    let board_json = to_js_board(final_board)
-        .iter()
-        .map(|&n| n.to_string())
-        .collect::<Vec<_>>()
-        .join(",");
+    if let Err(error) = check_board_values(&internal_board) {
+        return js_error(&error.to_string());
+    }
 
-    format!(
-        r#"{{"solved": {}, "techniques": [{}], "board": [{}], "branching_factor": {:.2}}}"#,
-        solved, techniques_json, board_json, branching_factor
-    )
+    to_js_value(&internal_crosshatch(&internal_board, digit))
 }
 
-/// Get a hint for the next move in a puzzle
-///
-/// Analyzes the current board state and suggests the next logical move
-/// that a human solver might make.
+/// Generate a 16x16 hexadoku puzzle
 ///
 /// # Arguments
-/// * `board` - The current puzzle state (flat array of 81 numbers)
+/// * `seed` - Seed for reproducible generation
 ///
 /// # Returns
-/// A JSON string with hint information (cell index, number, technique used)
+/// A flat array of 256 numbers (1-16, 0 for empty cells)
 ///
 /// # JavaScript Example
 /// ```javascript
-/// const hint = JSON.parse(get_hint(currentBoard));
-/// if (hint.cell !== -1) {
-///     console.log(`Try placing ${hint.number} at position ${hint.cell}`);
-///     console.log(`Technique: ${hint.technique}`);
-/// }
+/// const puzzle = generate_hexadoku(42);
+/// const symbol = n => n === 0 ? "." : (n - 1).toString(16).toUpperCase();
 /// ```
 #[wasm_bindgen]
-pub fn get_hint(board: Vec<u8>) -> String {
-    if board.len() != BOARD_SIZE {
-        return r#"{"error": "Invalid board size"}"#.to_string();
-    }
-
-    let internal_board = from_js_board(&board);
-    let mut solver = HumanStyleSolver::new(&internal_board);
-
-    // Try to make one step of progress
-    let original_board = solver.get_board().to_vec();
+pub fn generate_hexadoku(seed: u64) -> Vec<u8> {
+    to_js_board(&hexadoku::generate_hexadoku(seed))
+}
 
-    // Attempt one round of basic techniques
-    if solver.apply_basic_techniques() {
-        // Find what changed
-        let new_board = solver.get_board();
-        for (index, (&old, &new)) in original_board.iter().zip(new_board.iter()).enumerate() {
-            if old != new && new.is_some() {
-                return format!(
-                    r#"{{"cell": {}, "number": {}, "technique": "Basic solving technique"}}"#,
-                    index,
-                    new.unwrap()
-                );
-            }
-        }
+/// Solve a 16x16 hexadoku puzzle
+///
+/// # Arguments
+/// * `board` - The puzzle (flat array of 256 numbers, 1-16, 0 for empty)
+///
+/// # Returns
+/// The solved board, or the original board if it is malformed or unsolvable
+///
+/// # JavaScript Example
+/// ```javascript
+/// const solution = solve_hexadoku(puzzle);
+/// ```
+#[wasm_bindgen]
+pub fn solve_hexadoku(board: Vec<u8>) -> Vec<u8> {
+    let mut internal_board = from_js_board(&board);
+    if internal_board.len() == HEX_BOARD_SIZE && hexadoku::solve_hexadoku(&mut internal_board) {
+        to_js_board(&internal_board)
+    } else {
+        console::log_1(&"No solution found for the given hexadoku".into());
+        board
     }
-
-    // No immediate hint available
-    r#"{"cell": -1, "number": 0, "technique": "No immediate hint available"}"#.to_string()
 }
 
 /// Initialize the WASM module
@@ -450,10 +3090,30 @@ pub fn get_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()
 }
 
+/// Get the n-th complete solution grid
+///
+/// Deterministic, so test fixtures can use varied valid grids without
+/// hardcoding them.
+///
+/// # Arguments
+/// * `n` - Index of the grid
+///
+/// # Returns
+/// A completely filled board as a flat array of 81 numbers
+///
+/// # JavaScript Example
+/// ```javascript
+/// const grids = [0, 1, 2].map(n => nth_solution_grid(BigInt(n)));
+/// ```
+#[wasm_bindgen]
+pub fn nth_solution_grid(n: u64) -> Vec<u8> {
+    to_js_board(&internal_nth_solution_grid(n))
+}
+
 /// Generate a complete solved Sudoku board using a specific seed for reproducible results
 fn generate_solved_board_with_seed(seed: u64) -> Vec<u8> {
     let mut board = vec![None; BOARD_SIZE];
-    let mut rng = SmallRng::seed_from_u64(seed);
+    let mut rng = make_rng(Some(seed));
 
     fill_board_seeded(&mut board, &mut rng);
 
@@ -521,16 +3181,16 @@ fn is_valid_placement_seeded(board: &[Option<u8>], row: usize, col: usize, num:
 fn create_puzzle_with_seed(solved_board: &[u8], difficulty: u8, seed: u64) -> Vec<Option<u8>> {
     // Use seeded approach to ensure reproducible puzzles
     let mut board: Vec<Option<u8>> = solved_board.iter().map(|&x| Some(x)).collect();
-    let mut rng = SmallRng::seed_from_u64(seed.wrapping_add(difficulty as u64));
+    let mut rng = make_rng(Some(seed.wrapping_add(difficulty as u64)));
 
     // Updated cells_to_remove to match modal descriptions and new difficulty analysis
-    let cells_to_remove = match difficulty {
-        1 => 36, // VeryEasy - leave 45 clues (matches modal "35-45 clues")
-        2 => 40, // Easy - leave 41 clues (matches modal "35-45 clues")
-        3 => 48, // Medium - leave 33 clues (matches modal "30-35 clues")
-        4 => 53, // Hard - leave 28 clues (matches modal "25-30 clues" but safer)
-        5 => 60, // Expert - leave 21 clues (matches modal "17-24 clues")
-        _ => 48, // Default to Medium
+    let level = DifficultyLevel::from_legacy_number(difficulty).unwrap_or(DifficultyLevel::Medium);
+    let cells_to_remove = match level {
+        DifficultyLevel::VeryEasy => 36, // leave 45 clues (matches modal "35-45 clues")
+        DifficultyLevel::Easy => 40,     // leave 41 clues (matches modal "35-45 clues")
+        DifficultyLevel::Medium => 48,   // leave 33 clues (matches modal "30-35 clues")
+        DifficultyLevel::Hard => 53,     // leave 28 clues (matches modal "25-30 clues" but safer)
+        DifficultyLevel::Expert => 60,   // leave 21 clues (matches modal "17-24 clues")
     };
 
     let mut indices: Vec<usize> = (0..BOARD_SIZE).collect();
@@ -561,7 +3221,9 @@ fn create_puzzle_with_seed(solved_board: &[u8], difficulty: u8, seed: u64) -> Ve
 /// Create a new Sudoku game with specified difficulty and seed (legacy compatibility)
 ///
 /// # Arguments
-/// * `difficulty` - Difficulty level (1=Easy, 2=Medium, 3=Hard, 4=Expert)
+/// * `difficulty` - Legacy difficulty level (1=VeryEasy, 2=Easy, 3=Medium,
+///   4=Hard, 5=Expert), the same numbers as `level_numeric` in
+///   [`analyze_puzzle_difficulty`]
 /// * `seed` - Seed for deterministic puzzle generation
 ///
 /// # Returns
@@ -581,19 +3243,46 @@ pub fn createGameWithSeed(difficulty: u8, seed: u64) -> JsValue {
     let solved_board = generate_solved_board_with_seed(seed);
     let puzzle = create_puzzle_with_seed(&solved_board, difficulty, seed);
 
-    // Convert to JavaScript array of numbers/undefined
-    let js_array = Array::new();
-    for cell in puzzle {
-        match cell {
-            Some(num) => {
-                js_array.push(&JsValue::from(num));
-            }
-            None => {
-                js_array.push(&JsValue::undefined());
-            }
-        }
-    }
-    js_array.into()
+    board_to_js_array(&puzzle).into()
+}
+
+/// Create a seeded game together with its difficulty analysis (legacy compatibility)
+///
+/// The puzzle is the same one [`createGameWithSeed`] returns for the same
+/// arguments, so both the puzzle and its analysis can be reproduced.
+///
+/// # Arguments
+/// * `difficulty` - Legacy difficulty level (1-5, see [`createGameWithSeed`])
+/// * `seed` - Seed for deterministic puzzle generation
+///
+/// # Returns
+/// `{ puzzle, analysis }`, where `puzzle` is a JavaScript array (numbers for
+/// clues, undefined for empty cells) and `analysis` is the object described
+/// by [`analyze_puzzle_difficulty`]
+///
+/// # JavaScript Example
+/// ```javascript
+/// const { puzzle, analysis } = createGameWithAnalysisSeed(3, BigInt(seed));
+/// showBadge(analysis.level);
+/// ```
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+pub fn createGameWithAnalysisSeed(difficulty: u8, seed: u64) -> JsValue {
+    let (puzzle, analysis) = seeded_game_with_analysis(difficulty, seed);
+    let analysis = js_sys::JSON::parse(&difficulty_json(&analysis)).unwrap_or(JsValue::NULL);
+
+    let js_result = js_sys::Object::new();
+    js_sys::Reflect::set(&js_result, &"puzzle".into(), &board_to_js_array(&puzzle)).unwrap();
+    js_sys::Reflect::set(&js_result, &"analysis".into(), &analysis).unwrap();
+    js_result.into()
+}
+
+/// Build the puzzle and analysis behind [`createGameWithAnalysisSeed`]
+fn seeded_game_with_analysis(difficulty: u8, seed: u64) -> (Vec<Option<u8>>, DifficultyAnalysis) {
+    let solved_board = generate_solved_board_with_seed(seed);
+    let puzzle = create_puzzle_with_seed(&solved_board, difficulty, seed);
+    let analysis = analyze_difficulty(&puzzle);
+    (puzzle, analysis)
 }
 
 /// Validate a Sudoku board and return detailed validation result (legacy compatibility)
@@ -605,29 +3294,17 @@ pub fn createGameWithSeed(difficulty: u8, seed: u64) -> JsValue {
 /// * `board` - JavaScript array representing current board state
 ///
 /// # Returns
-/// JavaScript object with { invalidIndices: number[], isComplete: boolean }
+/// JavaScript object with { invalidIndices: number[], isComplete: boolean },
+/// or `{ error }` if the array isn't 81 cells of 1-9 or `undefined`
 #[wasm_bindgen]
 #[allow(non_snake_case)]
 pub fn validateBoard(board: JsValue) -> JsValue {
     console::log_1(&"Validating board".into());
 
-    // Convert JavaScript array to Rust Vec
-    let js_array: Array = board.into();
-    let mut rust_board = Vec::with_capacity(BOARD_SIZE);
-
-    for i in 0..BOARD_SIZE {
-        let cell = js_array.get(i as u32);
-        if cell.is_undefined() {
-            rust_board.push(None);
-        } else {
-            let num = cell.as_f64().unwrap_or(0.0) as u8;
-            if num >= 1 && num <= 9 {
-                rust_board.push(Some(num));
-            } else {
-                rust_board.push(None);
-            }
-        }
-    }
+    let rust_board = match board_from_js_array(&board.into()) {
+        Ok(rust_board) => rust_board,
+        Err(err) => return js_error(&err.to_string()),
+    };
 
     let result = internal_validate_board(&rust_board);
 
@@ -659,6 +3336,8 @@ pub fn validateBoard(board: JsValue) -> JsValue {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_fixtures::{parse_js, SINGLES_PUZZLE, SOLVED_GRID, STALLED_PUZZLE};
+    use crate::types::Confidence;
 
     #[test]
     fn test_board_conversion() {
@@ -670,6 +3349,311 @@ mod tests {
         assert_eq!(back_to_internal, internal);
     }
 
+    #[test]
+    fn test_seeded_game_with_analysis_is_reproducible() {
+        let (puzzle, analysis) = seeded_game_with_analysis(3, 99);
+        let (again, analysis_again) = seeded_game_with_analysis(3, 99);
+        assert_eq!(puzzle, again);
+        assert_eq!(difficulty_json(&analysis), difficulty_json(&analysis_again));
+
+        // Same puzzle as the analysis-free legacy path
+        let solved_board = generate_solved_board_with_seed(99);
+        assert_eq!(create_puzzle_with_seed(&solved_board, 3, 99), puzzle);
+
+        let (other, _) = seeded_game_with_analysis(3, 100);
+        assert_ne!(other, puzzle);
+    }
+
+    #[test]
+    fn test_config_json_round_trip() {
+        let json = config_to_json(1);
+        let config = parse_config_json(&json).unwrap();
+        assert_eq!(
+            config,
+            GeneratorConfig::for_difficulty(DifficultyLevel::Medium)
+        );
+
+        let original = PuzzleGenerator::new(config.clone()).generate_with_seed(7);
+        assert!(original.is_some());
+        assert_eq!(
+            generate_with_config_json(json, 7),
+            to_js_board(&original.unwrap())
+        );
+
+        // Missing fields fall back to defaults; invalid settings are rejected
+        assert_eq!(parse_config_json("{}"), Ok(GeneratorConfig::default()));
+        assert!(parse_config_json(r#"{"min_clues":16}"#)
+            .unwrap_err()
+            .contains("fewer than 17 clues"));
+        assert!(parse_config_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_puzzle_editor_toggle_restores_analysis() {
+        let puzzle = parse_js(SINGLES_PUZZLE);
+        let mut editor = PuzzleEditor::new(puzzle.clone());
+        let original = editor.current_analysis();
+        assert_eq!(original, analyze_puzzle_difficulty(puzzle.clone()));
+
+        // Removing a clue and putting it back
+        assert!(editor.set_cell(4, 0));
+        assert_eq!(editor.cache.len(), 2);
+        assert!(editor.set_cell(4, 5));
+        assert_eq!(editor.current_analysis(), original);
+        assert_eq!(editor.board(), puzzle);
+        assert_eq!(editor.cache.len(), 2);
+
+        assert!(!editor.set_cell(81, 1));
+        assert!(!editor.set_cell(0, 10));
+    }
+
+    #[test]
+    fn test_puzzle_editor_defers_analysis_of_agreeing_clues() {
+        let puzzle = parse_js(STALLED_PUZZLE);
+        let solution = unique_solution(&from_js_board(&puzzle)).unwrap();
+        let mut editor = PuzzleEditor::new(puzzle.clone());
+        let level = editor.level_bound();
+        assert_eq!(editor.analyses_run, 1);
+
+        // Filling cells from the solution leaves the level as a bound
+        let empties: Vec<usize> = (0..BOARD_SIZE).filter(|&i| puzzle[i] == 0).collect();
+        for &index in &empties[..3] {
+            assert!(editor.set_cell(index, solution[index].unwrap()));
+        }
+        assert_eq!(editor.analyses_run, 1);
+        assert_eq!(editor.level_bound(), level);
+
+        // Asking for the analysis runs it once, for the latest board
+        let analysis = editor.current_analysis();
+        assert_eq!(analysis, analyze_puzzle_difficulty(editor.board()));
+        assert_eq!(editor.current_analysis(), analysis);
+        assert_eq!(editor.analyses_run, 2);
+
+        // A clue that contradicts the solution is analyzed straight away
+        let index = empties[3];
+        let wrong = solution[index].unwrap() % 9 + 1;
+        assert!(editor.set_cell(index, wrong));
+        assert_eq!(editor.analyses_run, 3);
+    }
+
+    #[test]
+    fn test_solve_puzzle_leaves_empty_board_empty() {
+        assert_eq!(solve_puzzle(vec![0; BOARD_SIZE]), vec![0; BOARD_SIZE]);
+
+        // A single clue is enough to count as a puzzle
+        let mut one_clue = vec![0; BOARD_SIZE];
+        one_clue[40] = 5;
+        let solved = solve_puzzle(one_clue);
+        assert_eq!(solved[40], 5);
+        assert!(solved.iter().all(|&cell| cell != 0));
+    }
+
+    #[test]
+    fn test_strict_solve_statuses() {
+        let solution = parse_js(SOLVED_GRID);
+        let mut puzzle = solution.clone();
+        puzzle[0] = 0;
+        assert_eq!(
+            strict_solve(&puzzle),
+            StrictSolveRecord {
+                status: "ok",
+                board: Some(solution.clone()),
+            }
+        );
+
+        // R1C4, R1C5, R4C4 and R4C5 hold 6 and 7 crosswise, so clearing
+        // them allows both ways round
+        let mut ambiguous = solution.clone();
+        for index in [3, 4, 30, 31] {
+            ambiguous[index] = 0;
+        }
+        assert_eq!(strict_solve(&ambiguous).status, "multiple_solutions");
+
+        // Row 1 holds every digit but 5, and R2C1 holds the 5, so R1C1 has
+        // no candidate left
+        let mut stuck = vec![0; BOARD_SIZE];
+        stuck[1..9].copy_from_slice(&solution[1..9]);
+        stuck[9] = 5;
+        let no_solution = strict_solve(&stuck);
+        assert_eq!(no_solution.status, "no_solution");
+        assert_eq!(no_solution.board, None);
+
+        assert_eq!(strict_solve(&solution[..80]).status, "malformed");
+        let mut repeated = solution.clone();
+        repeated[1] = 5;
+        assert_eq!(strict_solve(&repeated).status, "malformed");
+    }
+
+    #[test]
+    fn test_puzzle_bundle_round_trips_puzzle() {
+        let line = SINGLES_PUZZLE;
+        let board = parse_js(line);
+
+        let bundle: serde_json::Value =
+            serde_json::from_str(&export_puzzle_bundle(board.clone())).unwrap();
+        assert_eq!(bundle["puzzle"], line);
+        for field in ["level", "hardest_technique", "step_count", "confidence"] {
+            assert!(!bundle["analysis"][field].is_null(), "missing {}", field);
+        }
+        let solution = bundle["solution"].as_str().unwrap();
+        assert_eq!(solution.len(), BOARD_SIZE);
+        assert!(!solution.contains('.'));
+        assert_eq!(bundle["fingerprint"], fingerprint(&from_js_board(&board)));
+
+        assert!(export_puzzle_bundle(vec![0; BOARD_SIZE]).contains("error"));
+    }
+
+    #[test]
+    fn test_same_givens_ignores_player_fills() {
+        let puzzle = parse_js(SINGLES_PUZZLE);
+        let mut progress = puzzle.clone();
+        progress[0] = 3;
+        progress[1] = 4;
+
+        assert!(same_givens(puzzle.clone(), progress.clone(), true));
+        assert!(same_givens(progress.clone(), puzzle.clone(), false));
+        // The progress board's fills aren't givens of the puzzle
+        assert!(!same_givens(progress.clone(), puzzle.clone(), true));
+
+        let other = parse_js(STALLED_PUZZLE);
+        assert!(!same_givens(other, progress, true));
+    }
+
+    #[test]
+    fn test_analysis_rejects_bad_values_before_solving() {
+        // Out-of-range values would index past the candidate masks
+        let mut board: Vec<u8> = vec![0; BOARD_SIZE];
+        board[0] = 12;
+        let analysis: serde_json::Value =
+            serde_json::from_str(&analyze_puzzle_difficulty(board)).unwrap();
+        assert!(analysis["error"].is_string());
+    }
+
+    #[test]
+    fn test_thresholds_out_of_order_are_rejected() {
+        let board: Vec<u8> = vec![0; BOARD_SIZE];
+        // 1.0 is below the default very_easy band of 1.7
+        let analysis: serde_json::Value = serde_json::from_str(&analyze_puzzle_with_thresholds(
+            board.clone(),
+            r#"{"easy":1.0}"#.to_string(),
+        ))
+        .unwrap();
+        assert!(analysis["error"].as_str().unwrap().contains("easy"));
+
+        let analysis: serde_json::Value = serde_json::from_str(&analyze_puzzle_with_thresholds(
+            board,
+            r#"{"easy":2.5}"#.to_string(),
+        ))
+        .unwrap();
+        assert!(analysis["error"].is_null());
+    }
+
+    #[test]
+    fn test_describe_board_layout() {
+        let board = parse_js(SINGLES_PUZZLE);
+        let description = describe_board(board);
+        let lines: Vec<&str> = description.lines().collect();
+
+        let rows: Vec<&&str> = lines.iter().filter(|line| line.contains(" | ")).collect();
+        assert_eq!(rows.len(), 9);
+        assert_eq!(lines[0], ". . . | . 5 . | 6 2 .");
+        assert_eq!(lines[3], "------+-------+------");
+        assert_eq!(lines.len(), 12);
+        assert!(lines[11].starts_with("Clues: 37, Level: "));
+        assert!(lines[11].ends_with(", Solutions: 1"));
+
+        assert_eq!(describe_board(vec![0; 3]), "Invalid board size: 3 cells");
+    }
+
+    #[test]
+    fn test_custom_generator_reaches_open_clue_counts() {
+        let generator = custom_generator(0, 55, 55, true).unwrap();
+        let puzzle = generator.generate_with_seed(7).expect("open puzzle");
+        assert_eq!(puzzle.iter().filter(|cell| cell.is_some()).count(), 55);
+        assert!(has_unique_solution(&puzzle));
+    }
+
+    #[test]
+    fn test_custom_generator_rejects_sixteen_clues() {
+        let err = custom_generator(2, 16, 30, false).err();
+        assert_eq!(err, Some(ConfigError::TooFewClues { min_clues: 16 }));
+        assert!(err.unwrap().to_string().contains("fewer than 17 clues"));
+        assert!(custom_generator(2, 17, 30, false).is_ok());
+    }
+
+    #[test]
+    fn test_custom_generator_rejects_impossible_clue_ranges() {
+        assert_eq!(
+            custom_generator(0, 40, BOARD_SIZE, true).err(),
+            Some(ConfigError::TooManyClues { max_clues: 81 })
+        );
+        assert_eq!(
+            custom_generator(0, 40, 30, true).err(),
+            Some(ConfigError::ClueRangeInverted {
+                min_clues: 40,
+                max_clues: 30
+            })
+        );
+        assert!(custom_generator(0, 40, BOARD_SIZE - 1, true).is_ok());
+    }
+
+    #[test]
+    fn test_level_numeric_matches_level() {
+        let levels = [
+            DifficultyLevel::VeryEasy,
+            DifficultyLevel::Easy,
+            DifficultyLevel::Medium,
+            DifficultyLevel::Hard,
+            DifficultyLevel::Expert,
+        ];
+        for (expected, level) in (1..=5).zip(levels) {
+            let analysis = DifficultyAnalysis {
+                level,
+                hardest_technique: SolvingTechnique::NakedSingle,
+                technique_diversity: 1,
+                branching_factor: 2.0,
+                average_branching_during_solve: 1.5,
+                step_count: 40,
+                estimated_seconds: 200.0,
+                confidence: Confidence::Solved,
+            };
+            let json = difficulty_json(&analysis);
+            assert!(json.contains(&format!(r#""level": "{}""#, level.name())));
+            assert!(json.contains(&format!(r#""level_numeric": {},"#, expected)));
+            assert_eq!(DifficultyLevel::from_legacy_number(expected), Some(level));
+        }
+        assert_eq!(DifficultyLevel::from_legacy_number(0), None);
+        assert_eq!(DifficultyLevel::from_legacy_number(6), None);
+    }
+
+    #[test]
+    fn test_board_from_cells_validation() {
+        let mut cells = vec![None; BOARD_SIZE];
+        cells[0] = Some(5.0);
+        let board = board_from_cells(cells.clone()).unwrap();
+        assert_eq!(board[0], Some(5));
+        assert_eq!(board[1], None);
+
+        assert_eq!(
+            board_from_cells(vec![None; 80]),
+            Err(InputError::InvalidLength {
+                expected: BOARD_SIZE,
+                actual: 80
+            })
+        );
+
+        for bad in [0.0, 10.0, 2.5] {
+            cells[3] = Some(bad);
+            assert_eq!(
+                board_from_cells(cells.clone()),
+                Err(InputError::InvalidValue {
+                    index: 3,
+                    value: bad as u8
+                })
+            );
+        }
+    }
+
     #[test]
     fn test_validate_empty_board() {
         let empty_board = vec![0; BOARD_SIZE];
@@ -3,6 +3,9 @@
 //! This module contains extensive tests that validate all aspects of our
 //! Sudoku implementation, from basic validation to complex puzzle generation.
 
+// Tests copy fixtures explicitly and spell out range checks
+#![allow(clippy::clone_on_copy, clippy::manual_range_contains)]
+
 use sudoku_wasm::{
     difficulty::analyze_difficulty, generator::PuzzleGenerator, solve_board,
     solver::HumanStyleSolver, types::DifficultyLevel, validate_board,
@@ -327,9 +330,20 @@ mod validation_tests {
         let mut bad_board = VALID_PUZZLE.clone();
         bad_board[0] = Some(10); // Invalid number > 9
 
-        // This should either be caught by type system or validation
-        // Since we use u8, 10 is valid for the type but invalid for Sudoku
-        // Our validation should catch logical conflicts, not type violations
+        // Since we use u8, 10 is valid for the type but invalid for Sudoku,
+        // so validation must flag the cell instead of ignoring it
+        let result = validate_board(&bad_board);
+        assert!(
+            result.invalid_indices.contains(&0),
+            "Out-of-range value should be reported as invalid"
+        );
+
+        bad_board[0] = Some(0);
+        let result = validate_board(&bad_board);
+        assert!(
+            result.invalid_indices.contains(&0),
+            "Some(0) is not a clue and should be reported as invalid"
+        );
     }
 }
 